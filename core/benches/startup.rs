@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use l10n_core::l10n::L10nBuilder;
+use l10n_core::locales::Locales;
+use std::fs;
+use tempfile::TempDir;
+
+const RESOURCES: usize = 20;
+const MESSAGES: usize = 50;
+
+fn locales() -> Locales {
+    Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap()
+}
+
+fn l10n_dir() -> TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    for locale in ["en", "fr"] {
+        let locale_dir = temp_dir.path().join(locale);
+        fs::create_dir(&locale_dir).unwrap();
+        for resource in 0..RESOURCES {
+            let source: String = (0..MESSAGES)
+                .map(|message| {
+                    format!("message-{message} = Message {message} for {{ $name }} [{locale}]\n")
+                })
+                .collect();
+            fs::write(locale_dir.join(format!("resource-{resource}.ftl")), source).unwrap();
+        }
+    }
+    temp_dir
+}
+
+fn startup(c: &mut Criterion) {
+    let temp_dir = l10n_dir();
+    let bytes = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+        .unwrap()
+        .build()
+        .unwrap()
+        .serialize()
+        .unwrap();
+
+    c.bench_function("parse", |b| {
+        b.iter(|| {
+            let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+                .unwrap()
+                .build()
+                .unwrap();
+            black_box(l10n);
+        })
+    });
+
+    c.bench_function("deserialize", |b| {
+        b.iter(|| {
+            let l10n = L10nBuilder::deserialize(&bytes).unwrap().build().unwrap();
+            black_box(l10n);
+        })
+    });
+}
+
+criterion_group!(benches, startup);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use l10n_core::fluent_bundle::{FluentArgs, FluentResource};
+use l10n_core::l10n::{L10n, L10nBuilder};
+use l10n_core::locales::Locales;
+use l10n_core::unic_langid::langid;
+use std::path::Path;
+
+fn l10n() -> L10n {
+    let locale = langid!("en");
+    let mut builder = L10nBuilder::new(Locales::try_from([("en", None)]).unwrap());
+    let resource = FluentResource::try_new(
+        "greeting = Hello { $name }, you have { $count } new messages.\ntitle = Inbox".to_string(),
+    )
+    .unwrap();
+    builder
+        .add_named_resource("app", Path::new(""), &locale, resource)
+        .unwrap();
+    builder.resolve_literals(true).build().unwrap()
+}
+
+fn translate(c: &mut Criterion) {
+    let l10n = l10n();
+    let locale = langid!("en");
+    let mut args = FluentArgs::new();
+    args.set("name", "Alice");
+    args.set("count", 3);
+
+    c.bench_function("try_translate_with_args", |b| {
+        let mut out = String::new();
+        b.iter(|| {
+            out.clear();
+            let translation = l10n
+                .try_translate_with_args(&locale, "app", "greeting", Some(&args))
+                .unwrap();
+            out.push_str(&translation);
+            black_box(&out);
+        })
+    });
+
+    c.bench_function("write_translation", |b| {
+        let mut out = String::new();
+        b.iter(|| {
+            out.clear();
+            l10n.write_translation(&mut out, &locale, "app", "greeting", Some(&args))
+                .unwrap();
+            black_box(&out);
+        })
+    });
+
+    c.bench_function("try_translate_with_args_literal", |b| {
+        b.iter(|| {
+            black_box(
+                l10n.try_translate_with_args(&locale, "app", "title", None)
+                    .unwrap(),
+            );
+        })
+    });
+
+    c.bench_function("translate_raw", |b| {
+        b.iter(|| {
+            black_box(l10n.translate_raw("app", "title", &locale).unwrap());
+        })
+    });
+}
+
+criterion_group!(benches, translate);
+criterion_main!(benches);
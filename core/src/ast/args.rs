@@ -4,13 +4,31 @@ use std::collections::HashSet;
 use syn::parse::{Parse, ParseStream, Peek};
 use syn::token::Dot3;
 use syn::{
-    braced, bracketed, parenthesized, token, Error, Ident, Index, LitInt, LitStr, Result, Token,
+    braced, bracketed, parenthesized, token, Error, Ident, Index, LitBool, LitInt, LitStr, Result,
+    Token,
 };
+use unic_langid::LanguageIdentifier;
 
 #[derive(Clone, Default)]
 pub struct MessageArgs {
     args: Vec<Argument>,
+    enum_args: Vec<Argument>,
     incomplete: Option<Dot3>,
+    // `skip_none = true`, `Option` fields set to `None` are not passed as
+    // arguments
+    skip_none: Option<LitBool>,
+    // `locale = "en"`, the message is translated to this locale whatever the
+    // locale requested
+    locale: Option<LitStr>,
+    // `kebab_case = true`, shorthand arguments `first_name` are passed as
+    // `first-name`
+    kebab_case: Option<LitBool>,
+}
+
+mod kw {
+    syn::custom_keyword!(skip_none);
+    syn::custom_keyword!(locale);
+    syn::custom_keyword!(kebab_case);
 }
 
 #[derive(Clone)]
@@ -24,6 +42,14 @@ pub enum Argument {
         equal: Token![=],
         value: TokenStream,
     },
+    // `alias("name" = "target")`, the variable `name` receives the value of `target`.
+    Alias {
+        alias: Ident,
+        paren: token::Paren,
+        name: LitStr,
+        equal: Token![=],
+        target: LitStr,
+    },
 }
 
 impl MessageArgs {
@@ -32,14 +58,31 @@ impl MessageArgs {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.args.is_empty()
+        self.args.is_empty() && self.enum_args.is_empty()
     }
 
     pub fn is_complete(&self) -> bool {
         self.incomplete.is_none()
     }
 
+    pub fn skip_none(&self) -> Option<&LitBool> {
+        self.skip_none.as_ref()
+    }
+
+    pub fn locale(&self) -> Option<&LitStr> {
+        self.locale.as_ref()
+    }
+
+    pub fn kebab_case(&self) -> Option<&LitBool> {
+        self.kebab_case.as_ref()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &Argument> {
+        self.args.iter().chain(self.enum_args.iter())
+    }
+
+    // Arguments without the ones merged from the enum
+    pub fn iter_own(&self) -> impl Iterator<Item = &Argument> {
         self.args.iter()
     }
 
@@ -74,6 +117,22 @@ impl MessageArgs {
             return Err(err);
         }
 
+        if self.is_complete() {
+            for arg in &self.args {
+                if let Argument::Alias { target, .. } = arg {
+                    let is_value_argument = self
+                        .iter()
+                        .any(|arg| arg.name().value() == target.value() && arg.value().is_some());
+                    if !is_value_argument {
+                        return Err(Error::new_spanned(
+                            target,
+                            format!("aliased argument {} is not set", target.to_token_stream()),
+                        ));
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -89,13 +148,25 @@ impl MessageArgs {
     }
 
     pub fn merge_enum_arguments(&mut self, enum_arguments: &MessageArgs) {
+        if self.skip_none.is_none() {
+            self.skip_none = enum_arguments.skip_none.clone();
+        }
+        if self.locale.is_none() {
+            self.locale = enum_arguments.locale.clone();
+        }
+        if self.kebab_case.is_none() {
+            self.kebab_case = enum_arguments.kebab_case.clone();
+            if is_true(&self.kebab_case) {
+                self.args.iter_mut().for_each(Argument::rename_kebab_case);
+            }
+        }
         let current_argument_names = self
             .iter()
             .map(|arg| arg.name().to_owned())
             .collect::<HashSet<_>>();
         for argument in enum_arguments.args.iter() {
             if !current_argument_names.contains(argument.name()) {
-                self.args.push(argument.clone());
+                self.enum_args.push(argument.clone());
             }
         }
     }
@@ -105,6 +176,39 @@ impl Parse for MessageArgs {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut arguments = Self::default();
         while !input.is_empty() {
+            // A bare `skip_none` or `kebab_case` is a shorthand argument
+            if input.peek(kw::skip_none) && input.peek2(Token![=]) {
+                input.parse::<kw::skip_none>()?;
+                input.parse::<Token![=]>()?;
+                arguments.skip_none = Some(input.parse()?);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+            if input.peek(kw::kebab_case) && input.peek2(Token![=]) {
+                input.parse::<kw::kebab_case>()?;
+                input.parse::<Token![=]>()?;
+                arguments.kebab_case = Some(input.parse()?);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+            if input.peek(kw::locale) && input.peek2(Token![=]) {
+                input.parse::<kw::locale>()?;
+                input.parse::<Token![=]>()?;
+                let locale: LitStr = input.parse()?;
+                if let Err(err) = locale.value().parse::<LanguageIdentifier>() {
+                    return Err(Error::new_spanned(locale, err));
+                }
+                arguments.locale = Some(locale);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+
             arguments.incomplete = input.parse::<Option<Dot3>>()?;
             if let Some(incomplete) = arguments.incomplete {
                 if !input.is_empty() {
@@ -122,6 +226,13 @@ impl Parse for MessageArgs {
             }
         }
 
+        if is_true(&arguments.kebab_case) {
+            arguments
+                .args
+                .iter_mut()
+                .for_each(Argument::rename_kebab_case);
+        }
+
         Ok(arguments)
     }
 }
@@ -131,13 +242,30 @@ impl Argument {
         match self {
             Self::Short { name, .. } => name,
             Self::Long { name, .. } => name,
+            Self::Alias { name, .. } => name,
+        }
+    }
+
+    // `None` for an alias
+    pub fn value(&self) -> Option<&TokenStream> {
+        match self {
+            Self::Short { value, .. } => Some(value),
+            Self::Long { value, .. } => Some(value),
+            Self::Alias { .. } => None,
         }
     }
 
-    pub fn value(&self) -> &TokenStream {
+    pub fn alias_target(&self) -> Option<&LitStr> {
         match self {
-            Self::Short { value, .. } => value,
-            Self::Long { value, .. } => value,
+            Self::Alias { target, .. } => Some(target),
+            _ => None,
+        }
+    }
+
+    // Only shorthand arguments are named after a Rust identifier.
+    fn rename_kebab_case(&mut self) {
+        if let Self::Short { name, .. } = self {
+            *name = LitStr::new(&name.value().replace('_', "-"), name.span());
         }
     }
 
@@ -149,12 +277,57 @@ impl Argument {
                 equal.to_token_stream(),
                 value.to_token_stream(),
             ]),
+            Self::Alias {
+                alias,
+                paren,
+                name,
+                equal,
+                target,
+            } => {
+                let mut group = Group::new(
+                    Delimiter::Parenthesis,
+                    TokenStream::from_iter([
+                        name.to_token_stream(),
+                        equal.to_token_stream(),
+                        target.to_token_stream(),
+                    ]),
+                );
+                group.set_span(paren.span);
+                TokenStream::from_iter([alias.to_token_stream(), TokenTree::Group(group).into()])
+            }
         }
     }
 }
 
 impl Parse for Argument {
     fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Ident) && input.peek2(token::Paren) {
+            let alias: Ident = input.parse()?;
+            if alias != "alias" {
+                return Err(Error::new_spanned(
+                    alias,
+                    r#"expected an argument or an alias, example: `alias("variable" = "argument")`"#,
+                ));
+            }
+            let content;
+            let paren = parenthesized!(content in input);
+            let name = content.parse()?;
+            let equal = content.parse()?;
+            let target = content.parse()?;
+            if !content.is_empty() {
+                return Err(content.error(
+                    "expected a single alias, example: `alias(\"variable\" = \"argument\")`",
+                ));
+            }
+            return Ok(Argument::Alias {
+                alias,
+                paren,
+                name,
+                equal,
+                target,
+            });
+        }
+
         if input.peek(Ident) || (input.peek(Token![*]) && input.peek2(Ident)) {
             let unary: Option<Token![*]> = input.parse()?;
             let ident: Ident = input.parse()?;
@@ -190,6 +363,10 @@ impl Parse for Argument {
     }
 }
 
+fn is_true(option: &Option<LitBool>) -> bool {
+    option.as_ref().map_or(false, LitBool::value)
+}
+
 fn parse_argument_value<T: Peek>(
     input: ParseStream,
     mut begin_expr: bool,
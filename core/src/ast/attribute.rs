@@ -0,0 +1,107 @@
+use super::MessageArgs;
+use proc_macro2::Span;
+use syn::parse::ParseStream;
+use syn::{Attribute, Error, Ident, Lifetime, LitStr, Result, Token};
+
+/// `#[l10n_message('a, "resource", "key", key = "...", arguments...)]` or
+/// `#[l10n_message(transparent)]`, the literals are resolved by the derive
+/// since a single one is a key or a resource depending on the item.
+#[derive(Default)]
+pub struct L10nAttribute<'a> {
+    pub attribute: Option<&'a Attribute>,
+    pub transparent: Option<Span>,
+    pub self_lifetime: Option<Lifetime>,
+    pub first_literal: Option<LitStr>,
+    pub second_literal: Option<LitStr>,
+    // `key = "..."`, the key of an enum using the default resource
+    pub key: Option<LitStr>,
+    pub arguments: MessageArgs,
+}
+
+/// The `#[l10n_message(...)]` attribute of a type, variant or item, if any.
+pub fn parse_l10n_attribute(attrs: &[Attribute]) -> Result<Option<L10nAttribute<'_>>> {
+    let mut l10n_attribute: Option<L10nAttribute> = None;
+    for attr in attrs {
+        if attr.path.is_ident("l10n_message") {
+            if l10n_attribute.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "only one #[l10n_message(...)] attribute is allowed",
+                ));
+            }
+            l10n_attribute = Some(_parse_l10n_attribute(attr)?);
+        }
+    }
+    Ok(l10n_attribute)
+}
+
+fn _parse_l10n_attribute(attr: &Attribute) -> Result<L10nAttribute<'_>> {
+    syn::custom_keyword!(transparent);
+    syn::custom_keyword!(key);
+
+    attr.parse_args_with(|input: ParseStream| {
+        let mut l10n_attribute = L10nAttribute {
+            attribute: Some(attr),
+            transparent: None,
+            self_lifetime: None,
+            first_literal: None,
+            second_literal: None,
+            key: None,
+            arguments: Default::default(),
+        };
+
+        l10n_attribute.transparent = input
+            .parse::<Option<transparent>>()
+            .map(|r| r.map(|kw| kw.span))?;
+        if l10n_attribute.transparent.is_some() {
+            return Ok(l10n_attribute);
+        }
+
+        l10n_attribute.self_lifetime = input.parse()?;
+        if input.is_empty() {
+            return Ok(l10n_attribute);
+        } else if l10n_attribute.self_lifetime.is_some() {
+            input.parse::<Token![,]>()?;
+        }
+
+        if !peek_potential_argument(input) {
+            l10n_attribute.first_literal = input.parse()?;
+            if input.is_empty() {
+                return Ok(l10n_attribute);
+            } else if l10n_attribute.first_literal.is_some() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        if !peek_potential_argument(input) {
+            l10n_attribute.second_literal = input.parse()?;
+            if input.is_empty() {
+                return Ok(l10n_attribute);
+            } else if l10n_attribute.second_literal.is_some() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        if input.peek(key) && input.peek2(Token![=]) {
+            input.parse::<key>()?;
+            input.parse::<Token![=]>()?;
+            l10n_attribute.key = Some(input.parse()?);
+            if input.is_empty() {
+                return Ok(l10n_attribute);
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        if !input.is_empty() {
+            l10n_attribute.arguments = input.parse()?;
+        }
+
+        Ok(l10n_attribute)
+    })
+}
+
+fn peek_potential_argument(input: ParseStream) -> bool {
+    (input.peek(LitStr) && input.peek2(Token![=]))
+        || input.peek(Ident)
+        || (input.peek(Token![*]) && input.peek2(Ident))
+}
@@ -0,0 +1,82 @@
+use super::{MessageArgs, MessageKey};
+use proc_macro2::{TokenStream, TokenTree};
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, LitStr, Result, Token};
+
+/// `message!("resource", "key", arguments...)`
+pub struct MessageSyntax {
+    pub resource: LitStr,
+    pub key: MessageKey,
+    pub arguments: MessageArgs,
+}
+
+impl Parse for MessageSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let resource = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse::<LitStr>()?.into();
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(Self {
+            resource,
+            key,
+            arguments: input.parse()?,
+        })
+    }
+}
+
+/// `const_message!("resource", "key")`
+pub struct ConstMessageSyntax {
+    pub resource: LitStr,
+    pub key: MessageKey,
+}
+
+impl Parse for ConstMessageSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let resource = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse::<LitStr>()?.into();
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { resource, key })
+    }
+}
+
+/// `assert_translation!("resource", "key", locale, arguments... => expected)`
+pub struct AssertTranslationSyntax {
+    pub message: MessageSyntax,
+    pub locale: Expr,
+    pub expected: Expr,
+}
+
+impl Parse for AssertTranslationSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let resource = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse::<LitStr>()?.into();
+        input.parse::<Token![,]>()?;
+        let locale = input.parse()?;
+
+        let mut arguments_tokens = TokenStream::new();
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            while !input.is_empty() && !input.peek(Token![=>]) {
+                arguments_tokens.extend([input.parse::<TokenTree>()?]);
+            }
+        }
+        let arguments = syn::parse2(arguments_tokens)?;
+        input.parse::<Token![=>]>()?;
+        let expected = input.parse()?;
+
+        Ok(Self {
+            message: MessageSyntax {
+                resource,
+                key,
+                arguments,
+            },
+            locale,
+            expected,
+        })
+    }
+}
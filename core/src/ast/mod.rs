@@ -0,0 +1,12 @@
+// Syntax of the macros and of the `#[l10n_message(...)]` attribute, shared by
+// the macros and the extraction of the call sites.
+
+mod args;
+mod attribute;
+mod input;
+mod key;
+
+pub use args::{Argument, MessageArgs};
+pub use attribute::{parse_l10n_attribute, L10nAttribute};
+pub use input::{AssertTranslationSyntax, ConstMessageSyntax, MessageSyntax};
+pub use key::MessageKey;
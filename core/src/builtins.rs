@@ -0,0 +1,191 @@
+#[cfg(feature = "builtins")]
+use fluent_bundle::{bundle::FluentBundle, FluentArgs, FluentResource, FluentValue};
+#[cfg(feature = "builtins")]
+use intl_pluralrules::{operands::PluralOperands, PluralCategory, PluralRuleType, PluralRules};
+#[cfg(feature = "builtins")]
+use std::borrow::Borrow;
+#[cfg(feature = "builtins")]
+use unic_langid::LanguageIdentifier;
+
+#[cfg(feature = "builtins")]
+pub const FUNCTIONS: &[&str] = &["ORDINAL", "RAW_NUMBER"];
+#[cfg(not(feature = "builtins"))]
+pub const FUNCTIONS: &[&str] = &[];
+
+// Functions defined by the user take precedence over the builtin functions.
+#[cfg(feature = "builtins")]
+pub(crate) fn add_functions<R, M>(
+    bundle: &mut FluentBundle<R, M>,
+    locale: &LanguageIdentifier,
+    is_defined: impl Fn(&str) -> bool,
+) where
+    R: Borrow<FluentResource>,
+{
+    if !is_defined("ORDINAL") {
+        let rules = PluralRules::create(locale.clone(), PluralRuleType::ORDINAL).ok();
+        bundle
+            .add_function("ORDINAL", move |positional, _: &FluentArgs| {
+                ordinal(rules.as_ref(), positional.first())
+            })
+            .expect("Unexpected error, ORDINAL function should not be already defined");
+    }
+
+    if !is_defined("RAW_NUMBER") {
+        bundle
+            .add_function("RAW_NUMBER", |positional, _: &FluentArgs| {
+                raw_number(positional.first())
+            })
+            .expect("Unexpected error, RAW_NUMBER function should not be already defined");
+    }
+}
+
+// Returns a number as a string so the formatter does not apply (e.g. no
+// grouping), for identifiers and codes: `Order #{ RAW_NUMBER($id) }`.
+#[cfg(feature = "builtins")]
+fn raw_number<'a>(value: Option<&FluentValue>) -> FluentValue<'a> {
+    match value {
+        Some(FluentValue::Number(number)) => FluentValue::String(number.as_string()),
+        Some(FluentValue::String(string)) => FluentValue::from(string.to_string()),
+        _ => FluentValue::Error,
+    }
+}
+
+// Returns the CLDR ordinal category of a number (`one`, `two`, `few`...) to
+// be used as a selector, e.g. `{ ORDINAL($place) -> [one] {$place}st ... }`.
+#[cfg(feature = "builtins")]
+fn ordinal<'a>(rules: Option<&PluralRules>, value: Option<&FluentValue>) -> FluentValue<'a> {
+    let operands = match value {
+        Some(FluentValue::Number(number)) => PluralOperands::from(number),
+        Some(FluentValue::String(string)) => match PluralOperands::try_from(string.as_ref()) {
+            Ok(operands) => operands,
+            Err(_) => return FluentValue::Error,
+        },
+        _ => return FluentValue::Error,
+    };
+
+    let category = rules
+        .and_then(|rules| rules.select(operands).ok())
+        .unwrap_or(PluralCategory::OTHER);
+
+    FluentValue::from(match category {
+        PluralCategory::ZERO => "zero",
+        PluralCategory::ONE => "one",
+        PluralCategory::TWO => "two",
+        PluralCategory::FEW => "few",
+        PluralCategory::MANY => "many",
+        PluralCategory::OTHER => "other",
+    })
+}
+
+#[cfg(all(test, feature = "builtins"))]
+mod tests {
+    use crate::l10n::L10nBuilder;
+    use crate::locales::Locales;
+    use fluent_bundle::{FluentArgs, FluentValue};
+    use indoc::indoc;
+    use intl_memoizer::concurrent::IntlLangMemoizer;
+    use unic_langid::langid;
+
+    #[test]
+    fn ordinal() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "race.ftl": indoc! {r#"
+                    place = { ORDINAL($place) ->
+                        [one] {$place}st
+                        [two] {$place}nd
+                        [few] {$place}rd
+                       *[other] {$place}th
+                    }
+                "#}
+            },
+            "de": {
+                "race.ftl": indoc! {r#"
+                    place = { ORDINAL($place) ->
+                        [one] {$place}(one)
+                       *[other] {$place}.
+                    }
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("de", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let place = |locale, place| {
+            let mut args = FluentArgs::new();
+            args.set("place", place);
+            l10n.try_translate_with_args(locale, "race", "place", Some(&args))
+                .unwrap()
+                .to_string()
+        };
+
+        let en = &langid!("en");
+        assert_eq!(place(en, 1), "1st");
+        assert_eq!(place(en, 2), "2nd");
+        assert_eq!(place(en, 3), "3rd");
+        assert_eq!(place(en, 4), "4th");
+        assert_eq!(place(en, 11), "11th");
+        assert_eq!(place(en, 12), "12th");
+        assert_eq!(place(en, 13), "13th");
+        assert_eq!(place(en, 21), "21st");
+        assert_eq!(place(en, 22), "22nd");
+        assert_eq!(place(en, 23), "23rd");
+        assert_eq!(place(en, 111), "111th");
+
+        // No ordinal distinctions in German.
+        let de = &langid!("de");
+        assert_eq!(place(de, 1), "1.");
+        assert_eq!(place(de, 2), "2.");
+        assert_eq!(place(de, 3), "3.");
+    }
+
+    #[test]
+    fn raw_number() {
+        // Groups the thousands like a locale aware formatter would.
+        fn formatter(value: &FluentValue, _: &IntlLangMemoizer) -> Option<String> {
+            match value {
+                FluentValue::Number(number) => {
+                    let digits = number.as_string();
+                    let mut grouped = String::new();
+                    for (i, c) in digits.chars().enumerate() {
+                        if i > 0 && (digits.len() - i) % 3 == 0 {
+                            grouped.push(',');
+                        }
+                        grouped.push(c);
+                    }
+                    Some(grouped)
+                }
+                _ => None,
+            }
+        }
+
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "order.ftl": "order = Order #{ RAW_NUMBER($id) } of { $id } items"
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .set_formatter(Some(formatter))
+            .build()
+            .unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("id", 1234567);
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "order", "order", Some(&args))
+                .unwrap(),
+            "Order #1234567 of 1,234,567 items"
+        );
+    }
+}
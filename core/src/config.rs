@@ -6,6 +6,7 @@ use std::{
     path::{Path, PathBuf},
 };
 use thiserror::Error;
+use unic_langid::LanguageIdentifier;
 
 #[derive(Deserialize)]
 struct ConfigFile {
@@ -17,6 +18,18 @@ pub struct Config {
     #[serde(alias = "path", default = "default_paths")]
     pub paths: Paths,
     pub locales: Option<Locales>,
+    pub default_resource: Option<String>,
+    pub primary: Option<LanguageIdentifier>,
+    pub runtime_path: Option<PathBuf>,
+    // File or directory name patterns skipped when parsing, e.g. `*.wip.ftl`
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    // Named resources whose messages are added to every bundle
+    #[serde(default)]
+    pub shared: Vec<String>,
+    // Named resources skipped by the consistency checks
+    #[serde(default)]
+    pub complete: Vec<String>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -89,17 +102,39 @@ impl Default for Config {
         Self {
             paths: default_paths(),
             locales: None,
+            default_resource: None,
+            primary: None,
+            runtime_path: None,
+            ignore: Vec::new(),
+            shared: Vec::new(),
+            complete: Vec::new(),
         }
     }
 }
 
+const DEFAULT_PATHS: [&str; 3] = ["l10n", "locales", "i18n"];
+
 fn default_paths() -> Paths {
+    let default = match env::var("L10N_DEFAULT_PATH") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => discover_default_path(Path::new("")),
+    };
     Paths {
         environments: HashMap::new(),
-        default: PathBuf::from("l10n"),
+        default,
     }
 }
 
+// Returns the first existing directory of `DEFAULT_PATHS` in `root`,
+// `l10n` if none exists.
+fn discover_default_path(root: &Path) -> PathBuf {
+    let path = DEFAULT_PATHS
+        .iter()
+        .find(|path| root.join(path).is_dir())
+        .unwrap_or(&DEFAULT_PATHS[0]);
+    PathBuf::from(path)
+}
+
 pub fn config_file_path() -> Result<Option<PathBuf>, ConfigError> {
     let l10n_config_file = env::var("L10N_CONFIG_FILE");
     if let Ok(l10n_config_file) = &l10n_config_file {
@@ -123,17 +158,40 @@ pub fn config_file_path() -> Result<Option<PathBuf>, ConfigError> {
             .map_err(|source| ConfigError::ReadFile { path, source });
     }
 
-    let l10n_path = root.join("l10n.toml");
-    if let Ok(path) = l10n_path.canonicalize() {
-        return Ok(Some(path));
-    }
+    Ok(find_config_file(&root))
+}
+
+// Looks for a configuration file in `root` and, when `root` is part of a
+// workspace, in its parents up to the workspace root like Cargo does for
+// `Cargo.toml`. The generic `config.toml` name is only looked for in `root`,
+// e.g. not to pick up `~/.cargo/config.toml` for a crate in the registry.
+fn find_config_file(root: &Path) -> Option<PathBuf> {
+    let dirs: Vec<&Path> = match root.ancestors().position(is_workspace_root) {
+        Some(position) => root.ancestors().take(position + 1).collect(),
+        None => vec![root],
+    };
 
-    let config_path = root.join("config.toml");
-    if let Ok(path) = config_path.canonicalize() {
-        return Ok(Some(path));
+    for dir in dirs {
+        if let Ok(path) = dir.join("l10n.toml").canonicalize() {
+            return Some(path);
+        }
+
+        if dir == root {
+            if let Ok(path) = dir.join("config.toml").canonicalize() {
+                return Some(path);
+            }
+        }
     }
 
-    Ok(None)
+    None
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| manifest.parse::<toml::Value>().ok())
+        .map(|manifest| manifest.get("workspace").is_some())
+        .unwrap_or(false)
 }
 
 pub fn get_config() -> Result<Config, ConfigError> {
@@ -157,6 +215,9 @@ pub fn get_config() -> Result<Config, ConfigError> {
             .environments
             .iter_mut()
             .for_each(|(_, path)| replace_root_var_in_path(path, &config_path));
+        if let Some(path) = &mut config.runtime_path {
+            replace_root_var_in_path(path, &config_path);
+        }
 
         config
     } else {
@@ -180,6 +241,21 @@ fn replace_root_var_in_path(path: &mut PathBuf, root_path: &Path) {
     }
 }
 
+// `$EXE_DIR` is replaced at runtime by the directory of the current executable.
+pub fn replace_exe_dir_var<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    if !path.is_absolute() && path.starts_with("$EXE_DIR") {
+        let unprefixed_path = path.strip_prefix("$EXE_DIR").unwrap();
+        let exe_path = env::current_exe().unwrap_or_default();
+        match exe_path.parent() {
+            Some(parent) => parent.join(unprefixed_path),
+            None => PathBuf::from("/").join(unprefixed_path),
+        }
+    } else {
+        path.to_path_buf()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,6 +292,12 @@ mod tests {
                 ])
                 .unwrap(),
             ),
+            default_resource: None,
+            primary: None,
+            runtime_path: None,
+            ignore: Vec::new(),
+            shared: Vec::new(),
+            complete: Vec::new(),
         };
         let actual = deserialize_translator_config(config).unwrap();
         assert_eq!(actual, expected);
@@ -229,6 +311,12 @@ mod tests {
                 default: PathBuf::from("l10n"),
             },
             locales: None,
+            default_resource: None,
+            primary: None,
+            runtime_path: None,
+            ignore: Vec::new(),
+            shared: Vec::new(),
+            complete: Vec::new(),
         };
         let actual = deserialize_translator_config(config).unwrap();
         assert_eq!(actual, expected);
@@ -243,9 +331,233 @@ mod tests {
                 default: PathBuf::from("$ROOT/l10n"),
             },
             locales: None,
+            default_resource: None,
+            primary: None,
+            runtime_path: None,
+            ignore: Vec::new(),
+            shared: Vec::new(),
+            complete: Vec::new(),
         };
         let actual = deserialize_translator_config(config).unwrap();
         assert_eq!(actual, expected);
+
+        let config = r#"
+            [l10n]
+            default_resource = "home"
+        "#;
+        let expected = Config {
+            paths: Paths {
+                environments: HashMap::new(),
+                default: PathBuf::from("l10n"),
+            },
+            locales: None,
+            default_resource: Some("home".to_string()),
+            primary: None,
+            runtime_path: None,
+            ignore: Vec::new(),
+            shared: Vec::new(),
+            complete: Vec::new(),
+        };
+        let actual = deserialize_translator_config(config).unwrap();
+        assert_eq!(actual, expected);
+
+        let config = r#"
+            [l10n]
+            locales = ["en", "fr"]
+            primary = "fr"
+        "#;
+        let expected = Config {
+            paths: Paths {
+                environments: HashMap::new(),
+                default: PathBuf::from("l10n"),
+            },
+            locales: Some(Locales::try_from([("en", None), ("fr", None)]).unwrap()),
+            default_resource: None,
+            primary: Some("fr".parse().unwrap()),
+            runtime_path: None,
+            ignore: Vec::new(),
+            shared: Vec::new(),
+            complete: Vec::new(),
+        };
+        let actual = deserialize_translator_config(config).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn deserialize_config_runtime_path() {
+        let config = r#"
+            [l10n]
+            runtime_path = "$EXE_DIR/l10n"
+        "#;
+        let actual = deserialize_translator_config(config).unwrap();
+        assert_eq!(actual.runtime_path, Some(PathBuf::from("$EXE_DIR/l10n")));
+    }
+
+    #[test]
+    fn deserialize_config_ignore() {
+        let config = r#"
+            [l10n]
+            ignore = ["*.wip.ftl", "drafts"]
+        "#;
+        let actual = deserialize_translator_config(config).unwrap();
+        assert_eq!(actual.ignore, vec!["*.wip.ftl", "drafts"]);
+    }
+
+    #[test]
+    fn deserialize_config_shared() {
+        let config = r#"
+            [l10n]
+            shared = ["common"]
+        "#;
+        let actual = deserialize_translator_config(config).unwrap();
+        assert_eq!(actual.shared, vec!["common"]);
+    }
+
+    #[test]
+    fn deserialize_config_complete() {
+        let config = r#"
+            [l10n]
+            complete = ["settings"]
+        "#;
+        let actual = deserialize_translator_config(config).unwrap();
+        assert_eq!(actual.complete, vec!["settings"]);
+    }
+
+    #[test]
+    fn replace_exe_dir() {
+        let exe_dir = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+        assert_eq!(replace_exe_dir_var("$EXE_DIR/l10n"), exe_dir.join("l10n"));
+        assert_eq!(replace_exe_dir_var("$EXE_DIR"), exe_dir);
+        assert_eq!(replace_exe_dir_var("l10n"), PathBuf::from("l10n"));
+        assert_eq!(replace_exe_dir_var("/l10n"), PathBuf::from("/l10n"));
+    }
+
+    #[test]
+    fn find_config_file_in_parents() {
+        let temp_dir = macro_files::create_temp!({
+            "l10n.toml": "[l10n]",
+            "workspace": {
+                "Cargo.toml": "[workspace]",
+                "crates": {
+                    "first": {
+                        "Cargo.toml": "[package]",
+                        "l10n.toml": "[l10n]",
+                    },
+                    "second": {
+                        "Cargo.toml": "[package]",
+                    },
+                },
+            },
+            "workspace-with-config": {
+                "Cargo.toml": "[workspace]",
+                "l10n.toml": "[l10n]",
+                "crate": {
+                    "Cargo.toml": "[package]",
+                    "config.toml": "[l10n]",
+                },
+                "other-crate": {
+                    "Cargo.toml": "[package]",
+                },
+            },
+            "workspace-with-generic-config": {
+                "Cargo.toml": "[workspace]",
+                "config.toml": "[l10n]",
+                "crate": {
+                    "Cargo.toml": "[package]",
+                },
+            },
+        })
+        .unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+
+        assert_eq!(
+            find_config_file(&root.join("workspace/crates/first")),
+            Some(root.join("workspace/crates/first/l10n.toml"))
+        );
+        // Stop at the workspace root.
+        assert_eq!(
+            find_config_file(&root.join("workspace/crates/second")),
+            None
+        );
+        assert_eq!(
+            find_config_file(&root.join("workspace-with-config/crate")),
+            Some(root.join("workspace-with-config/crate/config.toml"))
+        );
+        assert_eq!(
+            find_config_file(&root.join("workspace-with-config/other-crate")),
+            Some(root.join("workspace-with-config/l10n.toml"))
+        );
+        // `config.toml` is only looked for in the crate directory.
+        assert_eq!(
+            find_config_file(&root.join("workspace-with-generic-config/crate")),
+            None
+        );
+    }
+
+    #[test]
+    fn find_config_file_without_workspace() {
+        // A crate outside any workspace, e.g. a dependency unpacked in the
+        // Cargo registry next to the Cargo configuration.
+        let temp_dir = macro_files::create_temp!({
+            "config.toml": "[net]",
+            "l10n.toml": "[l10n]",
+            "registry": {
+                "dependency": {
+                    "Cargo.toml": "[package]",
+                },
+                "dependency-with-config": {
+                    "Cargo.toml": "[package]",
+                    "config.toml": "[l10n]",
+                },
+            },
+        })
+        .unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+
+        assert_eq!(find_config_file(&root.join("registry/dependency")), None);
+        assert_eq!(
+            find_config_file(&root.join("registry/dependency-with-config")),
+            Some(root.join("registry/dependency-with-config/config.toml"))
+        );
+    }
+
+    #[test]
+    fn discover_default_path_without_config() {
+        let temp_dir = macro_files::create_temp!({
+            "locales-only": {
+                "locales": { "en": { "app.ftl": "key = Key" } },
+            },
+            "i18n-only": {
+                "i18n": { "en": { "app.ftl": "key = Key" } },
+            },
+            "all": {
+                "i18n": { "en": { "app.ftl": "key = Key" } },
+                "l10n": { "en": { "app.ftl": "key = Key" } },
+                "locales": { "en": { "app.ftl": "key = Key" } },
+            },
+            "none": {
+                "Cargo.toml": "[package]",
+            },
+        })
+        .unwrap();
+        let root = temp_dir.path();
+
+        assert_eq!(
+            discover_default_path(&root.join("locales-only")),
+            PathBuf::from("locales")
+        );
+        assert_eq!(
+            discover_default_path(&root.join("i18n-only")),
+            PathBuf::from("i18n")
+        );
+        assert_eq!(
+            discover_default_path(&root.join("all")),
+            PathBuf::from("l10n")
+        );
+        assert_eq!(
+            discover_default_path(&root.join("none")),
+            PathBuf::from("l10n")
+        );
     }
 
     #[test]
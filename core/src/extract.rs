@@ -0,0 +1,386 @@
+use crate::ast::{
+    parse_l10n_attribute, AssertTranslationSyntax, ConstMessageSyntax, L10nAttribute, MessageArgs,
+    MessageKey, MessageSyntax,
+};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Attribute, ItemEnum, ItemStruct, LitStr};
+
+/// A translatable message found in Rust source, the data needed to extract
+/// the messages of a crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    pub kind: CallSiteKind,
+    /// `None` when the message uses the default resource of `l10n.toml`.
+    pub resource: Option<String>,
+    pub key: String,
+    /// Names of the arguments known at compile time, in declaration order.
+    pub arguments: Vec<String>,
+    /// 1-indexed line of the macro or of the `#[l10n_message]` attribute.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallSiteKind {
+    Message,
+    ConstMessage,
+    AssertTranslation,
+    /// The type (or `Type::Variant`) deriving `L10nMessage`.
+    Derive(String),
+}
+
+/// Call sites of `message!`, `const_message!`, `assert_translation!` and
+/// `#[derive(L10nMessage)]` in `file`, in the file order. The macros nested in
+/// other macros (e.g. `println!`) are found as well. Invalid call sites are
+/// skipped, they are reported by the macros at compile time.
+pub fn extract(file: &syn::File) -> Vec<CallSite> {
+    let mut visitor = Visitor::default();
+    visitor.visit_file(file);
+    visitor.call_sites
+}
+
+/// Same as [`extract`] from the source of a Rust file.
+pub fn extract_source(source: &str) -> syn::Result<Vec<CallSite>> {
+    Ok(extract(&syn::parse_file(source)?))
+}
+
+#[derive(Default)]
+struct Visitor {
+    call_sites: Vec<CallSite>,
+}
+
+impl<'ast> Visit<'ast> for Visitor {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Some(segment) = mac.path.segments.last() {
+            self.macro_call(&segment.ident.to_string(), &mac.tokens, mac.span());
+        }
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        if derives_l10n_message(&item.attrs) {
+            self.derive_struct(item);
+        }
+        visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        if derives_l10n_message(&item.attrs) {
+            self.derive_enum(item);
+        }
+        visit::visit_item_enum(self, item);
+    }
+}
+
+impl Visitor {
+    fn macro_call(&mut self, name: &str, tokens: &TokenStream, span: Span) {
+        let parsed = match name {
+            "message" => syn::parse2(tokens.clone())
+                .map(|message: MessageSyntax| (CallSiteKind::Message, message)),
+            "const_message" => syn::parse2(tokens.clone()).map(|message: ConstMessageSyntax| {
+                let message = MessageSyntax {
+                    resource: message.resource,
+                    key: message.key,
+                    arguments: MessageArgs::default(),
+                };
+                (CallSiteKind::ConstMessage, message)
+            }),
+            "assert_translation" => {
+                syn::parse2(tokens.clone()).map(|assert: AssertTranslationSyntax| {
+                    (CallSiteKind::AssertTranslation, assert.message)
+                })
+            }
+            // Not parsed by syn, e.g. `format!("{}", message!(...))`
+            _ => return self.nested_macro_calls(tokens.clone()),
+        };
+        if let Ok((kind, message)) = parsed {
+            self.call_sites.push(CallSite {
+                kind,
+                resource: Some(message.resource.value()),
+                key: message.key.value(),
+                arguments: argument_names(&message.arguments),
+                line: span.start().line,
+            });
+        }
+    }
+
+    fn nested_macro_calls(&mut self, tokens: TokenStream) {
+        let tokens: Vec<_> = tokens.into_iter().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            if let TokenTree::Group(group) = token {
+                let name = match i.checked_sub(2).map(|i| (&tokens[i], &tokens[i + 1])) {
+                    Some((TokenTree::Ident(name), TokenTree::Punct(bang)))
+                        if bang.as_char() == '!' =>
+                    {
+                        Some(name)
+                    }
+                    _ => None,
+                };
+                match name {
+                    Some(name) => self.macro_call(&name.to_string(), &group.stream(), name.span()),
+                    None => self.nested_macro_calls(group.stream()),
+                }
+            }
+        }
+    }
+
+    fn derive_struct(&mut self, item: &ItemStruct) {
+        let l10n_attribute = match parse_l10n_attribute(&item.attrs) {
+            Ok(Some(l10n_attribute)) => l10n_attribute,
+            _ => return,
+        };
+        let (resource, key) = match (
+            &l10n_attribute.first_literal,
+            &l10n_attribute.second_literal,
+        ) {
+            _ if l10n_attribute.transparent.is_some() || l10n_attribute.key.is_some() => return,
+            (Some(resource), Some(key)) => (Some(resource.value()), key.value()),
+            (Some(key), None) => (None, key.value()),
+            _ => return,
+        };
+        self.call_sites.push(CallSite {
+            kind: CallSiteKind::Derive(item.ident.to_string()),
+            resource,
+            key,
+            arguments: argument_names(&l10n_attribute.arguments),
+            line: attribute_line(&l10n_attribute, item.ident.span()),
+        });
+    }
+
+    // Follows the resolution of the derive: a variant without literal uses
+    // the message of the enum and `.attribute` keys the message id of the
+    // enum key.
+    fn derive_enum(&mut self, item: &ItemEnum) {
+        let enum_attribute = match parse_l10n_attribute(&item.attrs) {
+            Ok(l10n_attribute) => l10n_attribute.unwrap_or_default(),
+            Err(_) => return,
+        };
+        // A single literal on the enum is the resource, `key = "..."` is the
+        // key with the default resource unless a resource is given.
+        let (enum_resource, enum_key) = match (
+            &enum_attribute.first_literal,
+            &enum_attribute.second_literal,
+            &enum_attribute.key,
+        ) {
+            (Some(resource), Some(key), None) => (Some(resource), Some(key.clone())),
+            (Some(resource), None, key) => (Some(resource), key.clone()),
+            (None, None, key) => (None, key.clone()),
+            _ => return,
+        };
+
+        for variant in &item.variants {
+            let mut variant_attribute = match parse_l10n_attribute(&variant.attrs) {
+                Ok(l10n_attribute) => l10n_attribute.unwrap_or_default(),
+                Err(_) => continue,
+            };
+            let transparent = match variant_attribute.attribute {
+                Some(_) => variant_attribute.transparent,
+                None => enum_attribute.transparent,
+            };
+            if transparent.is_some() {
+                continue;
+            }
+            let (resource, key) = match (
+                &variant_attribute.first_literal,
+                &variant_attribute.second_literal,
+            ) {
+                (Some(resource), Some(key)) => (Some(resource), variant_key(&enum_key, key)),
+                (Some(key), None) => (enum_resource, variant_key(&enum_key, key)),
+                _ => (enum_resource, enum_key.clone().map(MessageKey::from)),
+            };
+            let key = match key {
+                Some(key) => key,
+                None => continue,
+            };
+            variant_attribute
+                .arguments
+                .merge_enum_arguments(&enum_attribute.arguments);
+            let line = match variant_attribute.attribute {
+                Some(_) => attribute_line(&variant_attribute, variant.ident.span()),
+                None => attribute_line(&enum_attribute, variant.ident.span()),
+            };
+            self.call_sites.push(CallSite {
+                kind: CallSiteKind::Derive(format!("{}::{}", item.ident, variant.ident)),
+                resource: resource.map(LitStr::value),
+                key: key.value(),
+                arguments: argument_names(&variant_attribute.arguments),
+                line,
+            });
+        }
+    }
+}
+
+fn variant_key(enum_key: &Option<LitStr>, variant_key: &LitStr) -> Option<MessageKey> {
+    MessageKey::from_enum_and_variant(enum_key, variant_key.clone()).ok()
+}
+
+fn attribute_line(l10n_attribute: &L10nAttribute, default: Span) -> usize {
+    l10n_attribute
+        .attribute
+        .map_or(default, |attr| attr.span())
+        .start()
+        .line
+}
+
+fn argument_names(arguments: &MessageArgs) -> Vec<String> {
+    arguments.iter().map(|arg| arg.name().value()).collect()
+}
+
+fn derives_l10n_message(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .any(|attr| {
+            attr.tokens.clone().into_iter().any(|token| match token {
+                TokenTree::Group(group) => group.stream().into_iter().any(
+                    |token| matches!(token, TokenTree::Ident(ident) if ident == "L10nMessage"),
+                ),
+                _ => false,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_source() {
+        let source = r#"
+            use l10n::{message, L10nMessage};
+
+            #[derive(L10nMessage)]
+            #[l10n_message("settings", "title", "user-name" = self.name.as_str(), count)]
+            struct Title {
+                name: String,
+                count: usize,
+            }
+
+            #[derive(L10nMessage)]
+            #[l10n_message("errors", "invalid", kebab_case = true, field_name)]
+            enum Error {
+                #[l10n_message(".email", alias("address" = "field-name"))]
+                Email { field_name: String },
+                #[l10n_message(transparent)]
+                Other(Title),
+                Unknown,
+            }
+
+            fn main() {
+                let name = "Alice";
+                let message = message!("home", "greeting", name, "count" = 3.max(2), ...);
+                println!("{}", l10n::message!("home", "welcome"));
+                static TITLE: l10n::StaticMessage = l10n::const_message!("home", "title");
+                assert_translation!("home", "greeting", &langid!("en"), name => "Hi Alice");
+            }
+        "#;
+
+        let call_site = |kind, resource: &str, key: &str, arguments: &[&str], line| CallSite {
+            kind,
+            resource: Some(resource.to_string()),
+            key: key.to_string(),
+            arguments: arguments.iter().map(|name| name.to_string()).collect(),
+            line,
+        };
+        assert_eq!(
+            super::extract_source(source).unwrap(),
+            vec![
+                call_site(
+                    CallSiteKind::Derive("Title".to_string()),
+                    "settings",
+                    "title",
+                    &["user-name", "count"],
+                    5
+                ),
+                call_site(
+                    CallSiteKind::Derive("Error::Email".to_string()),
+                    "errors",
+                    "invalid.email",
+                    &["address", "field-name"],
+                    14
+                ),
+                call_site(
+                    CallSiteKind::Derive("Error::Unknown".to_string()),
+                    "errors",
+                    "invalid",
+                    &["field-name"],
+                    12
+                ),
+                call_site(
+                    CallSiteKind::Message,
+                    "home",
+                    "greeting",
+                    &["name", "count"],
+                    23
+                ),
+                call_site(CallSiteKind::Message, "home", "welcome", &[], 24),
+                call_site(CallSiteKind::ConstMessage, "home", "title", &[], 25),
+                call_site(
+                    CallSiteKind::AssertTranslation,
+                    "home",
+                    "greeting",
+                    &["name"],
+                    26
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn derive_options() {
+        let source = r#"
+            #[derive(L10nMessage)]
+            #[l10n_message("settings", "profile", skip_none = true, kebab_case = true, user_id)]
+            enum Profile {
+                #[l10n_message(".name", first_name, "last-name" = last_name)]
+                Name { user_id: u32, first_name: String, last_name: Option<String> },
+            }
+        "#;
+
+        let call_sites = super::extract_source(source).unwrap();
+        assert_eq!(call_sites[0].key, "profile.name");
+        assert_eq!(
+            call_sites[0].arguments,
+            vec!["first-name", "last-name", "user-id"]
+        );
+    }
+
+    #[test]
+    fn default_resource() {
+        let source = r#"
+            #[derive(L10nMessage)]
+            #[l10n_message("title")]
+            struct Title;
+
+            #[derive(L10nMessage)]
+            #[l10n_message(key = "status")]
+            enum Status {
+                #[l10n_message(".active")]
+                Active,
+                Inactive,
+            }
+
+            #[derive(L10nMessage)]
+            #[l10n_message("settings")]
+            enum Theme {
+                #[l10n_message("theme.dark")]
+                Dark,
+            }
+        "#;
+
+        let call_sites = super::extract_source(source).unwrap();
+        assert_eq!(
+            call_sites
+                .iter()
+                .map(|call_site| (call_site.resource.as_deref(), call_site.key.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, "title"),
+                (None, "status.active"),
+                (None, "status"),
+                (Some("settings"), "theme.dark")
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+// Escapes a translation to be inserted in HTML, in text or in a quoted
+// attribute value.
+//
+// The whole translation is escaped (message text and argument values), except
+// the occurrences of the `markup` values which are copied as is. The isolation
+// marks (U+2068 and U+2069) are not special characters in HTML and are kept
+// around the arguments, including trusted markup, so they never split an
+// entity or a tag.
+//
+// A trusted markup is matched by value and not by position, so an untrusted
+// argument equal to a trusted markup is also copied as is, this is harmless
+// since it is the trusted markup itself.
+pub(crate) fn escape(translation: &str, markup: &[&str]) -> String {
+    let mut markup: Vec<_> = markup.iter().filter(|markup| !markup.is_empty()).collect();
+    // Longest first so a markup containing another one is matched entirely.
+    markup.sort_by_key(|markup| std::cmp::Reverse(markup.len()));
+
+    let mut escaped = String::with_capacity(translation.len());
+    let mut rest = translation;
+    while let Some(c) = rest.chars().next() {
+        if let Some(markup) = markup.iter().find(|markup| rest.starts_with(**markup)) {
+            escaped.push_str(markup);
+            rest = &rest[markup.len()..];
+            continue;
+        }
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::l10n::L10nBuilder;
+    use crate::l10n_message::L10nMessage;
+    use crate::locales::Locales;
+    use crate::message::Message;
+    use fluent_bundle::FluentArgs;
+    use unic_langid::langid;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            escape(r#"<b>Tom & "Jerry's"</b>"#, &[]),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&#39;s&quot;&lt;/b&gt;"
+        );
+        assert_eq!(
+            escape("\u{2068}<i>\u{2069} & \u{2068}x\u{2069}", &[]),
+            "\u{2068}&lt;i&gt;\u{2069} &amp; \u{2068}x\u{2069}"
+        );
+        assert_eq!(escape("&amp;", &[""]), "&amp;amp;");
+    }
+
+    #[test]
+    fn trusted_markup() {
+        let link = r#"<a href="/terms">terms</a>"#;
+        assert_eq!(
+            escape(
+                &format!("Accept the \u{2068}{}\u{2069} & <continue>", link),
+                &[link]
+            ),
+            format!("Accept the \u{2068}{}\u{2069} &amp; &lt;continue&gt;", link)
+        );
+        assert_eq!(escape("<a><b>", &["<a>", "<a><b>"]), "<a><b>");
+    }
+
+    #[test]
+    fn translate_html() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "accept = <{ $name }> accept the { $link } & continue"
+            },
+        })
+        .unwrap();
+        let l10n = L10nBuilder::parse(
+            temp_dir.path(),
+            Some(Locales::try_from([("en", None)]).unwrap()),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let en = langid!("en");
+        let link = r#"<a href="/terms">terms</a>"#;
+        let args = FluentArgs::from_iter([("name", "<script>"), ("link", link)]);
+        assert_eq!(
+            l10n.try_translate_html(&en, "app", "accept", Some(&args), &[link])
+                .unwrap(),
+            format!(
+                "&lt;\u{2068}&lt;script&gt;\u{2069}&gt; accept the \u{2068}{}\u{2069} &amp; continue",
+                link
+            )
+        );
+
+        let message = Message::new(&l10n, "app", "accept", Some(args));
+        assert_eq!(
+            message.translate_html(&en),
+            "&lt;\u{2068}&lt;script&gt;\u{2069}&gt; accept the \u{2068}&lt;a href=&quot;/terms&quot;&gt;terms&lt;/a&gt;\u{2069} &amp; continue"
+        );
+        assert_eq!(
+            message.translate_html_with_markup(&en, &[link]),
+            format!(
+                "&lt;\u{2068}&lt;script&gt;\u{2069}&gt; accept the \u{2068}{}\u{2069} &amp; continue",
+                link
+            )
+        );
+    }
+}
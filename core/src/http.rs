@@ -0,0 +1,122 @@
+use crate::l10n::{L10nBuilder, ParserError};
+use crate::locales::Locales;
+
+impl L10nBuilder {
+    /// Same as [`L10nBuilder::from_sources`] with the fluent files fetched
+    /// from `base_url`. The `manifest` file, relative to `base_url`, lists the
+    /// paths of the fluent files one per line, e.g. `en/settings/account.ftl`,
+    /// empty lines and lines starting with `#` are skipped. The `https` URLs
+    /// are verified with the Mozilla root certificates (rustls), not the ones
+    /// of the operating system.
+    ///
+    /// The files are fetched on every call and are not cached. The first
+    /// request failing or with an error status stops the parsing, nothing is
+    /// partially loaded. It blocks the current thread and must not be called
+    /// from an async runtime.
+    pub fn parse_url(
+        base_url: &str,
+        manifest: &str,
+        locales: Option<Locales>,
+    ) -> Result<Self, ParserError> {
+        let client = reqwest::blocking::Client::new();
+        let base_url = base_url.trim_end_matches('/');
+        let fetch = |path: &str| {
+            let url = format!("{}/{}", base_url, path.trim_start_matches('/'));
+            client
+                .get(&url)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.text())
+                .map_err(|err| ParserError::Fetch { url, err })
+        };
+
+        let manifest = fetch(manifest)?;
+        let sources = manifest
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|path| Ok((path, fetch(path)?)))
+            .collect::<Result<Vec<_>, ParserError>>()?;
+        Self::from_sources(sources, locales)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::l10n::{L10nBuilder, ParserError};
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use unic_langid::langid;
+
+    // Serves `files` until the test process exits, returns the base url.
+    fn mock_server(files: HashMap<&'static str, &'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(&stream);
+                reader.read_line(&mut request_line).unwrap();
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+                let path = request_line.split(' ').nth(1).unwrap_or_default();
+                let response = match files.get(path) {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        base_url
+    }
+
+    #[test]
+    fn parse_url() {
+        let base_url = mock_server(HashMap::from([
+            (
+                "/l10n/manifest.txt",
+                "# Translations\n_terms.ftl\nen/app.ftl\n\nfr/app.ftl\n",
+            ),
+            ("/l10n/_terms.ftl", "-brand = Brand"),
+            ("/l10n/en/app.ftl", "title = { -brand } app"),
+            ("/l10n/fr/app.ftl", "title = Appli { -brand }"),
+            ("/broken/manifest.txt", "en/app.ftl\nfr/app.ftl"),
+        ]));
+
+        let l10n = L10nBuilder::parse_url(&format!("{}/l10n/", base_url), "manifest.txt", None)
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "title", None)
+                .unwrap(),
+            "Brand app"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("fr"), "app", "title", None)
+                .unwrap(),
+            "Appli Brand"
+        );
+
+        match L10nBuilder::parse_url(&format!("{}/broken", base_url), "manifest.txt", None) {
+            Err(ParserError::Fetch { url, err }) => {
+                assert_eq!(url, format!("{}/broken/en/app.ftl", base_url));
+                assert_eq!(err.status(), Some(reqwest::StatusCode::NOT_FOUND));
+            }
+            _ => panic!("expected a fetch error"),
+        }
+    }
+}
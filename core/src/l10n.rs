@@ -1,43 +1,149 @@
 use crate::locales::Locales;
+use crate::pseudo::{self, PseudoOptions};
+use crate::resolver::BundleResolver;
 use crate::resource::L10nResource;
-use crate::utils::{for_locales, grammar_number, locales_to_string, values_to_string};
+use crate::utils::{
+    for_locales, grammar_number, locales_to_string, matches_pattern, values_to_string,
+};
 use fluent_bundle::{bundle::FluentBundle, FluentArgs, FluentResource};
 use fluent_bundle::{FluentError, FluentValue};
-use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement};
+use fluent_syntax::ast::{
+    Attribute, Entry, Expression, Identifier, InlineExpression, Pattern, PatternElement, VariantKey,
+};
 use intl_memoizer::concurrent::IntlLangMemoizer;
-use self_cell::self_cell;
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
 use std::ffi::OsStr;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::{
-    borrow::Cow,
-    collections::{HashMap, HashSet},
+    borrow::{Borrow, Cow},
+    collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::Debug,
     fs, io,
+    sync::{Arc, Weak},
 };
 use thiserror::Error;
 use unic_langid::LanguageIdentifier;
 
 type FluentResources = Vec<FluentResource>;
-type Resources<'s> = HashMap<String, L10nResource<&'s FluentResource>>;
+type Resources = HashMap<String, L10nResource<Arc<FluentResource>>>;
 type ResourceIndex = usize;
 type ResourceName = String;
 type GlobalUnnamedResources = Vec<ResourceIndex>;
 type UnnamedResources = HashMap<(String, LanguageIdentifier), Vec<ResourceIndex>>;
 type NamedResources = HashMap<ResourceName, HashMap<LanguageIdentifier, ResourceIndex>>;
+type LocaleTransforms = HashMap<LanguageIdentifier, fn(&str) -> Cow<str>>;
 type Functions = HashMap<String, for<'a> fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a>>;
-
-self_cell!(
-    struct InnerL10n {
-        owner: FluentResources,
-        #[covariant]
-        dependent: Resources,
-    }
-);
+type LocaleFunctions = HashMap<
+    String,
+    for<'a> fn(&LanguageIdentifier, &[FluentValue<'a>], &FluentArgs) -> FluentValue<'a>,
+>;
+type BundleFunctions = HashMap<
+    String,
+    for<'a> fn(&BundleResolver, &[FluentValue<'a>], &FluentArgs) -> FluentValue<'a>,
+>;
+type ArgsDefault = HashMap<String, String>;
+type LocaleArgsDefault = HashMap<LanguageIdentifier, ArgsDefault>;
+// Variables only in the first message, only in the second one and in both
+type ArgsDiff<'a> = (HashSet<&'a str>, HashSet<&'a str>, HashSet<&'a str>);
+type ResourcePaths = HashMap<ResourceIndex, PathBuf>;
+type CachePredicate = fn(&str, &str) -> bool;
+type Preprocessor = fn(&Path, String) -> String;
+type Postprocessor = fn(&LanguageIdentifier, String) -> String;
+#[cfg(feature = "metrics")]
+type FormatCounts =
+    std::sync::RwLock<HashMap<String, HashMap<String, std::sync::atomic::AtomicU64>>>;
+type Provenances = HashMap<(String, LanguageIdentifier, String), MessageProvenance>;
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+pub(crate) type Rebuilder = Box<dyn Fn() -> Result<L10n, ParserError> + Send>;
 
 pub struct L10n {
-    inner: InnerL10n,
+    // Shared by the bundles, a reloaded file only rebuilds the bundles using it
+    fluent_resources: Vec<Arc<FluentResource>>,
+    resources: Resources,
     pub locales: Locales,
+    args_default: ArgsDefault,
+    locale_args_default: LocaleArgsDefault,
+    pseudo_options: Option<PseudoOptions>,
+    postprocess: Option<Postprocessor>,
+    // Names of the functions added to the bundles, sorted
+    functions: Vec<String>,
+    // Kept to translate again the cached messages when a file is reloaded
+    cache_if: Option<CachePredicate>,
+    // Kept to rebuild the bundles when a file is reloaded
+    resources_index: ResourcesIndex,
+    bundle_options: BundleOptions,
+    paths: ResourcePaths,
+    preprocessor: Option<Preprocessor>,
+    provenances: Option<Provenances>,
+    case_insensitive_lookup: bool,
+    // Locale used by `translate_negotiated` for the unsupported locales
+    default_locale: Option<LanguageIdentifier>,
+    resolve_literals: bool,
+    // Kept to check the resources again when a file is reloaded
+    root: Option<PathBuf>,
+    complete_resources: Vec<String>,
+    check_terms: bool,
+    #[cfg(feature = "metrics")]
+    format_counts: FormatCounts,
+}
+
+/// Rough cost of the loaded translations, see [`L10n::estimated_memory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    /// Total size of the fluent sources.
+    pub source_bytes: usize,
+    /// Number of bundles, one per named resource and main locale.
+    pub bundles: usize,
+}
+
+/// The source providing a message once the resources of a bundle are merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageProvenance {
+    /// `None` for a global unnamed resource.
+    pub locale: Option<LanguageIdentifier>,
+    /// `None` for a resource not read from a file.
+    pub path: Option<PathBuf>,
+}
+
+/// A message of a named resource, see [`L10n::messages`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageInfo {
+    pub id: String,
+    /// The attributes defined in any locale.
+    pub attributes: BTreeSet<String>,
+    /// The main locales resolving a value for the message, including through
+    /// their fallbacks, sorted.
+    pub locales: Vec<LanguageIdentifier>,
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+struct ResourcesIndex {
+    global_unnamed_resources: GlobalUnnamedResources,
+    unnamed_resources: UnnamedResources,
+    named_resources: NamedResources,
+    // Named resources whose messages are added to every bundle
+    #[cfg_attr(feature = "serialize", serde(default))]
+    shared_resources: Vec<String>,
+}
+
+struct BundleOptions {
+    transform: Option<fn(&str) -> Cow<str>>,
+    locale_transforms: LocaleTransforms,
+    formatter: Option<fn(&FluentValue, &IntlLangMemoizer) -> Option<String>>,
+    use_isolating: bool,
+    functions: Functions,
+    locale_functions: LocaleFunctions,
+    bundle_functions: BundleFunctions,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedL10n<S> {
+    locales: Vec<(LanguageIdentifier, Option<LanguageIdentifier>)>,
+    sources: Vec<S>,
+    resources_index: ResourcesIndex,
 }
 
 pub struct L10nBuilder {
@@ -47,9 +153,38 @@ pub struct L10nBuilder {
     unnamed_resources: UnnamedResources,
     named_resources: NamedResources,
     transform: Option<fn(&str) -> Cow<str>>,
+    locale_transforms: LocaleTransforms,
     formatter: Option<fn(&FluentValue, &IntlLangMemoizer) -> Option<String>>,
     use_isolating: bool,
     functions: Functions,
+    locale_functions: LocaleFunctions,
+    bundle_functions: BundleFunctions,
+    primary_locale: Option<LanguageIdentifier>,
+    args_default: ArgsDefault,
+    locale_args_default: LocaleArgsDefault,
+    pseudo_options: Option<PseudoOptions>,
+    // The directory the builder was parsed from
+    root: Option<PathBuf>,
+    // Paths of the fluent resources read from files
+    paths: ResourcePaths,
+    preprocessor: Option<Preprocessor>,
+    postprocess: Option<Postprocessor>,
+    record_provenance: bool,
+    // Named resources used when missing from the parsed ones
+    embedded_defaults: Vec<(LanguageIdentifier, String, FluentResource)>,
+    cache_if: Option<CachePredicate>,
+    // File or directory name patterns skipped when parsing
+    ignore: Vec<String>,
+    case_insensitive_lookup: bool,
+    shared_resources: Vec<String>,
+    default_locale: Option<LanguageIdentifier>,
+    resolve_literals: bool,
+    // Named resources skipped by the consistency checks
+    complete_resources: Vec<String>,
+    check_terms: bool,
+    // Name and path of the parsed named resources by lowercased name and
+    // locale, to find a name collision with one lookup
+    lowercase_names: HashMap<(String, LanguageIdentifier), (String, PathBuf)>,
 }
 
 #[derive(Error, PartialEq, Eq, Debug)]
@@ -62,18 +197,22 @@ pub enum BuildError {
     MissingResource {
         resource: String,
         locales: Vec<LanguageIdentifier>,
+        // Expected file path for each locale
+        paths: Vec<PathBuf>,
     },
     #[error(r#"missing message "{message}" in resource "{resource}" for locales: {}"#, locales_to_string(.locales, ", "))]
     MissingMessage {
         resource: String,
         message: String,
         locales: Vec<LanguageIdentifier>,
+        paths: Vec<PathBuf>,
     },
     #[error(r#"extra message "{message}" in resource "{resource}" for locales: {}"#, locales_to_string(.locales, ", "))]
     ExtraMessage {
         resource: String,
         message: String,
         locales: Vec<LanguageIdentifier>,
+        paths: Vec<PathBuf>,
     },
     #[error(r#"missing attribute "{attribute}" for message "{message}" in resource "{resource}" for locales: {}"#, locales_to_string(.locales, ", "))]
     MissingAttribute {
@@ -81,6 +220,7 @@ pub enum BuildError {
         message: String,
         attribute: String,
         locales: Vec<LanguageIdentifier>,
+        paths: Vec<PathBuf>,
     },
     #[error(r#"extra attribute "{attribute}" for message "{message}" in resource "{resource}" for locales: {}"#, locales_to_string(.locales, ", "))]
     ExtraAttribute {
@@ -88,9 +228,73 @@ pub enum BuildError {
         message: String,
         attribute: String,
         locales: Vec<LanguageIdentifier>,
+        paths: Vec<PathBuf>,
+    },
+    #[error("no locales")]
+    NoLocales,
+    #[error(r#"primary locale "{locale}" is not a main locale"#)]
+    PrimaryNotMainLocale { locale: LanguageIdentifier },
+    #[error(r#"default locale "{locale}" is not a main locale"#)]
+    DefaultNotMainLocale { locale: LanguageIdentifier },
+    #[error(r#"missing plural category "{category}" for message "{message}" in resource "{resource}" for locale "{locale}""#)]
+    MissingPluralCategory {
+        resource: String,
+        message: String,
+        locale: LanguageIdentifier,
+        category: String,
+    },
+    #[error(r#"message "{message}" extends "{base}" which is not a message of its locale"#)]
+    UnknownBaseMessage { message: String, base: String },
+    #[error(r#"message "{message}" extends itself: {}"#, .cycle.join(" -> "))]
+    ExtendsCycle { message: String, cycle: Vec<String> },
+    #[error(r#"resource "{resource}" defined more than once for locale "{locale}" with a case-insensitive lookup"#)]
+    CaseInsensitiveConflict {
+        resource: String,
+        locale: LanguageIdentifier,
+    },
+    #[error(r#"shared resource "{resource}" is not a named resource"#)]
+    UnknownSharedResource { resource: String },
+    #[error("pseudo-localization accents replace the transforms, they cannot be combined")]
+    PseudoLocalizeTransform,
+    #[error(r#"missing term "-{term}" referenced by message "{message}" in resource "{resource}" for locales: {}"#, locales_to_string(.locales, ", "))]
+    MissingTerm {
+        resource: String,
+        message: String,
+        term: String,
+        locales: Vec<LanguageIdentifier>,
     },
 }
 
+impl BuildErrors {
+    pub fn errors(&self) -> &[BuildError] {
+        &self.0
+    }
+}
+
+impl BuildError {
+    /// Paths of the files expected to fix the error (one per locale), absolute
+    /// when the builder was parsed from a directory, relative to it otherwise.
+    pub fn paths(&self) -> &[PathBuf] {
+        match self {
+            Self::MissingResource { paths, .. }
+            | Self::MissingMessage { paths, .. }
+            | Self::ExtraMessage { paths, .. }
+            | Self::MissingAttribute { paths, .. }
+            | Self::ExtraAttribute { paths, .. } => paths,
+            Self::NoLocales
+            | Self::PrimaryNotMainLocale { .. }
+            | Self::DefaultNotMainLocale { .. }
+            | Self::MissingPluralCategory { .. }
+            | Self::UnknownBaseMessage { .. }
+            | Self::ExtendsCycle { .. }
+            | Self::CaseInsensitiveConflict { .. }
+            | Self::UnknownSharedResource { .. }
+            | Self::PseudoLocalizeTransform
+            | Self::MissingTerm { .. } => &[],
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ParserError {
     #[error("impossible to read path `{}` (io error: {err})", path.display())]
@@ -114,6 +318,38 @@ pub enum ParserError {
     FluentParser {
         errors: Vec<fluent_syntax::parser::ParserError>,
     },
+    #[error("file `{}` is not a loaded fluent resource", path.display())]
+    UnknownFile { path: PathBuf },
+    #[error(
+        r#"files `{}` and `{}` are both named resource "{name}" for locale "{locale}""#,
+        existing.display(),
+        path.display()
+    )]
+    ResourceNameCollision {
+        name: String,
+        locale: LanguageIdentifier,
+        existing: PathBuf,
+        path: PathBuf,
+    },
+    #[error(r#"named resource "{name}" already exists for locale "{locale}""#)]
+    NamedResourceExists {
+        name: String,
+        locale: LanguageIdentifier,
+    },
+    #[cfg(feature = "http")]
+    #[error("impossible to fetch `{url}` (http error: {err})")]
+    Fetch { url: String, err: reqwest::Error },
+    #[error(transparent)]
+    Build(#[from] BuildErrors),
+}
+
+/// Error returned when the translations of `init!` cannot be loaded.
+#[derive(Error, Debug)]
+pub enum InitError {
+    #[error(transparent)]
+    Parse(#[from] ParserError),
+    #[error(transparent)]
+    Build(#[from] BuildErrors),
 }
 
 #[derive(Error, PartialEq, Debug)]
@@ -122,6 +358,8 @@ pub enum TranslateError {
     ResourceNotExists(String),
     #[error(r#"locale "{locale}" not supported"#)]
     LocaleNotSupported { locale: LanguageIdentifier },
+    #[error("no locale pinned on the message")]
+    LocaleNotPinned,
     #[error(r#"message id: "{id}", not exists for locale "{locale}""#)]
     MessageIdNotExists {
         id: String,
@@ -140,8 +378,73 @@ pub enum TranslateError {
         id: String,
         locale: LanguageIdentifier,
     },
+    #[error(
+        r#"variant: "{variant}", not exists for variable "{variable}" in "{key}", for locale "{locale}""#
+    )]
+    VariantNotExists {
+        variant: String,
+        variable: String,
+        key: String,
+        locale: LanguageIdentifier,
+    },
     #[error("format errors:\n  - {}", values_to_string(.0, "\n  - "))]
-    FormatErrors(Vec<FluentError>),
+    FormatErrors(Vec<FormatError>),
+    #[error("write error: {0}")]
+    Write(#[from] fmt::Error),
+    #[error("transparent `{context}` failed to translate")]
+    Transparent {
+        context: &'static str,
+        source: Box<TranslateError>,
+    },
+}
+
+/// A fluent error with the message it occurred in.
+#[derive(Error, PartialEq, Debug)]
+#[error(r#"{source} (resource: "{resource}", key: "{key}", locale "{locale}")"#)]
+pub struct FormatError {
+    pub resource: String,
+    pub key: String,
+    pub locale: LanguageIdentifier,
+    pub source: FluentError,
+}
+
+impl TranslateError {
+    /// Whether the resource, locale or message does not exist, as opposed to
+    /// a message that exists but cannot be formatted.
+    pub fn is_missing(&self) -> bool {
+        match self {
+            Self::ResourceNotExists(_)
+            | Self::LocaleNotSupported { .. }
+            | Self::MessageIdNotExists { .. }
+            | Self::MessageAttributeNotExists { .. }
+            | Self::MessageIdValueNotExists { .. }
+            | Self::VariantNotExists { .. } => true,
+            Self::LocaleNotPinned | Self::FormatErrors(_) | Self::Write(_) => false,
+            Self::Transparent { source, .. } => source.is_missing(),
+        }
+    }
+
+    /// HTTP status code for web integrations: `404` for a missing
+    /// translation, `500` otherwise.
+    pub fn status_code(&self) -> u16 {
+        match self.is_missing() {
+            true => 404,
+            false => 500,
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Error, Debug)]
+pub enum SerializeError {
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+    #[error(transparent)]
+    Locales(#[from] crate::locales::InvariantError),
+    #[error("parsing errors: {errors:#?}")]
+    FluentParser {
+        errors: Vec<fluent_syntax::parser::ParserError>,
+    },
 }
 
 impl Debug for L10n {
@@ -155,645 +458,4731 @@ impl L10n {
         Self::check_consistency(&builder)?;
 
         let L10nBuilder {
-            locales,
+            mut locales,
             fluent_resources,
             global_unnamed_resources,
             unnamed_resources,
             named_resources,
             transform,
+            locale_transforms,
             formatter,
             use_isolating,
             functions,
+            locale_functions,
+            bundle_functions,
+            primary_locale,
+            args_default,
+            locale_args_default,
+            pseudo_options,
+            root,
+            paths,
+            preprocessor,
+            postprocess,
+            record_provenance,
+            embedded_defaults: _,
+            cache_if,
+            ignore: _,
+            case_insensitive_lookup,
+            mut shared_resources,
+            default_locale,
+            resolve_literals,
+            mut complete_resources,
+            check_terms,
+            lowercase_names: _,
         } = builder;
 
-        let inner_translator = InnerL10n::new(fluent_resources, |fluent_resources| {
-            named_resources
-                .iter()
-                .map(|(name, _)| {
-                    let mut l10n_resource = L10nResource::new();
-                    for locale in locales.main_locales() {
-                        let locales_resolution = locales
-                            .locale_resolution_route(&locale)
-                            .expect("Unexpected error, `locale_resolution_route` should not be None in this context!");
-                        let mut inverted_locales_resolution = locales_resolution.clone();
-                        inverted_locales_resolution.reverse();
-                        let mut fl_bundle = FluentBundle::new_concurrent(
-                            locales_resolution.into_iter().cloned().collect(),
-                        );
-
-                        for fl_res in Self::global_unnamed_fluent_resources(
-                            &global_unnamed_resources,
-                            fluent_resources,
-                        ) {
-                            fl_bundle.add_resource_overriding(fl_res);
-                        }
-
-                        let mut relative_paths = vec![];
-                        let mut relative_path = Some(
-                            name.parse::<PathBuf>()
-                                .unwrap()
-                                .parent()
-                                .unwrap()
-                                .to_path_buf(),
-                        );
-                        while let Some(path) = relative_path {
-                            relative_paths.push(path.clone());
-                            relative_path = path.parent().map(|p| p.to_path_buf());
-                        }
-                        relative_paths.reverse();
-
-                        for relative_path in relative_paths {
-                            for locale in &inverted_locales_resolution {
-                                for fl_res in Self::unnamed_fluent_resources(
-                                    &relative_path,
-                                    locale,
-                                    &unnamed_resources,
-                                    fluent_resources,
-                                ) {
-                                    fl_bundle.add_resource_overriding(fl_res);
-                                }
-                            }
-                        }
-
-                        for locale in &inverted_locales_resolution {
-                            if let Some(fl_res) = Self::named_fluent_resource(
-                                name,
-                                locale,
-                                &named_resources,
-                                fluent_resources,
-                            ) {
-                                fl_bundle.add_resource_overriding(fl_res);
-                            }
-                        }
-
-                        fl_bundle.set_transform(transform);
-                        fl_bundle.set_formatter(formatter);
-                        fl_bundle.set_use_isolating(use_isolating);
+        let (unnamed_resources, named_resources) = match case_insensitive_lookup {
+            true => {
+                for name in shared_resources.iter_mut().chain(&mut complete_resources) {
+                    *name = case_insensitive_name(name);
+                }
+                Self::case_insensitive_resources(unnamed_resources, named_resources)?
+            }
+            false => (unnamed_resources, named_resources),
+        };
+        let unknown_shared_resources: Vec<_> = shared_resources
+            .iter()
+            .filter(|name| !named_resources.contains_key(*name))
+            .map(|name| BuildError::UnknownSharedResource {
+                resource: name.to_owned(),
+            })
+            .collect();
+        if !unknown_shared_resources.is_empty() {
+            return Err(BuildErrors(unknown_shared_resources));
+        }
 
-                        for (name, function) in functions.clone() {
-                            // Future improvement: only add functions to bundle when is needed
-                            fl_bundle
-                                .add_function(&name, function)
-                                .expect("Unexpected error, there should not be functions with same names");
-                        }
+        let transform = match pseudo_options {
+            Some(PseudoOptions { accent: true, .. }) => {
+                if transform.is_some() || !locale_transforms.is_empty() {
+                    return Err(BuildErrors(vec![BuildError::PseudoLocalizeTransform]));
+                }
+                Some(pseudo::transform as fn(&str) -> Cow<str>)
+            }
+            _ => transform,
+        };
 
-                        l10n_resource.add_bundle(locale.to_owned(), fl_bundle);
-                    }
+        if let Some(primary_locale) = primary_locale {
+            locales.set_primary(primary_locale).expect(
+                "Unexpected error, primary locale should be a main locale in this context!",
+            );
+        }
 
-                    (name.to_string(), l10n_resource)
-                })
-                .collect()
+        let resources_index = ResourcesIndex {
+            global_unnamed_resources,
+            unnamed_resources,
+            named_resources,
+            shared_resources,
+        };
+        let fluent_resources: Vec<_> = fluent_resources.into_iter().map(Arc::new).collect();
+        if check_terms {
+            Self::check_terms(
+                &fluent_resources,
+                &locales,
+                &resources_index,
+                &complete_resources,
+            )?;
+        }
+        let bundle_options = BundleOptions {
+            transform,
+            locale_transforms,
+            formatter,
+            use_isolating,
+            functions,
+            locale_functions,
+            bundle_functions,
+        };
+        let provenances = record_provenance.then(|| {
+            Self::build_provenances(&fluent_resources, &locales, &resources_index, &paths)
         });
+        let resources = Self::build_resources(
+            &fluent_resources,
+            &locales,
+            &resources_index,
+            &bundle_options,
+        );
 
-        Ok(Self {
-            inner: inner_translator,
+        let mut functions: Vec<_> = bundle_options
+            .functions
+            .keys()
+            .chain(bundle_options.locale_functions.keys())
+            .chain(bundle_options.bundle_functions.keys())
+            .cloned()
+            .chain(
+                crate::builtins::FUNCTIONS
+                    .iter()
+                    .map(|name| name.to_string()),
+            )
+            .collect();
+        functions.sort();
+        functions.dedup();
+
+        let names: Vec<_> = resources.keys().cloned().collect();
+        let mut l10n = Self {
+            fluent_resources,
+            resources,
             locales,
-        })
+            args_default,
+            locale_args_default,
+            pseudo_options,
+            postprocess,
+            functions,
+            cache_if,
+            resources_index,
+            bundle_options,
+            paths,
+            preprocessor,
+            provenances,
+            case_insensitive_lookup,
+            default_locale,
+            resolve_literals,
+            root,
+            complete_resources,
+            check_terms,
+            #[cfg(feature = "metrics")]
+            format_counts: Default::default(),
+        };
+        l10n.add_literals(&names);
+        l10n.add_cached(&names);
+        Ok(l10n)
     }
 
-    fn check_consistency(builder: &L10nBuilder) -> Result<(), BuildErrors> {
-        Self::check_named_resources_consistency(
-            &builder.locales,
-            &builder.named_resources,
-            &builder.fluent_resources,
-        )?;
-        Ok(())
-    }
+    // Rekeys the resources with `case_insensitive_name`, the unnamed
+    // resources of the same path are merged.
+    fn case_insensitive_resources(
+        unnamed_resources: UnnamedResources,
+        named_resources: NamedResources,
+    ) -> Result<(UnnamedResources, NamedResources), BuildErrors> {
+        let mut rekeyed_unnamed_resources = UnnamedResources::new();
+        for ((path, locale), indices) in unnamed_resources {
+            rekeyed_unnamed_resources
+                .entry((case_insensitive_name(&path), locale))
+                .or_default()
+                .extend(indices);
+        }
 
-    fn check_named_resources_consistency(
-        locales: &Locales,
-        named_resources: &NamedResources,
-        fluent_resources: &FluentResources,
-    ) -> Result<(), BuildErrors> {
+        let mut rekeyed_named_resources = NamedResources::new();
         let mut errors = vec![];
-        for named_resource in named_resources.keys() {
-            let missing_locales: Vec<_> = locales
-                .mandatory_locales()
-                .iter()
-                .filter_map(|locale| {
-                    match Self::named_fluent_resource(
-                        named_resource,
-                        locale,
-                        named_resources,
-                        fluent_resources,
-                    ) {
-                        Some(_) => None,
-                        None => Some(locale.clone()),
+        for (name, resources) in named_resources {
+            let name = case_insensitive_name(&name);
+            let rekeyed_resources = rekeyed_named_resources.entry(name.clone()).or_default();
+            for (locale, index) in resources {
+                match rekeyed_resources.entry(locale) {
+                    hash_map::Entry::Occupied(entry) => {
+                        errors.push(BuildError::CaseInsensitiveConflict {
+                            resource: name.clone(),
+                            locale: entry.key().clone(),
+                        })
                     }
-                })
-                .collect();
-
-            if !missing_locales.is_empty() {
-                errors.push(BuildError::MissingResource {
-                    resource: named_resource.to_owned(),
-                    locales: missing_locales,
-                });
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(index);
+                    }
+                }
             }
         }
         match errors.is_empty() {
-            true => Ok(()),
-            false => Err(BuildErrors(errors)),
+            true => Ok((rekeyed_unnamed_resources, rekeyed_named_resources)),
+            false => {
+                errors.sort();
+                Err(BuildErrors(errors))
+            }
         }
     }
 
-    pub fn try_translate_with_args<'a, 'b>(
-        &'a self,
-        lang: &LanguageIdentifier,
-        resource: &str,
-        key: &str,
-        args: Option<&FluentArgs<'b>>,
-    ) -> Result<Cow<'a, str>, TranslateError> {
-        self.inner
-            .borrow_dependent()
-            .get(resource)
-            .ok_or_else(|| TranslateError::ResourceNotExists(resource.to_string()))?
-            .translate(lang, key, args)
+    fn build_resources(
+        fluent_resources: &[Arc<FluentResource>],
+        locales: &Locales,
+        resources_index: &ResourcesIndex,
+        bundle_options: &BundleOptions,
+    ) -> Resources {
+        resources_index
+            .named_resources
+            .keys()
+            .map(|name| {
+                let mut l10n_resource = L10nResource::new(name);
+                for locale in locales.main_locales() {
+                    let bundle = Self::build_bundle(
+                        name,
+                        &locale,
+                        fluent_resources,
+                        locales,
+                        resources_index,
+                        bundle_options,
+                    );
+                    l10n_resource.add_bundle(locale, bundle);
+                }
+                Self::add_keys(&mut l10n_resource, name, fluent_resources, resources_index);
+                (name.to_string(), l10n_resource)
+            })
+            .collect()
     }
 
-    pub fn required_variables(
-        &self,
-        resource: &str,
-        key: &str,
-    ) -> Result<HashSet<&str>, TranslateError> {
-        self.inner
-            .borrow_dependent()
-            .get(resource)
-            .ok_or_else(|| TranslateError::ResourceNotExists(resource.to_string()))?
-            .required_variables(key)
-    }
+    fn build_bundle(
+        name: &str,
+        main_locale: &LanguageIdentifier,
+        fluent_resources: &[Arc<FluentResource>],
+        locales: &Locales,
+        resources_index: &ResourcesIndex,
+        bundle_options: &BundleOptions,
+    ) -> FluentBundle<Arc<FluentResource>, IntlLangMemoizer> {
+        let bundle_locales: Vec<_> = locales
+            .locale_resolution_route(main_locale)
+            .expect(
+                "Unexpected error, `locale_resolution_route` should not be None in this context!",
+            )
+            .into_iter()
+            .cloned()
+            .collect();
+        let layers = Self::bundle_layers(name, main_locale, locales, resources_index);
 
-    pub fn required_functions(&self) -> HashSet<&str> {
-        let mut functions = HashSet::new();
-        let resources = self.inner.borrow_owner();
+        let mut fl_bundle = FluentBundle::new_concurrent(bundle_locales.clone());
+        for (index, _) in &layers {
+            fl_bundle.add_resource_overriding(Arc::clone(&fluent_resources[*index]));
+        }
+        Self::set_bundle_options(&mut fl_bundle, main_locale, bundle_options);
 
-        for resource in resources {
-            for entry in resource.entries() {
-                match entry {
-                    Entry::Message(message) => {
-                        if let Some(pattern) = &message.value {
-                            self.parse_pattern_functions(pattern, &mut functions);
-                        }
-                        for attribute in &message.attributes {
-                            self.parse_pattern_functions(&attribute.value, &mut functions);
-                        }
-                    }
-                    Entry::Term(term) => {
-                        self.parse_pattern_functions(&term.value, &mut functions);
-                        for attribute in &term.attributes {
-                            self.parse_pattern_functions(&attribute.value, &mut functions);
-                        }
-                    }
-                    _ => {}
+        if !bundle_options.bundle_functions.is_empty() {
+            // The resolver bundle shares the resources, its own bundle
+            // functions hold it weakly to not leak the cycle.
+            let resolver = Arc::new_cyclic(|weak_resolver: &Weak<BundleResolver>| {
+                let mut resolver_bundle = FluentBundle::new_concurrent(bundle_locales);
+                for (index, _) in &layers {
+                    resolver_bundle.add_resource_overriding(Arc::clone(&fluent_resources[*index]));
+                }
+                Self::set_bundle_options(&mut resolver_bundle, main_locale, bundle_options);
+                for (name, function) in bundle_options.bundle_functions.clone() {
+                    let weak_resolver = weak_resolver.clone();
+                    resolver_bundle
+                        .add_function(&name, move |positional, named: &FluentArgs| {
+                            match weak_resolver.upgrade() {
+                                Some(resolver) => function(&resolver, positional, named),
+                                None => FluentValue::Error,
+                            }
+                        })
+                        .expect("Unexpected error, there should not be functions with same names");
                 }
+                BundleResolver::new(resolver_bundle)
+            });
+
+            for (name, function) in bundle_options.bundle_functions.clone() {
+                let resolver = Arc::clone(&resolver);
+                fl_bundle
+                    .add_function(&name, move |positional, named: &FluentArgs| {
+                        function(&resolver, positional, named)
+                    })
+                    .expect("Unexpected error, there should not be functions with same names");
             }
         }
-
-        functions
-    }
-
-    fn global_unnamed_fluent_resources<'r, 'a>(
-        global_unnamed_resources: &'a [ResourceIndex],
-        fluent_resources: &'r [FluentResource],
-    ) -> Vec<&'r FluentResource> {
-        global_unnamed_resources
-            .iter()
-            .map(|resource_index| fluent_resources.get(*resource_index).expect("TODO 8"))
-            .collect()
+        fl_bundle
     }
 
-    fn unnamed_fluent_resources<'r, 'a>(
-        relative_path: &Path,
-        locale: &'a LanguageIdentifier,
-        unnamed_resources: &'a UnnamedResources,
-        fluent_resources: &'r [FluentResource],
-    ) -> Vec<&'r FluentResource> {
-        let path = normalized_path(relative_path);
-        let key = (path, locale.to_owned());
-        if let Some(resources_index) = unnamed_resources.get(&key) {
-            resources_index
-                .iter()
-                .map(|resource_index| fluent_resources.get(*resource_index).unwrap())
-                .collect()
-        } else {
-            vec![]
+    // The keys are the ones of the named resources of `name`.
+    fn add_keys(
+        l10n_resource: &mut L10nResource<Arc<FluentResource>>,
+        name: &str,
+        fluent_resources: &[Arc<FluentResource>],
+        resources_index: &ResourcesIndex,
+    ) {
+        l10n_resource.clear_keys();
+        for index in resources_index.named_resources[name].values() {
+            l10n_resource.add_keys(&fluent_resources[*index]);
         }
     }
 
-    fn named_fluent_resource<'r, 'a>(
-        name: &'a str,
-        locale: &'a LanguageIdentifier,
-        named_resources: &'a NamedResources,
-        fluent_resources: &'r [FluentResource],
-    ) -> Option<&'r FluentResource> {
-        named_resources
-            .get(name)
-            .and_then(|localized_resources| localized_resources.get(locale))
-            .map(|resource_index| fluent_resources.get(*resource_index).expect("TODO 10"))
-    }
-
-    fn parse_pattern_functions<'a>(
-        &'a self,
-        pattern: &Pattern<&'a str>,
-        functions: &mut HashSet<&'a str>,
-    ) {
-        for element in &pattern.elements {
-            if let PatternElement::Placeable { expression } = element {
-                self.parse_expression_functions(expression, functions);
+    // Resolves the literals of the resources `names` once their bundles and
+    // keys are added, finished like the formatted translations.
+    fn add_literals(&mut self, names: &[String]) {
+        if !self.resolve_literals {
+            return;
+        }
+        let (pseudo_options, postprocess) = (self.pseudo_options, self.postprocess);
+        for name in names {
+            if let Some(resource) = self.resources.get_mut(name) {
+                resource.add_literals(|locale, translation| {
+                    Self::finish_translation(
+                        &pseudo_options,
+                        postprocess,
+                        locale,
+                        Cow::Owned(translation),
+                    )
+                    .into_owned()
+                });
             }
         }
     }
 
-    fn parse_expression_functions<'a>(
-        &'a self,
-        expression: &Expression<&'a str>,
-        functions: &mut HashSet<&'a str>,
-    ) {
-        match expression {
-            Expression::Select { selector, variants } => {
-                self.parse_inline_expression_functions(selector, functions);
-                for variant in variants {
-                    self.parse_pattern_functions(&variant.value, functions);
+    // Translates without arguments the messages of the resources `names`
+    // matching the `cache_if` predicate, the ones failing are not cached.
+    fn add_cached(&mut self, names: &[String]) {
+        let predicate = match self.cache_if {
+            Some(predicate) => predicate,
+            None => return,
+        };
+        for name in names {
+            let resource = match self.resources.get(name) {
+                Some(resource) => resource,
+                None => continue,
+            };
+            let mut translations = vec![];
+            for locale in resource.locales() {
+                for key in resource.keys().filter(|key| predicate(name, key)) {
+                    if let Ok(translation) = self.format_translation(locale, resource, key, None) {
+                        translations.push((
+                            locale.clone(),
+                            key.to_owned(),
+                            translation.into_owned(),
+                        ));
+                    }
                 }
             }
-            Expression::Inline(inline_expression) => {
-                self.parse_inline_expression_functions(inline_expression, functions);
+            if let Some(resource) = self.resources.get_mut(name) {
+                for (locale, key, translation) in translations {
+                    resource.add_cached(locale, key, translation);
+                }
             }
         }
     }
 
-    fn parse_inline_expression_functions<'a>(
-        &'a self,
-        inline_expression: &InlineExpression<&'a str>,
-        functions: &mut HashSet<&'a str>,
+    // Sets the options and the functions, except the bundle functions.
+    fn set_bundle_options<R: Borrow<FluentResource>>(
+        bundle: &mut FluentBundle<R, IntlLangMemoizer>,
+        locale: &LanguageIdentifier,
+        bundle_options: &BundleOptions,
     ) {
-        if let InlineExpression::FunctionReference { id, .. } = inline_expression {
-            functions.insert(id.name);
+        let BundleOptions {
+            transform,
+            locale_transforms,
+            formatter,
+            use_isolating,
+            functions,
+            locale_functions,
+            bundle_functions: _,
+        } = bundle_options;
+
+        bundle.set_transform(locale_transforms.get(locale).copied().or(*transform));
+        bundle.set_formatter(*formatter);
+        bundle.set_use_isolating(*use_isolating);
+
+        for (name, function) in functions.clone() {
+            // Future improvement: only add functions to bundle when is needed
+            bundle
+                .add_function(&name, function)
+                .expect("Unexpected error, there should not be functions with same names");
         }
-    }
-}
 
-impl Default for L10nBuilder {
-    fn default() -> Self {
-        Self {
-            locales: Default::default(),
-            fluent_resources: Default::default(),
-            global_unnamed_resources: Default::default(),
-            unnamed_resources: Default::default(),
-            named_resources: Default::default(),
-            transform: Default::default(),
-            formatter: Default::default(),
-            use_isolating: true,
-            functions: Default::default(),
+        // The main locale of the bundle, even when a message is resolved from
+        // a fallback locale.
+        for (name, function) in locale_functions.clone() {
+            let locale = locale.to_owned();
+            bundle
+                .add_function(&name, move |positional, named: &FluentArgs| {
+                    function(&locale, positional, named)
+                })
+                .expect("Unexpected error, there should not be functions with same names");
         }
-    }
-}
 
-impl Debug for L10nBuilder {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("L10nBuilder")
-            .field("locales", &self.locales)
-            .finish()
+        #[cfg(feature = "builtins")]
+        crate::builtins::add_functions(bundle, locale, |name| {
+            functions.contains_key(name)
+                || locale_functions.contains_key(name)
+                || bundle_options.bundle_functions.contains_key(name)
+        });
     }
-}
 
-impl L10nBuilder {
-    pub fn new(locales: Locales) -> Self {
-        Self {
-            locales,
-            ..Default::default()
+    // Paths of the directories containing the named resource `name`, from the
+    // root to the closest one.
+    fn relative_paths(name: &str) -> Vec<PathBuf> {
+        let mut relative_paths = vec![];
+        let mut relative_path = Some(
+            name.parse::<PathBuf>()
+                .unwrap()
+                .parent()
+                .unwrap()
+                .to_path_buf(),
+        );
+        while let Some(path) = relative_path {
+            relative_paths.push(path.clone());
+            relative_path = path.parent().map(|p| p.to_path_buf());
         }
+        relative_paths.reverse();
+        relative_paths
     }
 
-    pub fn add_global_unnamed_resource(&mut self, resource: FluentResource) {
-        self.global_unnamed_resources
-            .push(self.fluent_resources.len());
-        self.fluent_resources.push(resource);
-    }
-
-    pub fn add_unnamed_resource(
-        &mut self,
-        relative_path: &Path,
-        locale: &LanguageIdentifier,
-        fluent_resource: FluentResource,
-    ) {
-        let path = normalized_path(relative_path);
-        let key = (path, locale.to_owned());
-        let resources = match self.unnamed_resources.get_mut(&key) {
-            Some(v) => v,
-            None => {
-                self.unnamed_resources
-                    .insert(key.clone(), Vec::with_capacity(1));
-                self.unnamed_resources.get_mut(&key).unwrap()
+    // Follows the merge order of the bundles so the last resource
+    // defining a message is the one providing it.
+    fn build_provenances(
+        fluent_resources: &[Arc<FluentResource>],
+        locales: &Locales,
+        resources_index: &ResourcesIndex,
+        paths: &ResourcePaths,
+    ) -> Provenances {
+        let mut provenances = HashMap::new();
+        for name in resources_index.named_resources.keys() {
+            for main_locale in locales.main_locales() {
+                let layers = Self::bundle_layers(name, &main_locale, locales, resources_index);
+                for (index, locale) in layers {
+                    for entry in fluent_resources[index].entries() {
+                        if let Entry::Message(message) = entry {
+                            provenances.insert(
+                                (
+                                    name.to_owned(),
+                                    main_locale.clone(),
+                                    message.id.name.to_string(),
+                                ),
+                                MessageProvenance {
+                                    locale: locale.cloned(),
+                                    path: paths.get(&index).cloned(),
+                                },
+                            );
+                        }
+                    }
+                }
             }
-        };
-        resources.push(self.fluent_resources.len());
-        self.fluent_resources.push(fluent_resource);
+        }
+        provenances
     }
 
-    pub fn add_named_resource(
-        &mut self,
+    // Resources of the bundle of `name` in `main_locale` in the order they
+    // are added (the last ones override the first ones), with their locale.
+    fn bundle_layers<'l>(
         name: &str,
-        relative_path: &Path,
-        locale: &LanguageIdentifier,
-        fluent_resource: FluentResource,
-    ) {
-        let resource_name = normalized_path(&relative_path.join(name));
-        let resources = match self.named_resources.get_mut(&resource_name) {
-            Some(v) => v,
-            None => {
-                self.named_resources
-                    .insert(resource_name.clone(), HashMap::new());
-                self.named_resources.get_mut(&resource_name).unwrap()
+        main_locale: &LanguageIdentifier,
+        locales: &'l Locales,
+        resources_index: &ResourcesIndex,
+    ) -> Vec<(ResourceIndex, Option<&'l LanguageIdentifier>)> {
+        let mut inverted_locales_resolution = locales.locale_resolution_route(main_locale).expect(
+            "Unexpected error, `locale_resolution_route` should not be None in this context!",
+        );
+        inverted_locales_resolution.reverse();
+
+        let mut layers: Vec<(ResourceIndex, Option<&LanguageIdentifier>)> = resources_index
+            .global_unnamed_resources
+            .iter()
+            .map(|index| (*index, None))
+            .collect();
+        for relative_path in Self::relative_paths(name) {
+            for locale in &inverted_locales_resolution {
+                let key = (normalized_path(&relative_path), (*locale).to_owned());
+                if let Some(indices) = resources_index.unnamed_resources.get(&key) {
+                    layers.extend(indices.iter().map(|index| (*index, Some(*locale))));
+                }
             }
-        };
-        if resources.contains_key(locale) {
-            // Maybe a first improvement could be to override the resource
-            // since it rely on fs I think it's ok for now.
-            unreachable!(
-                r#"named resource: "{}" already exists for locale: "{}""#,
-                resource_name, locale
-            );
         }
-        resources.insert(locale.to_owned(), self.fluent_resources.len());
-        self.fluent_resources.push(fluent_resource);
-    }
-
-    pub fn build(self) -> Result<L10n, BuildErrors> {
-        L10n::new(self)
+        for shared in resources_index
+            .shared_resources
+            .iter()
+            .filter(|shared| *shared != name)
+        {
+            for locale in &inverted_locales_resolution {
+                if let Some(index) = resources_index
+                    .named_resources
+                    .get(shared)
+                    .and_then(|resources| resources.get(*locale))
+                {
+                    layers.push((*index, Some(*locale)));
+                }
+            }
+        }
+        for locale in &inverted_locales_resolution {
+            if let Some(index) = resources_index
+                .named_resources
+                .get(name)
+                .and_then(|resources| resources.get(*locale))
+            {
+                layers.push((*index, Some(*locale)));
+            }
+        }
+        layers
     }
 
-    pub fn parse(
-        path: impl AsRef<Path>,
-        locales_option: Option<Locales>,
-    ) -> Result<Self, ParserError> {
-        let mut builder = Self::default();
-        let path = path.as_ref();
-        let locales_to_visit = locales_option.as_ref().map(|locales| locales.all_locales());
-        let mut locales_visited = HashSet::new();
-
-        let dir = fs::read_dir(path).map_err(|err| match err.kind() {
-            io::ErrorKind::NotFound => ParserError::ReadPath {
-                path: path.to_path_buf(),
-                err,
-            },
-            _ => err.into(),
-        })?;
-
-        for entry in dir {
-            let entry_path = entry?.path();
-            let entry_name = get_entry_name(&entry_path);
+    // The terms referenced by the messages and terms of a bundle must be
+    // defined in it, otherwise the messages only fail when formatted.
+    fn check_terms(
+        fluent_resources: &[Arc<FluentResource>],
+        locales: &Locales,
+        resources_index: &ResourcesIndex,
+        complete_resources: &[String],
+    ) -> Result<(), BuildErrors> {
+        let mut missing_terms: BTreeMap<(&str, String, &str), Vec<LanguageIdentifier>> =
+            BTreeMap::new();
+        for name in resources_index
+            .named_resources
+            .keys()
+            .filter(|name| !complete_resources.contains(name))
+        {
+            for main_locale in locales.main_locales() {
+                let layers = Self::bundle_layers(name, &main_locale, locales, resources_index);
 
-            if entry_path.is_file() {
-                let name = match entry_name {
-                    Some(v) => v.to_string_lossy(),
-                    None => continue,
-                };
-                if !name.starts_with('_') {
-                    return Err(ParserError::GlobalNamedResource { path: entry_path });
+                // The most specific locale first, an overridden entry is
+                // not formatted.
+                let mut message_ids = HashSet::new();
+                let mut term_ids = HashSet::new();
+                let mut patterns = vec![];
+                for (index, _) in layers.iter().rev() {
+                    for entry in fluent_resources[*index].entries() {
+                        let (key, value, attributes) = match entry {
+                            Entry::Message(message) if message_ids.insert(message.id.name) => (
+                                message.id.name.to_string(),
+                                message.value.as_ref(),
+                                &message.attributes,
+                            ),
+                            Entry::Term(term) if term_ids.insert(term.id.name) => (
+                                format!("-{}", term.id.name),
+                                Some(&term.value),
+                                &term.attributes,
+                            ),
+                            _ => continue,
+                        };
+                        for attribute in attributes {
+                            let attribute_key = format!("{}.{}", key, attribute.id.name);
+                            patterns.push((attribute_key, &attribute.value));
+                        }
+                        if let Some(value) = value {
+                            patterns.push((key, value));
+                        }
+                    }
                 }
 
-                let fluent_resource = Self::read_fluent_resource(&entry_path)?;
-                builder.add_global_unnamed_resource(fluent_resource);
-            } else if entry_path.is_dir() {
-                let dir_name = match entry_name.and_then(|v| v.to_str()) {
-                    Some(v) => v,
-                    None => continue,
-                };
-
-                let parsed_locale = dir_name.parse::<LanguageIdentifier>();
-                let locale = match &locales_to_visit {
-                    Some(locales_to_visit) => match parsed_locale {
-                        Ok(locale) if locales_to_visit.contains(&locale) => locale,
-                        _ => continue,
-                    },
-                    None => parsed_locale.map_err(|err| ParserError::ParseLangDir {
-                        dir_name: dir_name.to_string(),
-                        err,
-                    })?,
-                };
-                locales_visited.insert(locale.clone());
-
-                builder.parse_locale_directory(&locale, &entry_path, &PathBuf::default())?;
+                for (key, pattern) in patterns {
+                    let mut terms = HashSet::new();
+                    parse_pattern_terms(pattern, &mut terms);
+                    for term in terms {
+                        if !term_ids.contains(term) {
+                            missing_terms
+                                .entry((name.as_str(), key.clone(), term))
+                                .or_default()
+                                .push(main_locale.clone());
+                        }
+                    }
+                }
             }
         }
 
-        if let Some(mandatory_locales) = locales_option
-            .as_ref()
-            .map(|locales| locales.mandatory_locales())
-        {
-            let differences: Vec<_> = mandatory_locales
-                .difference(&locales_visited)
-                .cloned()
-                .collect();
-            if !differences.is_empty() {
-                return Err(ParserError::MissingLocales(differences));
-            }
+        let errors: Vec<_> = missing_terms
+            .into_iter()
+            .map(|((resource, message, term), mut locales)| {
+                locales.sort();
+                BuildError::MissingTerm {
+                    resource: resource.to_owned(),
+                    message,
+                    term: term.to_owned(),
+                    locales,
+                }
+            })
+            .collect();
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(BuildErrors(errors)),
         }
-
-        builder.locales = locales_option.unwrap_or_else(|| Locales::from(locales_visited));
-
-        Ok(builder)
     }
 
-    fn parse_locale_directory(
-        &mut self,
-        locale: &LanguageIdentifier,
-        locale_path: &Path,
-        relative_path: &Path,
-    ) -> Result<(), ParserError> {
-        let path = locale_path.join(relative_path);
+    /// Reads and parses again the fluent file at `path` (a file read by
+    /// [`L10nBuilder::parse`]) then rebuilds the bundles using it, the other
+    /// files are not read again. On error the translations are unchanged.
+    pub fn reload_file(&mut self, path: impl AsRef<Path>) -> Result<(), ParserError> {
+        let path = path.as_ref();
+        let canonical_path = path.canonicalize().map_err(|err| ParserError::ReadPath {
+            path: path.to_path_buf(),
+            err,
+        })?;
+        let index = self
+            .paths
+            .iter()
+            .find(|(_, resource_path)| {
+                resource_path.canonicalize().ok().as_ref() == Some(&canonical_path)
+            })
+            .map(|(index, _)| *index)
+            .ok_or_else(|| ParserError::UnknownFile {
+                path: path.to_path_buf(),
+            })?;
+        let fluent_resource = L10nBuilder::read_fluent_resource(path, self.preprocessor)?;
 
-        for entry in fs::read_dir(&path).map_err(|err| match err.kind() {
-            io::ErrorKind::NotFound => ParserError::ReadPath { path, err },
-            _ => err.into(),
-        })? {
-            let entry_path = entry?.path();
-            let name = match get_entry_name(&entry_path) {
-                Some(v) => v,
-                None => continue,
-            };
+        // Nothing is replaced before the reloaded resources pass the checks
+        let mut fluent_resources = self.fluent_resources.clone();
+        fluent_resources[index] = Arc::new(fluent_resource);
+        let mut reloaded = HashSet::from([index]);
+        for (index, fluent_resource) in inherit_attributes(
+            &fluent_resources,
+            &self.resources_index.unnamed_resources,
+            &self.resources_index.named_resources,
+        )? {
+            fluent_resources[index] = Arc::new(fluent_resource);
+            reloaded.insert(index);
+        }
+        Self::check_named_resources_consistency(
+            &self.locales,
+            &self.resources_index.named_resources,
+            &self.root,
+            &self.complete_resources,
+        )?;
+        if self.check_terms {
+            Self::check_terms(
+                &fluent_resources,
+                &self.locales,
+                &self.resources_index,
+                &self.complete_resources,
+            )?;
+        }
 
-            if entry_path.is_file() {
-                let resource = Self::read_fluent_resource(&entry_path)?;
-                let name = name.to_string_lossy();
-                if name.starts_with('_') {
-                    self.add_unnamed_resource(relative_path, locale, resource);
-                } else {
-                    self.add_named_resource(&name, relative_path, locale, resource);
+        let mut rebuilt_names = vec![];
+        for (name, l10n_resource) in &mut self.resources {
+            let mut rebuilt = false;
+            for locale in self.locales.main_locales() {
+                let layers =
+                    Self::bundle_layers(name, &locale, &self.locales, &self.resources_index);
+                if !layers.iter().any(|(index, _)| reloaded.contains(index)) {
+                    continue;
                 }
-            } else if entry_path.is_dir() {
-                self.parse_locale_directory(locale, locale_path, &relative_path.join(&name))?;
+                let bundle = Self::build_bundle(
+                    name,
+                    &locale,
+                    &fluent_resources,
+                    &self.locales,
+                    &self.resources_index,
+                    &self.bundle_options,
+                );
+                l10n_resource.add_bundle(locale, bundle);
+                rebuilt = true;
+            }
+            if rebuilt {
+                Self::add_keys(
+                    l10n_resource,
+                    name,
+                    &fluent_resources,
+                    &self.resources_index,
+                );
+                rebuilt_names.push(name.to_owned());
             }
         }
-
+        self.fluent_resources = fluent_resources;
+        self.add_literals(&rebuilt_names);
+        self.add_cached(&rebuilt_names);
+        if self.provenances.is_some() {
+            self.provenances = Some(Self::build_provenances(
+                &self.fluent_resources,
+                &self.locales,
+                &self.resources_index,
+                &self.paths,
+            ));
+        }
         Ok(())
     }
 
-    pub fn set_transform(mut self, transform: Option<fn(&str) -> Cow<str>>) -> Self {
-        self.transform = transform;
-        self
+    // Paths of the fluent files read when parsing
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    pub(crate) fn file_paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.values().map(PathBuf::as_path)
     }
 
-    pub fn set_formatter(
-        mut self,
-        formatter: Option<fn(&FluentValue, &IntlLangMemoizer) -> Option<String>>,
-    ) -> Self {
-        self.formatter = formatter;
-        self
+    /// Serializes the locales and the fluent resources, the result can be
+    /// loaded with [`L10nBuilder::deserialize`] without walking the file system.
+    #[cfg(feature = "serialize")]
+    pub fn serialize(&self) -> Result<Vec<u8>, SerializeError> {
+        let serialized = SerializedL10n {
+            locales: self
+                .locales
+                .into_iter()
+                .map(|entry| (entry.locale().clone(), entry.fallback().clone()))
+                .collect(),
+            sources: self
+                .fluent_resources
+                .iter()
+                .map(|resource| resource.source())
+                .collect(),
+            resources_index: self.resources_index.clone(),
+        };
+        Ok(bincode::serialize(&serialized)?)
     }
 
-    pub fn set_use_isolating(mut self, use_isolating: bool) -> Self {
-        self.use_isolating = use_isolating;
-        self
+    fn check_consistency(builder: &L10nBuilder) -> Result<(), BuildErrors> {
+        if builder.locales.is_empty() {
+            return Err(BuildErrors(vec![BuildError::NoLocales]));
+        }
+        Self::check_named_resources_consistency(
+            &builder.locales,
+            &builder.named_resources,
+            &builder.root,
+            &builder.complete_resources,
+        )?;
+        Self::check_primary_locale(&builder.locales, &builder.primary_locale)?;
+        Self::check_default_locale(&builder.locales, &builder.default_locale)?;
+        Ok(())
     }
 
-    pub fn add_function(
-        mut self,
-        name: &str,
-        function: for<'a> fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a>,
-    ) -> Self {
-        self.functions.insert(name.to_owned(), function);
-        self
+    fn check_default_locale(
+        locales: &Locales,
+        default_locale: &Option<LanguageIdentifier>,
+    ) -> Result<(), BuildErrors> {
+        match default_locale {
+            Some(locale) if !locales.main_locales().contains(locale) => {
+                Err(BuildErrors(vec![BuildError::DefaultNotMainLocale {
+                    locale: locale.clone(),
+                }]))
+            }
+            _ => Ok(()),
+        }
     }
 
-    fn read_fluent_resource(path: &Path) -> Result<FluentResource, ParserError> {
-        let source = fs::read_to_string(path)?;
-        FluentResource::try_new(source).map_err(|(_, errors)| ParserError::FluentParser { errors })
+    fn check_primary_locale(
+        locales: &Locales,
+        primary_locale: &Option<LanguageIdentifier>,
+    ) -> Result<(), BuildErrors> {
+        match primary_locale {
+            Some(locale) if !locales.main_locales().contains(locale) => {
+                Err(BuildErrors(vec![BuildError::PrimaryNotMainLocale {
+                    locale: locale.clone(),
+                }]))
+            }
+            _ => Ok(()),
+        }
     }
-}
 
-fn normalized_path(path: &Path) -> String {
-    path.iter()
-        .map(|c| c.to_string_lossy())
-        .collect::<Vec<_>>()
-        .join("/")
-}
+    fn check_named_resources_consistency(
+        locales: &Locales,
+        named_resources: &NamedResources,
+        root: &Option<PathBuf>,
+        complete_resources: &[String],
+    ) -> Result<(), BuildErrors> {
+        let mut errors = vec![];
+        for named_resource in named_resources
+            .keys()
+            .filter(|name| !complete_resources.contains(name))
+        {
+            let missing_locales: Vec<_> = locales
+                .mandatory_locales()
+                .iter()
+                .filter(|locale| !named_resources[named_resource].contains_key(*locale))
+                .cloned()
+                .collect();
 
-fn get_entry_name(entry_path: &Path) -> Option<&OsStr> {
-    if entry_path.is_dir() {
-        entry_path.file_name()
-    } else {
-        match entry_path.extension() {
-            Some(extension) if extension == "ftl" => entry_path.file_stem(),
-            _ => None,
+            if !missing_locales.is_empty() {
+                errors.push(BuildError::MissingResource {
+                    resource: named_resource.to_owned(),
+                    paths: missing_locales
+                        .iter()
+                        .map(|locale| resource_path(root, named_resource, locale))
+                        .collect(),
+                    locales: missing_locales,
+                });
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(BuildErrors(errors)),
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// Checks that the select expressions on plural categories or numbers
+    /// have a variant for every CLDR plural category of their locale, e.g.
+    /// `few` and `many` in Polish. The numbers matched by a numeric variant
+    /// like `[1]` do not need their category.
+    pub fn check_plural_categories(&self) -> Result<(), BuildErrors> {
+        let fluent_resources = &self.fluent_resources;
+        let mut errors = vec![];
+        for (name, resources) in &self.resources_index.named_resources {
+            for (locale, index) in resources {
+                let rules = match PluralRules::create(locale.clone(), PluralRuleType::CARDINAL) {
+                    Ok(cardinal) => SelectorRules {
+                        cardinal,
+                        ordinal: PluralRules::create(locale.clone(), PluralRuleType::ORDINAL).ok(),
+                    },
+                    Err(_) => continue,
+                };
+                for entry in fluent_resources[*index].entries() {
+                    let message = match entry {
+                        Entry::Message(message) => message,
+                        _ => continue,
+                    };
+                    let patterns = message
+                        .value
+                        .iter()
+                        .map(|pattern| (message.id.name.to_string(), pattern))
+                        .chain(message.attributes.iter().map(|attribute| {
+                            let key = format!("{}.{}", message.id.name, attribute.id.name);
+                            (key, &attribute.value)
+                        }));
+                    for (key, pattern) in patterns {
+                        let mut categories = BTreeSet::new();
+                        parse_pattern_missing_plural_categories(&rules, pattern, &mut categories);
+                        errors.extend(categories.into_iter().map(|category| {
+                            BuildError::MissingPluralCategory {
+                                resource: name.clone(),
+                                message: key.clone(),
+                                locale: locale.clone(),
+                                category: category.to_string(),
+                            }
+                        }));
+                    }
+                }
+            }
+        }
+        errors.sort();
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(BuildErrors(errors)),
+        }
+    }
+
+    pub fn try_translate_with_args<'a, 'b>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs<'b>>,
+    ) -> Result<Cow<'a, str>, TranslateError> {
+        if self.cache_if.is_some() && args.is_none() {
+            let cached = self
+                .resource(resource)
+                .ok()
+                .and_then(|l10n_resource| l10n_resource.cached(lang, key));
+            if let Some(translation) = cached {
+                return Ok(Cow::Borrowed(translation));
+            }
+        }
+        self.translate_uncached(lang, resource, key, args)
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but returns
+    /// [`UNEXPECTED_MESSAGE`](crate::UNEXPECTED_MESSAGE) on error, for resources
+    /// and keys only known at runtime.
+    pub fn translate_with_args<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Cow<'a, str> {
+        self.try_translate_with_args(lang, resource, key, args)
+            .unwrap_or_else(|_| Cow::from(crate::UNEXPECTED_MESSAGE))
+    }
+
+    /// Same as [`L10n::translate_with_args`] without arguments.
+    pub fn translate<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+    ) -> Cow<'a, str> {
+        self.translate_with_args(lang, resource, key, None)
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but translates to the
+    /// default locale when `requested` is not supported, see
+    /// [`L10nBuilder::set_default_locale`], e.g. for a server handling
+    /// arbitrary locales.
+    pub fn translate_negotiated<'a>(
+        &'a self,
+        requested: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<Cow<'a, str>, TranslateError> {
+        match (
+            self.try_translate_with_args(requested, resource, key, args),
+            &self.default_locale,
+        ) {
+            (Err(TranslateError::LocaleNotSupported { .. }), Some(default_locale)) => {
+                self.try_translate_with_args(default_locale, resource, key, args)
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn translate_uncached<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<Cow<'a, str>, TranslateError> {
+        #[cfg(feature = "metrics")]
+        self.count_format(resource, key);
+        self.format_translation(lang, self.resource(resource)?, key, args)
+    }
+
+    fn format_translation<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &'a L10nResource<Arc<FluentResource>>,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<Cow<'a, str>, TranslateError> {
+        let translation = match self.args_with_default(lang, resource, key, args)? {
+            Some(args) => resource.translate(lang, key, Some(&args)),
+            None => resource.translate(lang, key, args),
+        }?;
+        Ok(Self::finish_translation(
+            &self.pseudo_options,
+            self.postprocess,
+            lang,
+            translation,
+        ))
+    }
+
+    // The pseudo localization then the postprocess of every translation.
+    fn finish_translation<'a>(
+        pseudo_options: &Option<PseudoOptions>,
+        postprocess: Option<Postprocessor>,
+        lang: &LanguageIdentifier,
+        translation: Cow<'a, str>,
+    ) -> Cow<'a, str> {
+        let translation = match pseudo_options {
+            Some(options) => Cow::Owned(pseudo::expand(&translation, options)),
+            None => translation,
+        };
+        match postprocess {
+            Some(postprocess) => Cow::Owned(postprocess(lang, translation.into_owned())),
+            None => translation,
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn count_format(&self, resource: &str, key: &str) {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        if let Some(count) = self
+            .format_counts
+            .read()
+            .unwrap()
+            .get(resource)
+            .and_then(|counts| counts.get(key))
+        {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.format_counts
+            .write()
+            .unwrap()
+            .entry(resource.to_string())
+            .or_default()
+            .entry(key.to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of times each `(resource, key)` was formatted, the most
+    /// formatted first, to find the messages worth caching.
+    #[cfg(feature = "metrics")]
+    pub fn hot_messages(&self) -> Vec<((String, String), u64)> {
+        let mut hot_messages: Vec<_> = self
+            .format_counts
+            .read()
+            .unwrap()
+            .iter()
+            .flat_map(|(resource, counts)| {
+                counts.iter().map(move |(key, count)| {
+                    (
+                        (resource.clone(), key.clone()),
+                        count.load(std::sync::atomic::Ordering::Relaxed),
+                    )
+                })
+            })
+            .collect();
+        hot_messages.sort_by(|(a_key, a_count), (b_key, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_key.cmp(b_key))
+        });
+        hot_messages
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but takes owned arguments and
+    /// returns an owned translation, to translate on another thread or task.
+    pub fn try_translate_owned_args(
+        &self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<FluentArgs<'static>>,
+    ) -> Result<String, TranslateError> {
+        self.try_translate_with_args(lang, resource, key, args.as_ref())
+            .map(Cow::into_owned)
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but builds the arguments from
+    /// `(name, value)` pairs, e.g. `vec![("name", "Alice")]`, no argument is
+    /// the same as `None`.
+    pub fn try_translate_with_args_iter<'a, 'b, K, V>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Cow<'a, str>, TranslateError>
+    where
+        K: Into<Cow<'b, str>>,
+        V: Into<FluentValue<'b>>,
+    {
+        let args: FluentArgs = args.into_iter().collect();
+        let is_empty = args.iter().next().is_none();
+        let args = (!is_empty).then(|| args);
+        self.try_translate_with_args(lang, resource, key, args.as_ref())
+    }
+
+    /// Translation of a message made only of text, resolved when building
+    /// and returned without formatting, `None` for a message with placeables
+    /// (variables, references, functions...) or if it does not exist. The
+    /// literals are only resolved with [`L10nBuilder::resolve_literals`].
+    pub fn translate_raw(
+        &self,
+        resource: &str,
+        key: &str,
+        locale: &LanguageIdentifier,
+    ) -> Option<&str> {
+        self.resource(resource).ok()?.translate_raw(locale, key)
+    }
+
+    /// Translation of `key` without arguments in the primary locale, e.g. to
+    /// show translators the original text next to their translation.
+    pub fn source_text(&self, resource: &str, key: &str) -> Result<Cow<'_, str>, TranslateError> {
+        self.try_translate_with_args(self.locales.primary(), resource, key, None)
+    }
+
+    /// Translation of `key` without arguments in every main locale, e.g. to
+    /// review a message side by side.
+    pub fn all_locales_translation(
+        &self,
+        resource: &str,
+        key: &str,
+    ) -> BTreeMap<LanguageIdentifier, Result<String, TranslateError>> {
+        self.main_locales_translations(|locale| {
+            self.try_translate_with_args(locale, resource, key, None)
+                .map(Cow::into_owned)
+        })
+    }
+
+    // Translations in every main locale, sorted by locale.
+    pub(crate) fn main_locales_translations<T>(
+        &self,
+        translate: impl Fn(&LanguageIdentifier) -> T,
+    ) -> BTreeMap<LanguageIdentifier, T> {
+        self.locales
+            .main_locales()
+            .into_iter()
+            .map(|locale| {
+                let translation = translate(&locale);
+                (locale, translation)
+            })
+            .collect()
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but returns `default` when the
+    /// message or its attribute does not exist, other errors are returned.
+    pub fn translate_or_default<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+        default: &'a str,
+    ) -> Result<Cow<'a, str>, TranslateError> {
+        match self.try_translate_with_args(lang, resource, key, args) {
+            Err(
+                TranslateError::MessageIdNotExists { .. }
+                | TranslateError::MessageAttributeNotExists { .. },
+            ) => Ok(Cow::Borrowed(default)),
+            result => result,
+        }
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but the translation is
+    /// escaped for HTML, except the occurrences of the trusted `markup` values.
+    #[cfg(feature = "html")]
+    pub fn try_translate_html(
+        &self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+        markup: &[&str],
+    ) -> Result<String, TranslateError> {
+        let translation = self.try_translate_with_args(lang, resource, key, args)?;
+        Ok(crate::html::escape(&translation, markup))
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but writes the translation to
+    /// `out` instead of returning it.
+    pub fn write_translation<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<(), TranslateError> {
+        if self.pseudo_options.is_some() || self.postprocess.is_some() {
+            let translation = self.try_translate_with_args(lang, resource, key, args)?;
+            return Ok(out.write_str(&translation)?);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.count_format(resource, key);
+        let resource = self.resource(resource)?;
+        match self.args_with_default(lang, resource, key, args)? {
+            Some(args) => resource.write(out, lang, key, Some(&args)),
+            None => resource.write(out, lang, key, args),
+        }
+    }
+
+    /// Same as [`L10n::try_translate_with_args`] but returns the UTF-8 bytes of
+    /// the translation, for byte-oriented protocols.
+    pub fn try_translate_bytes(
+        &self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<Vec<u8>, TranslateError> {
+        let mut out = Vec::new();
+        self.write_translation_bytes(&mut out, lang, resource, key, args)?;
+        Ok(out)
+    }
+
+    /// Same as [`L10n::write_translation`] but appends the UTF-8 bytes of the
+    /// translation to `out`, e.g. to reuse a buffer in a loop.
+    pub fn write_translation_bytes(
+        &self,
+        out: &mut Vec<u8>,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<(), TranslateError> {
+        self.write_translation(&mut BytesWriter(out), lang, resource, key, args)
+    }
+
+    fn resource(
+        &self,
+        resource: &str,
+    ) -> Result<&L10nResource<Arc<FluentResource>>, TranslateError> {
+        self.resources
+            .get(self.lookup_name(resource).as_ref())
+            .ok_or_else(|| TranslateError::ResourceNotExists(resource.to_string()))
+    }
+
+    // Returns `None` when there is no default argument to add to `args`.
+    fn args_with_default<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &L10nResource<Arc<FluentResource>>,
+        key: &str,
+        args: Option<&'a FluentArgs>,
+    ) -> Result<Option<FluentArgs<'a>>, TranslateError> {
+        let locale_args_default = self.locale_args_default.get(lang);
+        if self.args_default.is_empty() && locale_args_default.is_none() {
+            return Ok(None);
+        }
+
+        let required_variables = resource.required_variables(key)?;
+        // The defaults of the locale take precedence over the global ones.
+        let missing_args_default: Vec<_> = locale_args_default
+            .into_iter()
+            .flatten()
+            .chain(self.args_default.iter().filter(|(name, _)| {
+                !matches!(locale_args_default, Some(defaults) if defaults.contains_key(*name))
+            }))
+            .filter(|(name, _)| {
+                required_variables.contains(name.as_str())
+                    && args.and_then(|args| args.get(name.as_str())).is_none()
+            })
+            .collect();
+
+        if missing_args_default.is_empty() {
+            return Ok(None);
+        }
+
+        let mut args_with_default = FluentArgs::new();
+        if let Some(args) = args {
+            for (name, value) in args.iter() {
+                args_with_default.set(name, value.clone());
+            }
+        }
+        for (name, value) in missing_args_default {
+            args_with_default.set(name.as_str(), value.as_str());
+        }
+        Ok(Some(args_with_default))
+    }
+
+    /// Errors for the messages not found in every locale of their resource,
+    /// e.g. to check in a test the keys of the types deriving `#[l10n_keys]`
+    /// against translations loaded at runtime.
+    pub fn check_message_keys<'k>(
+        &self,
+        keys: impl IntoIterator<Item = (&'k str, &'k str)>,
+    ) -> Vec<TranslateError> {
+        keys.into_iter()
+            .filter_map(|(resource, key)| self.required_variables(resource, key).err())
+            .collect()
+    }
+
+    /// The main locales in the order they were configured, e.g. for a
+    /// language switcher.
+    pub fn available_locales(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+        self.locales.into_iter().map(|entry| entry.locale())
+    }
+
+    /// The names of the resources, in no particular order.
+    pub fn resource_names(&self) -> impl Iterator<Item = &str> {
+        self.resources.keys().map(String::as_str)
+    }
+
+    /// The main locales of the bundles of `resource`, sorted.
+    pub fn resource_locales(&self, resource: &str) -> Vec<&LanguageIdentifier> {
+        self.resource(resource)
+            .map(|resource| resource.locales())
+            .unwrap_or_default()
+    }
+
+    /// The `(resource, key)` of the messages and attributes neither in `used`
+    /// nor referenced by another message of the resource, sorted. With the
+    /// `registry` feature, `used` is typically `l10n::used_messages()`, the
+    /// keys only translated at runtime (e.g. [`L10n::translate`]) must be
+    /// added to it.
+    pub fn report_unused(&self, used: &HashSet<(String, String)>) -> Vec<(String, String)> {
+        let mut unused = vec![];
+        for (name, resource) in &self.resources {
+            let referenced_keys = resource.referenced_keys();
+            for key in resource.keys() {
+                let entry = (name.to_owned(), key.to_owned());
+                if !used.contains(&entry) && !referenced_keys.contains(key) {
+                    unused.push(entry);
+                }
+            }
+        }
+        unused.sort();
+        unused
+    }
+
+    /// The messages of `resource` sorted by id, e.g. for a coverage report.
+    pub fn messages(&self, resource: &str) -> Result<Vec<MessageInfo>, TranslateError> {
+        Ok(self.resource(resource)?.messages())
+    }
+
+    pub fn required_variables(
+        &self,
+        resource: &str,
+        key: &str,
+    ) -> Result<HashSet<&str>, TranslateError> {
+        self.resource(resource)?.required_variables(key)
+    }
+
+    /// Required variables only in `key_a`, only in `key_b` and in both, e.g.
+    /// to migrate the call sites of a message split in two.
+    pub fn args_diff(
+        &self,
+        resource: &str,
+        key_a: &str,
+        key_b: &str,
+    ) -> Result<ArgsDiff<'_>, TranslateError> {
+        let variables_a = self.required_variables(resource, key_a)?;
+        let variables_b = self.required_variables(resource, key_b)?;
+        Ok((
+            variables_a.difference(&variables_b).copied().collect(),
+            variables_b.difference(&variables_a).copied().collect(),
+            variables_a.intersection(&variables_b).copied().collect(),
+        ))
+    }
+
+    /// Translates `key` with `variable` set to `variant`, which must be a
+    /// variant of a select expression of the message in `lang`, e.g. to test
+    /// every branch of a select expression.
+    pub fn translate_variant<'a>(
+        &'a self,
+        lang: &LanguageIdentifier,
+        resource: &str,
+        key: &str,
+        variable: &str,
+        variant: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<Cow<'a, str>, TranslateError> {
+        let variants = self
+            .resource(resource)?
+            .locale_selector_variants(lang, key, variable)?;
+        if !variants.contains(variant) {
+            return Err(TranslateError::VariantNotExists {
+                variant: variant.to_string(),
+                variable: variable.to_string(),
+                key: key.to_string(),
+                locale: lang.to_owned(),
+            });
+        }
+
+        let mut variant_args = FluentArgs::new();
+        if let Some(args) = args {
+            for (name, value) in args.iter() {
+                variant_args.set(name, value.clone());
+            }
+        }
+        variant_args.set(variable, FluentValue::try_number(variant));
+        self.try_translate_with_args(lang, resource, key, Some(&variant_args))
+    }
+
+    /// The variants of the select expressions on `variable` of a message in
+    /// every locale, sorted.
+    pub fn selector_variants(
+        &self,
+        resource: &str,
+        key: &str,
+        variable: &str,
+    ) -> Result<Vec<String>, TranslateError> {
+        self.resource(resource)?.selector_variants(key, variable)
+    }
+
+    pub fn locales_required_variables(
+        &self,
+        resource: &str,
+        key: &str,
+    ) -> Result<HashMap<&LanguageIdentifier, HashSet<&str>>, TranslateError> {
+        self.resource(resource)?.locales_required_variables(key)
+    }
+
+    /// Variables formatted as a number in any locale, i.e. the first argument
+    /// of `NUMBER`, `ORDINAL` or `RAW_NUMBER`.
+    pub fn numeric_variables(
+        &self,
+        resource: &str,
+        key: &str,
+    ) -> Result<HashSet<&str>, TranslateError> {
+        self.resource(resource)?.numeric_variables(key)
+    }
+
+    /// Variables referenced by each message and attribute of the resource in
+    /// any locale, to audit them against the arguments set in the code.
+    pub fn defined_variables(
+        &self,
+        resource: &str,
+    ) -> Result<BTreeMap<&str, HashSet<&str>>, TranslateError> {
+        Ok(self.resource(resource)?.defined_variables())
+    }
+
+    /// Locale and file providing the message `key` (its attribute is ignored)
+    /// of `resource` in the bundle of `locale`, only available when the
+    /// builder records the provenance, see [`L10nBuilder::record_provenance`].
+    pub fn message_provenance(
+        &self,
+        resource: &str,
+        key: &str,
+        locale: &LanguageIdentifier,
+    ) -> Option<&MessageProvenance> {
+        let id = key.split_once('.').map_or(key, |(id, _)| id);
+        self.provenances.as_ref()?.get(&(
+            self.lookup_name(resource).into_owned(),
+            locale.to_owned(),
+            id.to_owned(),
+        ))
+    }
+
+    fn lookup_name<'a>(&self, resource: &'a str) -> Cow<'a, str> {
+        match self.case_insensitive_lookup {
+            true => Cow::Owned(case_insensitive_name(resource)),
+            false => Cow::Borrowed(resource),
+        }
+    }
+
+    /// Diagnostic helper to see the cost of adding resources or locales, the
+    /// parsed entries and the bundles themselves are not measured.
+    pub fn estimated_memory(&self) -> MemoryEstimate {
+        MemoryEstimate {
+            source_bytes: self
+                .fluent_resources
+                .iter()
+                .map(|resource| resource.source().len())
+                .sum(),
+            bundles: self
+                .resources
+                .values()
+                .map(|resource| resource.locales().len())
+                .sum(),
+        }
+    }
+
+    /// The names of the functions added to the bundles, sorted.
+    pub fn registered_functions(&self) -> Vec<&str> {
+        self.functions.iter().map(String::as_str).collect()
+    }
+
+    pub fn required_functions(&self) -> HashSet<&str> {
+        let mut functions = HashSet::new();
+        let resources = &self.fluent_resources;
+
+        for resource in resources {
+            for entry in resource.entries() {
+                match entry {
+                    Entry::Message(message) => {
+                        if let Some(pattern) = &message.value {
+                            self.parse_pattern_functions(pattern, &mut functions);
+                        }
+                        for attribute in &message.attributes {
+                            self.parse_pattern_functions(&attribute.value, &mut functions);
+                        }
+                    }
+                    Entry::Term(term) => {
+                        self.parse_pattern_functions(&term.value, &mut functions);
+                        for attribute in &term.attributes {
+                            self.parse_pattern_functions(&attribute.value, &mut functions);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        functions
+    }
+
+    /// Same as [`L10n::required_functions`] but ignores the terms no message
+    /// references, directly or through other terms.
+    pub fn required_functions_without_unused_terms(&self) -> HashSet<&str> {
+        let mut functions = HashSet::new();
+        let mut referenced_terms = HashSet::new();
+        let resources = &self.fluent_resources;
+
+        let mut patterns: Vec<&Pattern<&str>> = Vec::new();
+        for resource in resources {
+            for entry in resource.entries() {
+                if let Entry::Message(message) = entry {
+                    patterns.extend(&message.value);
+                    patterns.extend(message.attributes.iter().map(|attribute| &attribute.value));
+                }
+            }
+        }
+
+        while let Some(pattern) = patterns.pop() {
+            self.parse_pattern_functions(pattern, &mut functions);
+            let mut terms = HashSet::new();
+            parse_pattern_terms(pattern, &mut terms);
+            for term_id in terms {
+                if !referenced_terms.insert(term_id) {
+                    continue;
+                }
+                // The term can be defined in many resources and locales.
+                for resource in resources {
+                    for entry in resource.entries() {
+                        match entry {
+                            Entry::Term(term) if term.id.name == term_id => {
+                                patterns.push(&term.value);
+                                patterns.extend(
+                                    term.attributes.iter().map(|attribute| &attribute.value),
+                                );
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        functions
+    }
+
+    fn parse_pattern_functions<'a>(
+        &'a self,
+        pattern: &Pattern<&'a str>,
+        functions: &mut HashSet<&'a str>,
+    ) {
+        for element in &pattern.elements {
+            if let PatternElement::Placeable { expression } = element {
+                self.parse_expression_functions(expression, functions);
+            }
+        }
+    }
+
+    fn parse_expression_functions<'a>(
+        &'a self,
+        expression: &Expression<&'a str>,
+        functions: &mut HashSet<&'a str>,
+    ) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                self.parse_inline_expression_functions(selector, functions);
+                for variant in variants {
+                    self.parse_pattern_functions(&variant.value, functions);
+                }
+            }
+            Expression::Inline(inline_expression) => {
+                self.parse_inline_expression_functions(inline_expression, functions);
+            }
+        }
+    }
+
+    fn parse_inline_expression_functions<'a>(
+        &'a self,
+        inline_expression: &InlineExpression<&'a str>,
+        functions: &mut HashSet<&'a str>,
+    ) {
+        if let InlineExpression::FunctionReference { id, .. } = inline_expression {
+            functions.insert(id.name);
+        }
+    }
+}
+
+fn parse_pattern_terms<'a>(pattern: &Pattern<&'a str>, terms: &mut HashSet<&'a str>) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            parse_expression_terms(expression, terms);
+        }
+    }
+}
+
+fn parse_expression_terms<'a>(expression: &Expression<&'a str>, terms: &mut HashSet<&'a str>) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            parse_inline_expression_terms(selector, terms);
+            for variant in variants {
+                parse_pattern_terms(&variant.value, terms);
+            }
+        }
+        Expression::Inline(inline_expression) => {
+            parse_inline_expression_terms(inline_expression, terms);
+        }
+    }
+}
+
+fn parse_inline_expression_terms<'a>(
+    inline_expression: &InlineExpression<&'a str>,
+    terms: &mut HashSet<&'a str>,
+) {
+    let arguments = match inline_expression {
+        InlineExpression::TermReference { id, arguments, .. } => {
+            terms.insert(id.name);
+            arguments.as_ref()
+        }
+        InlineExpression::FunctionReference { arguments, .. } => Some(arguments),
+        InlineExpression::Placeable { expression } => {
+            parse_expression_terms(expression, terms);
+            None
+        }
+        _ => None,
+    };
+    if let Some(arguments) = arguments {
+        for argument in &arguments.positional {
+            parse_inline_expression_terms(argument, terms);
+        }
+        for argument in &arguments.named {
+            parse_inline_expression_terms(&argument.value, terms);
+        }
+    }
+}
+
+// A message commented with `# @extends base` gets the attributes of `base`
+// (and of the bases of `base`) it does not define, as `.attribute = {
+// base.attribute }` so they are formatted with the same arguments. The base
+// is looked up in the locale of the message then in the global resources.
+fn inherit_attributes<R: Borrow<FluentResource>>(
+    fluent_resources: &[R],
+    unnamed_resources: &UnnamedResources,
+    named_resources: &NamedResources,
+) -> Result<Vec<(ResourceIndex, FluentResource)>, BuildErrors> {
+    const EXTENDS: &str = "@extends";
+    let fluent_resources: Vec<&FluentResource> =
+        fluent_resources.iter().map(Borrow::borrow).collect();
+    if !fluent_resources
+        .iter()
+        .any(|resource| resource.source().contains(EXTENDS))
+    {
+        return Ok(vec![]);
+    }
+
+    let mut resource_locales: Vec<Option<&LanguageIdentifier>> = vec![None; fluent_resources.len()];
+    for ((_, locale), indices) in unnamed_resources {
+        for index in indices {
+            resource_locales[*index] = Some(locale);
+        }
+    }
+    for resources in named_resources.values() {
+        for (locale, index) in resources {
+            resource_locales[*index] = Some(locale);
+        }
+    }
+
+    // Attributes and base of the messages by locale (`None` when global)
+    let mut attributes: HashMap<(Option<&LanguageIdentifier>, String), Vec<&str>> = HashMap::new();
+    let mut bases: HashMap<(Option<&LanguageIdentifier>, String), String> = HashMap::new();
+    for (index, resource) in fluent_resources.iter().enumerate() {
+        let locale = resource_locales[index];
+        for entry in resource.entries() {
+            if let Entry::Message(message) = entry {
+                attributes.insert(
+                    (locale, message.id.name.to_string()),
+                    message
+                        .attributes
+                        .iter()
+                        .map(|attribute| attribute.id.name)
+                        .collect(),
+                );
+            }
+        }
+        if !resource.source().contains(EXTENDS) {
+            continue;
+        }
+        // The runtime parser skips the comments
+        let parsed = fluent_syntax::parser::parse(resource.source())
+            .unwrap_or_else(|(resource, _)| resource);
+        for entry in parsed.body {
+            if let Entry::Message(message) = entry {
+                let base = message
+                    .comment
+                    .iter()
+                    .flat_map(|comment| &comment.content)
+                    .find_map(|line| {
+                        line.trim()
+                            .strip_prefix(EXTENDS)
+                            .map(|base| base.trim().to_string())
+                    });
+                if let Some(base) = base {
+                    bases.insert((locale, message.id.name.to_string()), base);
+                }
+            }
+        }
+    }
+    let find = |locale, id: &str| {
+        [locale, None].into_iter().find_map(|locale| {
+            attributes
+                .get(&(locale, id.to_string()))
+                .map(|attributes| (locale, attributes))
+        })
+    };
+
+    // Inherited `(attribute, message defining it)` by resource and message
+    let mut inherited: HashMap<ResourceIndex, HashMap<String, Vec<(String, String)>>> =
+        HashMap::new();
+    let mut errors = vec![];
+    for (index, resource) in fluent_resources.iter().enumerate() {
+        let locale = resource_locales[index];
+        for entry in resource.entries() {
+            let message = match entry {
+                Entry::Message(message) => message,
+                _ => continue,
+            };
+            let mut base = match bases.get(&(locale, message.id.name.to_string())) {
+                Some(base) => base,
+                None => continue,
+            };
+            let mut defined: HashSet<&str> = message
+                .attributes
+                .iter()
+                .map(|attribute| attribute.id.name)
+                .collect();
+            let mut cycle = vec![message.id.name.to_string()];
+            let mut message_inherited = vec![];
+            loop {
+                if cycle.contains(base) {
+                    cycle.push(base.to_owned());
+                    errors.push(BuildError::ExtendsCycle {
+                        message: message.id.name.to_string(),
+                        cycle,
+                    });
+                    break;
+                }
+                let (base_locale, base_attributes) = match find(locale, base) {
+                    Some(found) => found,
+                    None => {
+                        errors.push(BuildError::UnknownBaseMessage {
+                            message: cycle.pop().unwrap(),
+                            base: base.to_owned(),
+                        });
+                        break;
+                    }
+                };
+                for attribute in base_attributes {
+                    if defined.insert(attribute) {
+                        message_inherited.push((attribute.to_string(), base.to_owned()));
+                    }
+                }
+                cycle.push(base.to_owned());
+                match bases.get(&(base_locale, base.to_owned())) {
+                    Some(next) => base = next,
+                    None => break,
+                }
+            }
+            if !message_inherited.is_empty() {
+                inherited
+                    .entry(index)
+                    .or_default()
+                    .insert(message.id.name.to_string(), message_inherited);
+            }
+        }
+    }
+    if !errors.is_empty() {
+        errors.sort();
+        errors.dedup();
+        return Err(BuildErrors(errors));
+    }
+
+    let mut rewritten = vec![];
+    for (index, messages) in inherited {
+        let mut parsed = fluent_syntax::parser::parse(fluent_resources[index].source())
+            .unwrap_or_else(|(resource, _)| resource);
+        for entry in &mut parsed.body {
+            if let Entry::Message(message) = entry {
+                for (attribute, base) in messages.get(message.id.name).into_iter().flatten() {
+                    message.attributes.push(Attribute {
+                        id: Identifier { name: attribute },
+                        value: Pattern {
+                            elements: vec![PatternElement::Placeable {
+                                expression: Expression::Inline(
+                                    InlineExpression::MessageReference {
+                                        id: Identifier { name: base },
+                                        attribute: Some(Identifier { name: attribute }),
+                                    },
+                                ),
+                            }],
+                        },
+                    });
+                }
+            }
+        }
+        let source = fluent_syntax::serializer::serialize(&parsed);
+        let fluent_resource = FluentResource::try_new(source)
+            .expect("Unexpected error, the serialized resource should be valid");
+        rewritten.push((index, fluent_resource));
+    }
+    Ok(rewritten)
+}
+
+const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+// Enough numbers to hit every category of the CLDR rules, the fractions are
+// often the only `other` numbers (e.g. Polish).
+fn plural_samples() -> impl Iterator<Item = String> {
+    (0..=200)
+        .chain([1000, 10000, 100000, 1000000])
+        .map(|number: u32| number.to_string())
+        .chain(["0.5", "1.5", "2.5", "10.5"].map(String::from))
+}
+
+fn plural_category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::ZERO => "zero",
+        PluralCategory::ONE => "one",
+        PluralCategory::TWO => "two",
+        PluralCategory::FEW => "few",
+        PluralCategory::MANY => "many",
+        PluralCategory::OTHER => "other",
+    }
+}
+
+// The plural rules of a locale a select expression is checked against.
+struct SelectorRules {
+    cardinal: PluralRules,
+    ordinal: Option<PluralRules>,
+}
+
+impl SelectorRules {
+    // Ordinal rules for `ORDINAL(..)` and `NUMBER(.., type: "ordinal")`,
+    // cardinal rules for a variable or a number, `None` for the other
+    // selectors (strings, references, other functions).
+    fn for_selector(&self, selector: &InlineExpression<&str>) -> Option<&PluralRules> {
+        match selector {
+            InlineExpression::VariableReference { .. } | InlineExpression::NumberLiteral { .. } => {
+                Some(&self.cardinal)
+            }
+            InlineExpression::FunctionReference { id, .. } if id.name == "ORDINAL" => {
+                self.ordinal.as_ref()
+            }
+            InlineExpression::FunctionReference { id, arguments } if id.name == "NUMBER" => {
+                let is_ordinal = arguments.named.iter().any(|argument| {
+                    argument.name.name == "type"
+                        && matches!(
+                            argument.value,
+                            InlineExpression::StringLiteral { value: "ordinal" }
+                        )
+                });
+                match is_ordinal {
+                    true => self.ordinal.as_ref(),
+                    false => Some(&self.cardinal),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+fn parse_pattern_missing_plural_categories(
+    rules: &SelectorRules,
+    pattern: &Pattern<&str>,
+    missing: &mut BTreeSet<&'static str>,
+) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            parse_expression_missing_plural_categories(rules, expression, missing);
+        }
+    }
+}
+
+fn parse_expression_missing_plural_categories(
+    rules: &SelectorRules,
+    expression: &Expression<&str>,
+    missing: &mut BTreeSet<&'static str>,
+) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            let mut categories = HashSet::new();
+            let mut numbers = vec![];
+            for variant in variants {
+                match variant.key {
+                    VariantKey::Identifier { name } => {
+                        categories.insert(name);
+                    }
+                    VariantKey::NumberLiteral { value } => numbers.extend(value.parse::<f64>()),
+                }
+                parse_pattern_missing_plural_categories(rules, &variant.value, missing);
+            }
+
+            let is_plural = !numbers.is_empty()
+                || categories
+                    .iter()
+                    .any(|name| *name != "other" && PLURAL_CATEGORIES.contains(name));
+            let rules = match rules.for_selector(selector) {
+                Some(rules) if is_plural => rules,
+                _ => return,
+            };
+            for sample in plural_samples() {
+                if matches!(sample.parse::<f64>(), Ok(number) if numbers.contains(&number)) {
+                    continue;
+                }
+                if let Ok(category) = rules.select(sample.as_str()) {
+                    let name = plural_category_name(category);
+                    if !categories.contains(name) {
+                        missing.insert(name);
+                    }
+                }
+            }
+        }
+        Expression::Inline(InlineExpression::Placeable { expression }) => {
+            parse_expression_missing_plural_categories(rules, expression, missing);
+        }
+        Expression::Inline(_) => {}
+    }
+}
+
+impl Default for L10nBuilder {
+    fn default() -> Self {
+        Self {
+            locales: Default::default(),
+            fluent_resources: Default::default(),
+            global_unnamed_resources: Default::default(),
+            unnamed_resources: Default::default(),
+            named_resources: Default::default(),
+            transform: Default::default(),
+            locale_transforms: Default::default(),
+            formatter: Default::default(),
+            use_isolating: true,
+            functions: Default::default(),
+            locale_functions: Default::default(),
+            bundle_functions: Default::default(),
+            primary_locale: Default::default(),
+            args_default: Default::default(),
+            locale_args_default: Default::default(),
+            pseudo_options: None,
+            root: Default::default(),
+            paths: Default::default(),
+            preprocessor: Default::default(),
+            postprocess: Default::default(),
+            record_provenance: false,
+            embedded_defaults: Default::default(),
+            cache_if: Default::default(),
+            ignore: Default::default(),
+            shared_resources: Default::default(),
+            case_insensitive_lookup: false,
+            default_locale: None,
+            resolve_literals: false,
+            complete_resources: Default::default(),
+            check_terms: false,
+            lowercase_names: Default::default(),
+        }
+    }
+}
+
+impl Debug for L10nBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("L10nBuilder")
+            .field("locales", &self.locales)
+            .finish()
+    }
+}
+
+impl L10nBuilder {
+    pub fn new(locales: Locales) -> Self {
+        Self {
+            locales,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_global_unnamed_resource(&mut self, resource: FluentResource) {
+        self.global_unnamed_resources
+            .push(self.fluent_resources.len());
+        self.fluent_resources.push(resource);
+    }
+
+    pub fn add_unnamed_resource(
+        &mut self,
+        relative_path: &Path,
+        locale: &LanguageIdentifier,
+        fluent_resource: FluentResource,
+    ) {
+        let path = normalized_path(relative_path);
+        let key = (path, locale.to_owned());
+        let resources = match self.unnamed_resources.get_mut(&key) {
+            Some(v) => v,
+            None => {
+                self.unnamed_resources
+                    .insert(key.clone(), Vec::with_capacity(1));
+                self.unnamed_resources.get_mut(&key).unwrap()
+            }
+        };
+        resources.push(self.fluent_resources.len());
+        self.fluent_resources.push(fluent_resource);
+    }
+
+    pub fn add_named_resource(
+        &mut self,
+        name: &str,
+        relative_path: &Path,
+        locale: &LanguageIdentifier,
+        fluent_resource: FluentResource,
+    ) -> Result<(), ParserError> {
+        let resource_name = normalized_path(&relative_path.join(name));
+        let resources = match self.named_resources.get_mut(&resource_name) {
+            Some(v) => v,
+            None => {
+                self.named_resources
+                    .insert(resource_name.clone(), HashMap::new());
+                self.named_resources.get_mut(&resource_name).unwrap()
+            }
+        };
+        if resources.contains_key(locale) {
+            return Err(ParserError::NamedResourceExists {
+                name: resource_name,
+                locale: locale.to_owned(),
+            });
+        }
+        resources.insert(locale.to_owned(), self.fluent_resources.len());
+        self.fluent_resources.push(fluent_resource);
+        Ok(())
+    }
+
+    /// Same as [`L10nBuilder::add_named_resource`] but merges the messages
+    /// with the existing resource for the locale, the messages of
+    /// `fluent_resource` override the existing ones.
+    pub fn add_named_resource_overriding(
+        &mut self,
+        name: &str,
+        relative_path: &Path,
+        locale: &LanguageIdentifier,
+        fluent_resource: FluentResource,
+    ) {
+        let resource_name = normalized_path(&relative_path.join(name));
+        let index = self
+            .named_resources
+            .get(&resource_name)
+            .and_then(|resources| resources.get(locale))
+            .copied();
+        match index {
+            Some(index) => {
+                // The bundles add the entries in order, the last entry with
+                // the same identifier wins.
+                let existing = self.fluent_resources[index].source();
+                let source = format!("{}\n{}", existing, fluent_resource.source());
+                self.fluent_resources[index] = FluentResource::try_new(source)
+                    .expect("Unexpected error, the resources were already parsed");
+            }
+            None => self
+                .add_named_resource(name, relative_path, locale, fluent_resource)
+                .expect("Unexpected error, the resource does not exist"),
+        }
+    }
+
+    pub fn build(mut self) -> Result<L10n, BuildErrors> {
+        self.add_embedded_defaults();
+        for (index, fluent_resource) in inherit_attributes(
+            &self.fluent_resources,
+            &self.unnamed_resources,
+            &self.named_resources,
+        )? {
+            self.fluent_resources[index] = fluent_resource;
+        }
+        L10n::new(self)
+    }
+
+    /// Adds default named resources, typically embedded in the binary with
+    /// `include_str!`, as `(locale, resource name, fluent source)`. They are
+    /// only used for the resources and locales missing from the parsed ones,
+    /// the locale must be part of the builder locales.
+    pub fn with_embedded_defaults<'a>(
+        mut self,
+        defaults: impl IntoIterator<Item = (LanguageIdentifier, &'a str, &'a str)>,
+    ) -> Result<Self, ParserError> {
+        for (locale, name, source) in defaults {
+            let fluent_resource = FluentResource::try_new(source.to_string())
+                .map_err(|(_, errors)| ParserError::FluentParser { errors })?;
+            self.embedded_defaults
+                .push((locale, name.to_string(), fluent_resource));
+        }
+        Ok(self)
+    }
+
+    fn add_embedded_defaults(&mut self) {
+        let locales = self.locales.all_locales();
+        for (locale, name, fluent_resource) in std::mem::take(&mut self.embedded_defaults) {
+            let exists = matches!(
+                self.named_resources.get(&name),
+                Some(resources) if resources.contains_key(&locale)
+            );
+            if locales.contains(&locale) && !exists {
+                self.add_named_resource(&name, Path::new(""), &locale, fluent_resource)
+                    .expect("Unexpected error, the resource does not exist");
+            }
+        }
+    }
+
+    /// Loads the output of [`L10n::serialize`], functions, transform,
+    /// formatter and other options are not serialized and must be set again.
+    #[cfg(feature = "serialize")]
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let serialized: SerializedL10n<String> = bincode::deserialize(bytes)?;
+        let locales = Locales::try_new(
+            serialized
+                .locales
+                .into_iter()
+                .map(|(main, fallback)| crate::locales::LocaleEntry::new(main, fallback))
+                .collect(),
+        )?;
+        let fluent_resources = serialized
+            .sources
+            .into_iter()
+            .map(|source| {
+                FluentResource::try_new(source)
+                    .map_err(|(_, errors)| SerializeError::FluentParser { errors })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            locales,
+            fluent_resources,
+            global_unnamed_resources: serialized.resources_index.global_unnamed_resources,
+            unnamed_resources: serialized.resources_index.unnamed_resources,
+            named_resources: serialized.resources_index.named_resources,
+            shared_resources: serialized.resources_index.shared_resources,
+            ..Default::default()
+        })
+    }
+
+    pub fn parse(
+        path: impl AsRef<Path>,
+        locales_option: Option<Locales>,
+    ) -> Result<Self, ParserError> {
+        Self::parse_with_progress(path, locales_option, |_| {})
+    }
+
+    /// Same as [`L10nBuilder::parse`] but `on_file` is called with the path of
+    /// each fluent file before it is read.
+    pub fn parse_with_progress(
+        path: impl AsRef<Path>,
+        locales_option: Option<Locales>,
+        on_file: fn(&Path),
+    ) -> Result<Self, ParserError> {
+        Self::default().parse_path_with_progress(path, locales_option, on_file)
+    }
+
+    /// Same as [`L10nBuilder::parse`] but keeps the options already set on
+    /// the builder, e.g. to set a preprocessor before the files are read.
+    pub fn parse_path(
+        self,
+        path: impl AsRef<Path>,
+        locales_option: Option<Locales>,
+    ) -> Result<Self, ParserError> {
+        self.parse_path_with_progress(path, locales_option, |_| {})
+    }
+
+    fn parse_path_with_progress(
+        self,
+        path: impl AsRef<Path>,
+        locales_option: Option<Locales>,
+        on_file: fn(&Path),
+    ) -> Result<Self, ParserError> {
+        let path = path.as_ref();
+        let mut builder = Self {
+            root: Some(path.to_path_buf()),
+            ..self
+        };
+        let locales_to_visit = locales_option.as_ref().map(|locales| locales.all_locales());
+        let mut locales_visited = HashSet::new();
+
+        let dir = fs::read_dir(path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => ParserError::ReadPath {
+                path: path.to_path_buf(),
+                err,
+            },
+            _ => err.into(),
+        })?;
+
+        for entry in dir {
+            let entry_path = entry?.path();
+            if builder.is_ignored(&entry_path) {
+                continue;
+            }
+            let entry_name = get_entry_name(&entry_path);
+
+            if entry_path.is_file() {
+                let name = match entry_name {
+                    Some(v) => v.to_string_lossy(),
+                    None => continue,
+                };
+                if !name.starts_with('_') {
+                    return Err(ParserError::GlobalNamedResource { path: entry_path });
+                }
+
+                on_file(&entry_path);
+                let fluent_resource =
+                    Self::read_fluent_resource(&entry_path, builder.preprocessor)?;
+                builder.add_global_unnamed_resource(fluent_resource);
+                builder.record_path(entry_path);
+            } else if entry_path.is_dir() {
+                let dir_name = match entry_name.and_then(|v| v.to_str()) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                let locale = match locale_to_visit(dir_name, &locales_to_visit)? {
+                    Some(locale) => locale,
+                    None => continue,
+                };
+                locales_visited.insert(locale.clone());
+
+                builder.parse_locale_directory(
+                    &locale,
+                    &entry_path,
+                    &PathBuf::default(),
+                    on_file,
+                )?;
+            }
+        }
+
+        check_mandatory_locales(&locales_option, &locales_visited)?;
+        builder.locales = locales_option.unwrap_or_else(|| Locales::from(locales_visited));
+
+        Ok(builder)
+    }
+
+    /// Same as [`L10nBuilder::parse`] but walks a directory embedded in the
+    /// binary with `include_dir!`, for deployments without the `l10n`
+    /// directory. [`L10n::reload_file`] is not supported for these resources.
+    #[cfg(feature = "embed")]
+    pub fn from_embedded(
+        dir: &include_dir::Dir,
+        locales_option: Option<Locales>,
+    ) -> Result<Self, ParserError> {
+        let mut builder = Self::default();
+        let locales_to_visit = locales_option.as_ref().map(|locales| locales.all_locales());
+        let mut locales_visited = HashSet::new();
+
+        for entry in dir.entries() {
+            let entry_path = entry.path();
+            if builder.is_ignored(entry_path) {
+                continue;
+            }
+
+            match entry {
+                include_dir::DirEntry::File(file) => {
+                    let name = match get_file_name(entry_path) {
+                        Some(v) => v.to_string_lossy(),
+                        None => continue,
+                    };
+                    if !name.starts_with('_') {
+                        return Err(ParserError::GlobalNamedResource {
+                            path: entry_path.to_path_buf(),
+                        });
+                    }
+
+                    let fluent_resource = builder.parse_embedded_file(file)?;
+                    builder.add_global_unnamed_resource(fluent_resource);
+                }
+                include_dir::DirEntry::Dir(locale_dir) => {
+                    let dir_name = match entry_path.file_name().and_then(|v| v.to_str()) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    let locale = match locale_to_visit(dir_name, &locales_to_visit)? {
+                        Some(locale) => locale,
+                        None => continue,
+                    };
+                    locales_visited.insert(locale.clone());
+
+                    builder.parse_embedded_locale_directory(
+                        &locale,
+                        locale_dir,
+                        &PathBuf::default(),
+                    )?;
+                }
+            }
+        }
+
+        check_mandatory_locales(&locales_option, &locales_visited)?;
+        builder.locales = locales_option.unwrap_or_else(|| Locales::from(locales_visited));
+
+        Ok(builder)
+    }
+
+    /// Same as [`L10nBuilder::parse`] from `(path, source)` pairs following
+    /// the layout of the `l10n` directory, e.g. `("en/settings/account.ftl",
+    /// source)`, for translations not read from the file system. A `\` is a
+    /// separator too. [`L10n::reload_file`] is not supported for these
+    /// resources.
+    pub fn from_sources<P: AsRef<Path>>(
+        sources: impl IntoIterator<Item = (P, String)>,
+        locales_option: Option<Locales>,
+    ) -> Result<Self, ParserError> {
+        let mut builder = Self::default();
+        let locales_to_visit = locales_option.as_ref().map(|locales| locales.all_locales());
+        let mut locales_visited = HashSet::new();
+
+        for (path, source) in sources {
+            // The separators of the paths are the same on every platform
+            let path = PathBuf::from(path.as_ref().to_string_lossy().replace('\\', "/"));
+            let path = path.as_path();
+            let name = match get_file_name(path) {
+                Some(v) => v.to_string_lossy().into_owned(),
+                None => continue,
+            };
+            let mut directories = path.parent().unwrap_or_else(|| Path::new("")).iter();
+            let dir_name = match directories.next() {
+                Some(dir_name) => dir_name.to_string_lossy(),
+                None => {
+                    if !name.starts_with('_') {
+                        return Err(ParserError::GlobalNamedResource {
+                            path: path.to_path_buf(),
+                        });
+                    }
+                    let resource = Self::parse_fluent_source(path, source, builder.preprocessor)?;
+                    builder.add_global_unnamed_resource(resource);
+                    continue;
+                }
+            };
+            let locale = match locale_to_visit(&dir_name, &locales_to_visit)? {
+                Some(locale) => locale,
+                None => continue,
+            };
+            locales_visited.insert(locale.clone());
+
+            let relative_path = directories.as_path();
+            let resource = Self::parse_fluent_source(path, source, builder.preprocessor)?;
+            if name.starts_with('_') {
+                builder.add_unnamed_resource(relative_path, &locale, resource);
+            } else {
+                builder.check_name_collision(&name, relative_path, &locale, path)?;
+                builder.add_named_resource(&name, relative_path, &locale, resource)?;
+            }
+        }
+
+        check_mandatory_locales(&locales_option, &locales_visited)?;
+        builder.locales = locales_option.unwrap_or_else(|| Locales::from(locales_visited));
+
+        Ok(builder)
+    }
+
+    #[cfg(feature = "embed")]
+    fn parse_embedded_locale_directory(
+        &mut self,
+        locale: &LanguageIdentifier,
+        dir: &include_dir::Dir,
+        relative_path: &Path,
+    ) -> Result<(), ParserError> {
+        for entry in dir.entries() {
+            let entry_path = entry.path();
+            if self.is_ignored(entry_path) {
+                continue;
+            }
+
+            match entry {
+                include_dir::DirEntry::File(file) => {
+                    let name = match get_file_name(entry_path) {
+                        Some(v) => v.to_string_lossy(),
+                        None => continue,
+                    };
+                    let resource = self.parse_embedded_file(file)?;
+                    if name.starts_with('_') {
+                        self.add_unnamed_resource(relative_path, locale, resource);
+                    } else {
+                        self.check_name_collision(&name, relative_path, locale, entry_path)?;
+                        self.add_named_resource(&name, relative_path, locale, resource)?;
+                    }
+                }
+                include_dir::DirEntry::Dir(dir) => {
+                    let name = match entry_path.file_name() {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    self.parse_embedded_locale_directory(locale, dir, &relative_path.join(name))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "embed")]
+    fn parse_embedded_file(&self, file: &include_dir::File) -> Result<FluentResource, ParserError> {
+        let source = std::str::from_utf8(file.contents())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Self::parse_fluent_source(file.path(), source.to_string(), self.preprocessor)
+    }
+
+    fn parse_locale_directory(
+        &mut self,
+        locale: &LanguageIdentifier,
+        locale_path: &Path,
+        relative_path: &Path,
+        on_file: fn(&Path),
+    ) -> Result<(), ParserError> {
+        let path = locale_path.join(relative_path);
+
+        for entry in fs::read_dir(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => ParserError::ReadPath { path, err },
+            _ => err.into(),
+        })? {
+            let entry_path = entry?.path();
+            if self.is_ignored(&entry_path) {
+                continue;
+            }
+            let name = match get_entry_name(&entry_path) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if entry_path.is_file() {
+                on_file(&entry_path);
+                let resource = Self::read_fluent_resource(&entry_path, self.preprocessor)?;
+                let name = name.to_string_lossy();
+                if name.starts_with('_') {
+                    self.add_unnamed_resource(relative_path, locale, resource);
+                } else {
+                    self.check_name_collision(&name, relative_path, locale, &entry_path)?;
+                    self.add_named_resource(&name, relative_path, locale, resource)?;
+                }
+                self.record_path(entry_path);
+            } else if entry_path.is_dir() {
+                self.parse_locale_directory(
+                    locale,
+                    locale_path,
+                    &relative_path.join(&name),
+                    on_file,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn set_transform(mut self, transform: Option<fn(&str) -> Cow<str>>) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Sets the transform of the `locale` bundle only, used instead of the
+    /// one of [`L10nBuilder::set_transform`].
+    pub fn set_transform_for(
+        mut self,
+        locale: LanguageIdentifier,
+        transform: fn(&str) -> Cow<str>,
+    ) -> Self {
+        self.locale_transforms.insert(locale, transform);
+        self
+    }
+
+    pub fn set_formatter(
+        mut self,
+        formatter: Option<fn(&FluentValue, &IntlLangMemoizer) -> Option<String>>,
+    ) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    pub fn set_use_isolating(mut self, use_isolating: bool) -> Self {
+        self.use_isolating = use_isolating;
+        self
+    }
+
+    pub fn set_primary_locale(mut self, primary_locale: Option<LanguageIdentifier>) -> Self {
+        self.primary_locale = primary_locale;
+        self
+    }
+
+    /// Locale used by [`L10n::translate_negotiated`] when the requested locale
+    /// is not supported, it must be a main locale.
+    pub fn set_default_locale(mut self, default_locale: LanguageIdentifier) -> Self {
+        self.default_locale = Some(default_locale);
+        self
+    }
+
+    /// Lowercases the resource names and replaces their `\\` separators with
+    /// `/`, both for the `Resources` keys built from
+    /// [`L10nBuilder::add_named_resource`] and for the lookups of
+    /// [`L10n::try_translate_with_args`] and the other methods taking a
+    /// resource name, so `Settings/Account` and `settings\\account` are the
+    /// same resource. [`L10n::resource_names`] returns the lowercased names.
+    pub fn set_case_insensitive_lookup(mut self, case_insensitive_lookup: bool) -> Self {
+        self.case_insensitive_lookup = case_insensitive_lookup;
+        self
+    }
+
+    /// Records the locale and file providing each message once the resources
+    /// are merged, see [`L10n::message_provenance`].
+    pub fn record_provenance(mut self, record_provenance: bool) -> Self {
+        self.record_provenance = record_provenance;
+        self
+    }
+
+    /// Resolves when building the translations of the messages made only of
+    /// text, for [`L10n::translate_raw`].
+    pub fn resolve_literals(mut self, resolve_literals: bool) -> Self {
+        self.resolve_literals = resolve_literals;
+        self
+    }
+
+    /// Checks when building that the terms referenced by the messages and
+    /// terms of each bundle are defined, see [`BuildError::MissingTerm`].
+    pub fn check_terms(mut self, check_terms: bool) -> Self {
+        self.check_terms = check_terms;
+        self
+    }
+
+    /// Pseudo-localizes every translation with the accents, padding and
+    /// brackets set by `options`, the placeables are never accented and the
+    /// isolating marks are kept as set by [`L10nBuilder::set_use_isolating`].
+    /// The accents are a transform, building fails with
+    /// [`BuildError::PseudoLocalizeTransform`] if another one is set.
+    pub fn set_pseudo_localize(mut self, options: Option<PseudoOptions>) -> Self {
+        self.pseudo_options = options;
+        self
+    }
+
+    /// Applies `postprocess(locale, translation)` to every translation, after
+    /// formatting, with the requested locale.
+    pub fn set_postprocess(
+        mut self,
+        postprocess: Option<fn(&LanguageIdentifier, String) -> String>,
+    ) -> Self {
+        self.postprocess = postprocess;
+        self
+    }
+
+    /// Translates when building the messages without arguments for which
+    /// `predicate(resource, key)` returns `true`, they are then returned
+    /// borrowed. Messages using volatile functions (e.g. the current date)
+    /// must be excluded.
+    pub fn cache_if(mut self, predicate: fn(&str, &str) -> bool) -> Self {
+        self.cache_if = Some(predicate);
+        self
+    }
+
+    /// Transforms the source of each fluent file before it is parsed, e.g. to
+    /// substitute build-time constants. It is called once per file with its
+    /// path and must be set before parsing, see [`L10nBuilder::parse_path`].
+    pub fn set_preprocessor(mut self, preprocessor: fn(&Path, String) -> String) -> Self {
+        self.preprocessor = Some(preprocessor);
+        self
+    }
+
+    /// Skips the files and directories whose name matches one of the
+    /// `patterns` (`*` and `?` wildcards), e.g. `*.wip.ftl`. It must be set
+    /// before parsing, see [`L10nBuilder::parse_path`].
+    pub fn set_ignore(mut self, patterns: Vec<String>) -> Self {
+        self.ignore = patterns;
+        self
+    }
+
+    /// Adds the messages of the named resources `names` to the bundles of
+    /// every other resource so they can be referenced across resources. They
+    /// override the messages of the unnamed resources (`_` prefixed) and are
+    /// overridden by the resource's own messages.
+    pub fn set_shared_resources(mut self, names: Vec<String>) -> Self {
+        self.shared_resources = names;
+        self
+    }
+
+    /// Marks the named resources `names` as complete, the consistency checks
+    /// run at build time skip them, e.g. for the known and intentional gaps
+    /// of mature resources. Unlike an optional resource it is still required,
+    /// only not checked.
+    pub fn set_complete_resources(mut self, names: Vec<String>) -> Self {
+        self.complete_resources = names;
+        self
+    }
+
+    /// Sets the default value of the argument `name`, used when not given.
+    pub fn with_args_default(mut self, name: &str, value: &str) -> Self {
+        self.args_default.insert(name.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Same as [`L10nBuilder::with_args_default`] but only for `locale`, e.g.
+    /// when the default grammatical gender differs between languages. It
+    /// takes precedence over the default set for all the locales.
+    pub fn with_locale_args_default(
+        mut self,
+        locale: LanguageIdentifier,
+        name: &str,
+        value: &str,
+    ) -> Self {
+        self.locale_args_default
+            .entry(locale)
+            .or_default()
+            .insert(name.to_owned(), value.to_owned());
+        self
+    }
+
+    pub fn add_function(
+        mut self,
+        name: &str,
+        function: for<'a> fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a>,
+    ) -> Self {
+        self.remove_function(name);
+        self.functions.insert(name.to_owned(), function);
+        self
+    }
+
+    /// Same as [`L10nBuilder::add_function`] but the function also receives
+    /// the main locale of the bundle it is called from, e.g. to format a date.
+    pub fn add_function_with_locale(
+        mut self,
+        name: &str,
+        function: for<'a> fn(
+            &LanguageIdentifier,
+            &[FluentValue<'a>],
+            &FluentArgs,
+        ) -> FluentValue<'a>,
+    ) -> Self {
+        self.remove_function(name);
+        self.locale_functions.insert(name.to_owned(), function);
+        self
+    }
+
+    /// Same as [`L10nBuilder::add_function`] but the function also receives a
+    /// [`BundleResolver`] to format the terms and messages of the bundle it is
+    /// called from, bundle functions included.
+    pub fn add_bundle_function(
+        mut self,
+        name: &str,
+        function: for<'a> fn(&BundleResolver, &[FluentValue<'a>], &FluentArgs) -> FluentValue<'a>,
+    ) -> Self {
+        self.remove_function(name);
+        self.bundle_functions.insert(name.to_owned(), function);
+        self
+    }
+
+    // The last function added with a name replaces the previous one.
+    fn remove_function(&mut self, name: &str) {
+        self.functions.remove(name);
+        self.locale_functions.remove(name);
+        self.bundle_functions.remove(name);
+    }
+
+    // Builds the parsed directory again with the same options, for the
+    // watcher to pick up the added and removed files.
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    pub(crate) fn rebuilder(&self) -> Option<(PathBuf, Rebuilder)> {
+        let root = self.root.clone()?;
+        let locales = self.locales.clone();
+        let options = self.clone_options();
+        let path = root.clone();
+        Some((
+            root,
+            Box::new(move || {
+                Ok(options
+                    .clone_options()
+                    .parse_path(&path, Some(locales.clone()))?
+                    .build()?)
+            }),
+        ))
+    }
+
+    // The builder without the parsed resources.
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    fn clone_options(&self) -> Self {
+        Self {
+            transform: self.transform,
+            locale_transforms: self.locale_transforms.clone(),
+            formatter: self.formatter,
+            use_isolating: self.use_isolating,
+            functions: self.functions.clone(),
+            locale_functions: self.locale_functions.clone(),
+            bundle_functions: self.bundle_functions.clone(),
+            primary_locale: self.primary_locale.clone(),
+            args_default: self.args_default.clone(),
+            locale_args_default: self.locale_args_default.clone(),
+            pseudo_options: self.pseudo_options,
+            preprocessor: self.preprocessor,
+            postprocess: self.postprocess,
+            record_provenance: self.record_provenance,
+            embedded_defaults: self
+                .embedded_defaults
+                .iter()
+                .map(|(locale, name, fluent_resource)| {
+                    let fluent_resource =
+                        FluentResource::try_new(fluent_resource.source().to_owned())
+                            .expect("Unexpected error, the resource was already parsed");
+                    (locale.clone(), name.clone(), fluent_resource)
+                })
+                .collect(),
+            cache_if: self.cache_if,
+            ignore: self.ignore.clone(),
+            case_insensitive_lookup: self.case_insensitive_lookup,
+            shared_resources: self.shared_resources.clone(),
+            default_locale: self.default_locale.clone(),
+            resolve_literals: self.resolve_literals,
+            complete_resources: self.complete_resources.clone(),
+            check_terms: self.check_terms,
+            ..Default::default()
+        }
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => self
+                .ignore
+                .iter()
+                .any(|pattern| matches_pattern(pattern, name)),
+            None => false,
+        }
+    }
+
+    // Named resources whose names only differ by case would silently override
+    // each other on a case insensitive file system. The name is recorded when
+    // there is no collision.
+    fn check_name_collision(
+        &mut self,
+        name: &str,
+        relative_path: &Path,
+        locale: &LanguageIdentifier,
+        path: &Path,
+    ) -> Result<(), ParserError> {
+        let resource_name = normalized_path(&relative_path.join(name));
+        match self
+            .lowercase_names
+            .entry((resource_name.to_lowercase(), locale.to_owned()))
+        {
+            hash_map::Entry::Occupied(entry) => {
+                let (name, existing) = entry.get();
+                Err(ParserError::ResourceNameCollision {
+                    name: name.to_owned(),
+                    locale: locale.to_owned(),
+                    existing: existing.to_owned(),
+                    path: path.to_path_buf(),
+                })
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert((resource_name, path.to_path_buf()));
+                Ok(())
+            }
+        }
+    }
+
+    // Associates `path` with the last added fluent resource.
+    fn record_path(&mut self, path: PathBuf) {
+        self.paths.insert(self.fluent_resources.len() - 1, path);
+    }
+
+    fn read_fluent_resource(
+        path: &Path,
+        preprocessor: Option<Preprocessor>,
+    ) -> Result<FluentResource, ParserError> {
+        Self::parse_fluent_source(path, fs::read_to_string(path)?, preprocessor)
+    }
+
+    fn parse_fluent_source(
+        path: &Path,
+        mut source: String,
+        preprocessor: Option<Preprocessor>,
+    ) -> Result<FluentResource, ParserError> {
+        if let Some(preprocessor) = preprocessor {
+            source = preprocessor(path, source);
+        }
+        FluentResource::try_new(source).map_err(|(_, errors)| ParserError::FluentParser { errors })
+    }
+}
+
+// The locale of the directory `dir_name`, `None` if it is not one of the
+// locales to visit.
+fn locale_to_visit(
+    dir_name: &str,
+    locales_to_visit: &Option<HashSet<LanguageIdentifier>>,
+) -> Result<Option<LanguageIdentifier>, ParserError> {
+    let parsed_locale = dir_name.parse::<LanguageIdentifier>();
+    match locales_to_visit {
+        Some(locales_to_visit) => Ok(match parsed_locale {
+            Ok(locale) if locales_to_visit.contains(&locale) => Some(locale),
+            _ => None,
+        }),
+        None => parsed_locale
+            .map(Some)
+            .map_err(|err| ParserError::ParseLangDir {
+                dir_name: dir_name.to_string(),
+                err,
+            }),
+    }
+}
+
+fn check_mandatory_locales(
+    locales_option: &Option<Locales>,
+    locales_visited: &HashSet<LanguageIdentifier>,
+) -> Result<(), ParserError> {
+    if let Some(mandatory_locales) = locales_option
+        .as_ref()
+        .map(|locales| locales.mandatory_locales())
+    {
+        let differences: Vec<_> = mandatory_locales
+            .difference(locales_visited)
+            .cloned()
+            .collect();
+        if !differences.is_empty() {
+            return Err(ParserError::MissingLocales(differences));
+        }
+    }
+    Ok(())
+}
+
+struct BytesWriter<'a>(&'a mut Vec<u8>);
+
+impl fmt::Write for BytesWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+// Resource name used with `L10nBuilder::set_case_insensitive_lookup`.
+// Backslashes are separators too, so the same resource name is computed
+// whatever the separators used.
+fn case_insensitive_name(name: &str) -> String {
+    name.replace('\\', "/").to_lowercase()
+}
+
+fn normalized_path(path: &Path) -> String {
+    path.iter()
+        .flat_map(|c| {
+            c.to_string_lossy()
+                .split('\\')
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Path of the file containing the named `resource` for `locale`.
+fn resource_path(root: &Option<PathBuf>, resource: &str, locale: &LanguageIdentifier) -> PathBuf {
+    let path = PathBuf::from(locale.to_string()).join(format!("{}.ftl", resource));
+    match root {
+        Some(root) => root.join(path),
+        None => path,
+    }
+}
+
+// The name of a fluent file path that is not on the file system.
+fn get_file_name(entry_path: &Path) -> Option<&OsStr> {
+    match entry_path.extension() {
+        Some(extension) if extension == "ftl" => entry_path.file_stem(),
+        _ => None,
+    }
+}
+
+fn get_entry_name(entry_path: &Path) -> Option<&OsStr> {
+    if entry_path.is_dir() {
+        entry_path.file_name()
+    } else {
+        match entry_path.extension() {
+            Some(extension) if extension == "ftl" => entry_path.file_stem(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
     use indoc::indoc;
     use unic_langid::langid;
 
     #[test]
-    fn parse_ok() {
-        let global_term = indoc! {r#"
-            -global-brand = Global Brand
-        "#};
-        let lang_term = indoc! {r#"
-            -brand = Root Brand
-            -lang-term = Lang term
-        "#};
-        let nested_term = indoc! {r#"
-            -brand = Nested Brand
-        "#};
+    fn parse_ok() {
+        let global_term = indoc! {r#"
+            -global-brand = Global Brand
+        "#};
+        let lang_term = indoc! {r#"
+            -brand = Root Brand
+            -lang-term = Lang term
+        "#};
+        let nested_term = indoc! {r#"
+            -brand = Nested Brand
+        "#};
+        let temp_dir = macro_files::create_temp!({
+            "_term.ftl": &global_term,
+            "en": {
+                "nested": {
+                    "_term.ftl": &nested_term,
+                    "about.ftl": indoc! {r#"
+                        about-us = (Nested) About { -brand } a { -global-brand } subdivision [{ -lang-term }].
+                    "#}
+                },
+                "_term.ftl": &lang_term,
+                "about.ftl": indoc! {r#"
+                    about-us = About { -brand } [{ -lang-term }].
+                "#}
+            },
+            "fr": {
+                "nested": {
+                    "_term.ftl": &nested_term,
+                    "about.ftl": indoc! {r#"
+                        about-us = (Nested) À propos de { -brand } une sous division de { -global-brand } [{ -lang-term }].
+                    "#}
+                },
+                "_term.ftl": &lang_term,
+                "about.ftl": indoc! {r#"
+                    about-us = À propos de { -brand } [{ -lang-term }].
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([
+            ("en", None),
+            ("en-GB", Some("en")),
+            ("en-CA", Some("en-GB")),
+            ("fr", None),
+            ("fr-CA", Some("fr")),
+        ])
+        .unwrap();
+
+        let translator_builder = L10nBuilder::parse(temp_dir.path(), Some(locales)).unwrap();
+        let translator = translator_builder.build().unwrap();
+
+        assert_eq!(
+            translator
+                .try_translate_with_args(&langid!("en-CA"), "about", "about-us", None)
+                .unwrap(),
+            "About Root Brand [Lang term]."
+        );
+        assert_eq!(
+            translator
+                .try_translate_with_args(&langid!("en-CA"), "nested/about", "about-us", None)
+                .unwrap(),
+            "(Nested) About Nested Brand a Global Brand subdivision [Lang term]."
+        );
+        assert_eq!(
+            translator
+                .try_translate_with_args(&langid!("fr"), "about", "about-us", None)
+                .unwrap(),
+            "À propos de Root Brand [Lang term]."
+        );
+        assert_eq!(
+            translator
+                .try_translate_with_args(&langid!("fr"), "nested/about", "about-us", None)
+                .unwrap(),
+            "(Nested) À propos de Nested Brand une sous division de Global Brand [Lang term]."
+        );
+    }
+
+    #[test]
+    fn parse_missing_resource() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "resource-1.ftl": indoc! {r#"
+                    first-key = First key [en]
+                "#},
+                "resource-2.ftl": indoc! {r#"
+                    first-key = First key [en]
+                "#}
+            },
+            "fr": {
+                "resource-1.ftl": indoc! {r#"
+                    first-key = First key [fr]
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales =
+            Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap();
+
+        let translator_builder = L10nBuilder::parse(temp_dir.path(), Some(locales)).unwrap();
+        let actual_err = translator_builder.build().unwrap_err();
+        let expected_err = BuildErrors(vec![BuildError::MissingResource {
+            resource: "resource-2".to_string(),
+            locales: vec![langid!("fr")],
+            paths: vec![temp_dir.path().join("fr").join("resource-2.ftl")],
+        }]);
+        assert_eq!(actual_err, expected_err);
+        assert_eq!(
+            actual_err.errors()[0].paths(),
+            [temp_dir.path().join("fr").join("resource-2.ftl")]
+        );
+    }
+
+    #[test]
+    fn missing_term() {
+        let temp_dir = macro_files::create_temp!({
+            "_brand.ftl": "-company = Company",
+            "en": {
+                "_terms.ftl": "-brand = Chat App\n-slogan = { -brand } by { -maker }",
+                "home.ftl": "welcome = Welcome on { -brand } by { -company }\nlegal =\n    .notice = { -brand }",
+                "settings.ftl": "title = { -settings }",
+            },
+            "fr": {
+                "home.ftl": "welcome = Bienvenue sur { -brand }\nlegal =\n    .notice = { -legal-brand }",
+                "settings.ftl": "title = { -settings }",
+            },
+            "fr-CA": {
+                "_terms.ftl": "-brand = Clavardage",
+            },
+        })
+        .unwrap();
+
+        let locales =
+            Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap();
+        assert!(L10nBuilder::parse(temp_dir.path(), Some(locales.clone()))
+            .unwrap()
+            .build()
+            .is_ok());
+
+        let actual_err = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_complete_resources(vec!["settings".to_string()])
+            .check_terms(true)
+            .build()
+            .unwrap_err();
+        let expected_err = BuildErrors(vec![
+            BuildError::MissingTerm {
+                resource: "home".to_string(),
+                message: "-slogan".to_string(),
+                term: "maker".to_string(),
+                locales: vec![langid!("en")],
+            },
+            BuildError::MissingTerm {
+                resource: "home".to_string(),
+                message: "legal.notice".to_string(),
+                term: "legal-brand".to_string(),
+                locales: vec![langid!("fr"), langid!("fr-CA")],
+            },
+            BuildError::MissingTerm {
+                resource: "home".to_string(),
+                message: "welcome".to_string(),
+                term: "brand".to_string(),
+                locales: vec![langid!("fr")],
+            },
+        ]);
+        assert_eq!(actual_err, expected_err);
+        assert_eq!(
+            actual_err.errors()[2].to_string(),
+            r#"missing term "-brand" referenced by message "welcome" in resource "home" for locales: fr"#
+        );
+    }
+
+    #[test]
+    fn complete_resources() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "resource-1.ftl": "first-key = First key [en]",
+                "resource-2.ftl": "first-key = First key [en]",
+            },
+            "fr": {
+                "resource-1.ftl": "first-key = First key [fr]",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_complete_resources(vec!["resource-2".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "resource-2", "first-key", None)
+                .unwrap(),
+            "First key [en]"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("fr"), "resource-2", "first-key", None),
+            Err(TranslateError::MessageIdNotExists {
+                id: "first-key".to_string(),
+                locale: langid!("fr"),
+            })
+        );
+    }
+
+    #[test]
+    fn primary_locale() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "resource.ftl": "key = Key [en]"
+            },
+            "fr": {
+                "resource.ftl": "key = Clé [fr]"
+            },
+        })
+        .unwrap();
+
+        let locales =
+            || Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap();
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(l10n.locales.primary(), &langid!("en"));
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_primary_locale(Some(langid!("fr-CA")))
+            .build()
+            .unwrap();
+        assert_eq!(l10n.locales.primary(), &langid!("fr-CA"));
+
+        let actual_err = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_primary_locale(Some(langid!("de")))
+            .build()
+            .unwrap_err();
+        let expected_err = BuildErrors(vec![BuildError::PrimaryNotMainLocale {
+            locale: langid!("de"),
+        }]);
+        assert_eq!(actual_err, expected_err);
+
+        let actual_err = L10nBuilder::default().build().unwrap_err();
+        assert_eq!(actual_err, BuildErrors(vec![BuildError::NoLocales]));
+    }
+
+    #[test]
+    fn translate_negotiated() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "resource.ftl": "key = Key [en]"
+            },
+            "fr": {
+                "resource.ftl": "key = Clé [fr]"
+            },
+        })
+        .unwrap();
+
+        let locales = || Locales::try_from([("en", None), ("fr", None)]).unwrap();
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_default_locale(langid!("fr"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            l10n.translate_negotiated(&langid!("en"), "resource", "key", None)
+                .unwrap(),
+            "Key [en]"
+        );
+        assert_eq!(
+            l10n.translate_negotiated(&langid!("de"), "resource", "key", None)
+                .unwrap(),
+            "Clé [fr]"
+        );
+        assert_eq!(
+            l10n.translate_negotiated(&langid!("de"), "resource", "unknown", None),
+            Err(TranslateError::MessageIdNotExists {
+                id: "unknown".to_string(),
+                locale: langid!("fr"),
+            })
+        );
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            l10n.translate_negotiated(&langid!("de"), "resource", "key", None),
+            Err(TranslateError::LocaleNotSupported {
+                locale: langid!("de"),
+            })
+        );
+
+        let actual_err = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_default_locale(langid!("de"))
+            .build()
+            .unwrap_err();
+        let expected_err = BuildErrors(vec![BuildError::DefaultNotMainLocale {
+            locale: langid!("de"),
+        }]);
+        assert_eq!(actual_err, expected_err);
+    }
+
+    #[test]
+    fn args_default() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "settings.ftl": indoc! {r#"
+                    busy = { $gender ->
+                        [male] Busy (he)
+                        [female] Busy (she)
+                       *[other] Busy
+                    } ({ $reason })
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .with_args_default("gender", "female")
+            .with_args_default("unused", "value")
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("reason", "Meeting");
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "settings", "busy", Some(&args))
+                .unwrap(),
+            "Busy (she) (Meeting)"
+        );
+
+        args.set("gender", "male");
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "settings", "busy", Some(&args))
+                .unwrap(),
+            "Busy (he) (Meeting)"
+        );
+    }
+
+    #[test]
+    fn locale_args_default() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "settings.ftl": indoc! {r#"
+                    busy = { $gender ->
+                        [male] Busy (he)
+                        [female] Busy (she)
+                       *[other] Busy
+                    }
+                "#}
+            },
+            "fr": {
+                "settings.ftl": indoc! {r#"
+                    busy = { $gender ->
+                        [male] Occupé
+                       *[female] Occupée
+                    }
+                "#}
+            },
+            "de": {
+                "settings.ftl": indoc! {r#"
+                    busy = { $gender ->
+                        [male] Beschäftigt (er)
+                        [female] Beschäftigt (sie)
+                       *[other] Beschäftigt
+                    }
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales =
+            Locales::try_from([("en", None), ("fr", Some("en")), ("de", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .with_args_default("gender", "other")
+            .with_locale_args_default(langid!("fr"), "gender", "male")
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let translate = |locale: &LanguageIdentifier, args: Option<&FluentArgs>| {
+            l10n.try_translate_with_args(locale, "settings", "busy", args)
+                .unwrap()
+                .into_owned()
+        };
+        assert_eq!(translate(&langid!("en"), None), "Busy");
+        assert_eq!(translate(&langid!("fr"), None), "Occupé");
+        assert_eq!(translate(&langid!("de"), None), "Beschäftigt");
+
+        let mut args = FluentArgs::new();
+        args.set("gender", "female");
+        assert_eq!(translate(&langid!("fr"), Some(&args)), "Occupée");
+    }
+
+    #[test]
+    fn try_translate_bytes() {
+        let temp_dir = macro_files::create_temp!({
+            "fr": {
+                "app.ftl": "greeting = Déjà vu, { $name } ✓"
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Zoë");
+
+        let bytes = l10n
+            .try_translate_bytes(&langid!("fr"), "app", "greeting", Some(&args))
+            .unwrap();
+        assert_eq!(bytes, "Déjà vu, Zoë ✓".as_bytes());
+        assert_eq!(
+            bytes,
+            b"D\xc3\xa9j\xc3\xa0 vu, Zo\xc3\xab \xe2\x9c\x93".to_vec()
+        );
+
+        let mut out = b"> ".to_vec();
+        l10n.write_translation_bytes(&mut out, &langid!("fr"), "app", "greeting", Some(&args))
+            .unwrap();
+        assert_eq!(out, "> Déjà vu, Zoë ✓".as_bytes());
+
+        assert_eq!(
+            l10n.try_translate_bytes(&langid!("fr"), "app", "missing", None),
+            Err(TranslateError::MessageIdNotExists {
+                id: "missing".to_string(),
+                locale: langid!("fr"),
+            })
+        );
+    }
+
+    #[test]
+    fn write_translation() {
+        struct CountingWriter {
+            chunks: usize,
+            output: String,
+        }
+
+        impl fmt::Write for CountingWriter {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.chunks += 1;
+                self.output.push_str(s);
+                Ok(())
+            }
+        }
+
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "greeting = Hello { $name }!"
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice");
+
+        let mut out = String::from("> ");
+        l10n.write_translation(&mut out, &langid!("en"), "app", "greeting", Some(&args))
+            .unwrap();
+        assert_eq!(out, "> Hello \u{2068}Alice\u{2069}!");
+
+        let mut writer = CountingWriter {
+            chunks: 0,
+            output: String::new(),
+        };
+        l10n.write_translation(&mut writer, &langid!("en"), "app", "greeting", Some(&args))
+            .unwrap();
+        assert_eq!(writer.output, "Hello \u{2068}Alice\u{2069}!");
+        assert!(writer.chunks > 1);
+
+        let actual_err = l10n
+            .write_translation(&mut out, &langid!("en"), "app", "missing", None)
+            .unwrap_err();
+        assert_eq!(
+            actual_err,
+            TranslateError::MessageIdNotExists {
+                id: "missing".to_string(),
+                locale: langid!("en"),
+            }
+        );
+    }
+
+    #[test]
+    fn resource_locales() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "key = Key [en]"
+            },
+            "fr": {
+                "app.ftl": "key = Clé [fr]"
+            },
+        })
+        .unwrap();
+
+        let locales =
+            Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.resource_locales("app"),
+            vec![&langid!("en"), &langid!("fr"), &langid!("fr-CA")]
+        );
+        assert!(l10n.resource_locales("unknown").is_empty());
+    }
+
+    #[test]
+    fn try_translate_with_args_iter() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    title = Welcome
+                    greeting = Hello { $name }, you have { $count } messages
+                "#},
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let args = vec![
+            ("name", FluentValue::from("Alice")),
+            ("count", FluentValue::from(3)),
+        ];
+        assert_eq!(
+            l10n.try_translate_with_args_iter(&langid!("en"), "app", "greeting", args)
+                .unwrap(),
+            "Hello Alice, you have 3 messages"
+        );
+
+        let name = String::from("name");
+        assert_eq!(
+            l10n.try_translate_with_args_iter(
+                &langid!("en"),
+                "app",
+                "greeting",
+                [(name, "Bob"), ("count".to_string(), "no")]
+            )
+            .unwrap(),
+            "Hello Bob, you have no messages"
+        );
+
+        assert_eq!(
+            l10n.try_translate_with_args_iter(
+                &langid!("en"),
+                "app",
+                "title",
+                Vec::<(&str, &str)>::new()
+            )
+            .unwrap(),
+            "Welcome"
+        );
+    }
+
+    #[test]
+    fn try_translate_owned_args() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "greeting = Hello { $name }!"
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = std::sync::Arc::new(
+            L10nBuilder::parse(temp_dir.path(), Some(locales))
+                .unwrap()
+                .set_use_isolating(false)
+                .build()
+                .unwrap(),
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice".to_string());
+        let handle = std::thread::spawn({
+            let l10n = l10n.clone();
+            move || l10n.try_translate_owned_args(&langid!("en"), "app", "greeting", Some(args))
+        });
+        assert_eq!(handle.join().unwrap().unwrap(), "Hello Alice!");
+    }
+
+    #[test]
+    fn translate() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    tagline = Have fun
+                    greeting = Hello { $name }!
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let en = langid!("en");
+        assert_eq!(l10n.translate(&en, "app", "tagline"), "Have fun");
+        assert_eq!(
+            l10n.translate(&en, "app", "subtitle"),
+            crate::UNEXPECTED_MESSAGE
+        );
+        assert_eq!(
+            l10n.translate(&en, "settings", "tagline"),
+            crate::UNEXPECTED_MESSAGE
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice");
+        assert_eq!(
+            l10n.translate_with_args(&en, "app", "greeting", Some(&args)),
+            "Hello Alice!"
+        );
+        assert_eq!(
+            l10n.translate_with_args(&en, "app", "greeting", None),
+            crate::UNEXPECTED_MESSAGE
+        );
+    }
+
+    #[test]
+    fn check_message_keys() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    tagline = Have fun
+                    status = Status
+                        .online = Online
+                "#}
+            },
+            "fr": {
+                "app.ftl": indoc! {r#"
+                    tagline = Amusez-vous
+                    status = Statut
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(l10n
+            .check_message_keys([("app", "tagline"), ("app", "status")])
+            .is_empty());
+        let errors = l10n.check_message_keys([
+            ("app", "tagline"),
+            ("app", "status.online"),
+            ("app", "title"),
+            ("settings", "title"),
+        ]);
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            &errors[0],
+            TranslateError::MessageAttributeNotExists { attribute, .. } if attribute == "online"
+        ));
+        assert!(matches!(
+            &errors[1],
+            TranslateError::MessageIdNotExists { id, .. } if id == "title"
+        ));
+        assert!(matches!(
+            &errors[2],
+            TranslateError::ResourceNotExists(resource) if resource == "settings"
+        ));
+    }
+
+    #[test]
+    fn source_text() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    tagline = Have fun
+                "#}
+            },
+            "fr": {
+                "app.ftl": indoc! {r#"
+                    tagline = Amusez-vous
+                    title = Accueil
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(l10n.source_text("app", "tagline").unwrap(), "Have fun");
+        assert_eq!(
+            l10n.source_text("app", "title"),
+            Err(TranslateError::MessageIdNotExists {
+                id: "title".to_string(),
+                locale: langid!("en"),
+            })
+        );
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_primary_locale(Some(langid!("fr")))
+            .build()
+            .unwrap();
+        assert_eq!(l10n.source_text("app", "tagline").unwrap(), "Amusez-vous");
+    }
+
+    #[test]
+    fn translate_error_classification() {
+        let locale = langid!("en");
+        let missing = [
+            TranslateError::ResourceNotExists("app".to_string()),
+            TranslateError::LocaleNotSupported {
+                locale: locale.clone(),
+            },
+            TranslateError::MessageIdNotExists {
+                id: "title".to_string(),
+                locale: locale.clone(),
+            },
+            TranslateError::MessageAttributeNotExists {
+                attribute: "online".to_string(),
+                id: "status".to_string(),
+                locale: locale.clone(),
+            },
+            TranslateError::MessageIdValueNotExists {
+                id: "status".to_string(),
+                locale: locale.clone(),
+            },
+            TranslateError::VariantNotExists {
+                variant: "none".to_string(),
+                variable: "gender".to_string(),
+                key: "status".to_string(),
+                locale,
+            },
+            TranslateError::Transparent {
+                context: "Status",
+                source: Box::new(TranslateError::ResourceNotExists("app".to_string())),
+            },
+        ];
+        for err in missing {
+            assert!(err.is_missing(), "{:?}", err);
+            assert_eq!(err.status_code(), 404);
+        }
+
+        let others = [
+            TranslateError::LocaleNotPinned,
+            TranslateError::FormatErrors(vec![]),
+            TranslateError::Write(fmt::Error),
+            TranslateError::Transparent {
+                context: "Status",
+                source: Box::new(TranslateError::FormatErrors(vec![])),
+            },
+        ];
+        for err in others {
+            assert!(!err.is_missing(), "{:?}", err);
+            assert_eq!(err.status_code(), 500);
+        }
+    }
+
+    #[test]
+    fn format_errors() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "settings.ftl": "greeting = Hello { $first-name } { $last-name }"
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let errors =
+            match l10n.try_translate_with_args(&langid!("en"), "settings", "greeting", None) {
+                Err(TranslateError::FormatErrors(errors)) => errors,
+                result => panic!("unexpected result: {:?}", result),
+            };
+        assert_eq!(errors.len(), 2);
+        for error in &errors {
+            assert_eq!(error.resource, "settings");
+            assert_eq!(error.key, "greeting");
+            assert_eq!(error.locale, langid!("en"));
+        }
+        assert!(errors[1]
+            .to_string()
+            .ends_with(r#"(resource: "settings", key: "greeting", locale "en")"#));
+    }
+
+    #[test]
+    fn translate_or_default() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    tagline = Have fun
+                    status = Status
+                        .online = Online
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let en = langid!("en");
+        assert_eq!(
+            l10n.translate_or_default(&en, "app", "tagline", None, "")
+                .unwrap(),
+            "Have fun"
+        );
+        assert_eq!(
+            l10n.translate_or_default(&en, "app", "subtitle", None, "")
+                .unwrap(),
+            ""
+        );
+        assert_eq!(
+            l10n.translate_or_default(&en, "app", "status.offline", None, "Offline")
+                .unwrap(),
+            "Offline"
+        );
+        assert_eq!(
+            l10n.translate_or_default(&en, "unknown", "tagline", None, ""),
+            Err(TranslateError::ResourceNotExists("unknown".to_string()))
+        );
+    }
+
+    #[test]
+    fn cache_if() {
+        use std::sync::atomic::{AtomicI64, Ordering};
+        static NOW: AtomicI64 = AtomicI64::new(0);
+
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    tagline = Have fun at { NOW() }
+                    clock = It is { NOW() }
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .add_function("NOW", |_, _| {
+                FluentValue::from(NOW.fetch_add(1, Ordering::SeqCst))
+            })
+            .cache_if(|_, key| key != "clock")
+            .build()
+            .unwrap();
+
+        let en = langid!("en");
+        let translate = |key| {
+            l10n.try_translate_with_args(&en, "app", key, None)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(translate("tagline"), "Have fun at 0");
+        assert_eq!(translate("tagline"), "Have fun at 0");
+        assert_eq!(translate("clock"), "It is 1");
+        assert_eq!(translate("clock"), "It is 2");
+        assert!(matches!(
+            l10n.try_translate_with_args(&en, "app", "tagline", None),
+            Ok(Cow::Borrowed("Have fun at 0"))
+        ));
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .add_function("NOW", |_, _| {
+                FluentValue::from(NOW.fetch_add(1, Ordering::SeqCst))
+            })
+            .set_case_insensitive_lookup(true)
+            .cache_if(|resource, key| resource == "app" && key == "tagline")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            l10n.try_translate_with_args(&en, "App", "tagline", None),
+            Ok(Cow::Borrowed("Have fun at 3"))
+        ));
+        assert!(matches!(
+            l10n.try_translate_with_args(&en, "app", "tagline", None),
+            Ok(Cow::Borrowed("Have fun at 3"))
+        ));
+    }
+
+    #[test]
+    fn reload_file() {
+        let temp_dir = macro_files::create_temp!({
+            "_common.ftl": "brand = Acme",
+            "en": {
+                "app.ftl": "tagline = Welcome to { brand }",
+                "settings.ftl": "title = Settings",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let mut l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .cache_if(|_, _| true)
+            .check_terms(true)
+            .build()
+            .unwrap();
+
+        let en = langid!("en");
+        assert_eq!(
+            l10n.try_translate_with_args(&en, "app", "tagline", None)
+                .unwrap(),
+            "Welcome to Acme"
+        );
+
+        let path = temp_dir.path().join("en/app.ftl");
+        fs::write(&path, "tagline = Hello from { brand }").unwrap();
+        l10n.reload_file(&path).unwrap();
+        assert_eq!(
+            l10n.try_translate_with_args(&en, "app", "tagline", None)
+                .unwrap(),
+            "Hello from Acme"
+        );
+
+        let path = temp_dir.path().join("_common.ftl");
+        fs::write(&path, "brand = Globex").unwrap();
+        l10n.reload_file(&path).unwrap();
+        assert_eq!(
+            l10n.try_translate_with_args(&en, "app", "tagline", None)
+                .unwrap(),
+            "Hello from Globex"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&en, "settings", "title", None)
+                .unwrap(),
+            "Settings"
+        );
+
+        let path = temp_dir.path().join("en/unknown.ftl");
+        fs::write(&path, "title = Unknown").unwrap();
+        assert!(matches!(
+            l10n.reload_file(&path),
+            Err(ParserError::UnknownFile { .. })
+        ));
+
+        let path = temp_dir.path().join("en/app.ftl");
+        fs::write(&path, "tagline = Hello from { -company }").unwrap();
+        assert!(matches!(
+            l10n.reload_file(&path),
+            Err(ParserError::Build(BuildErrors(errors)))
+                if matches!(errors.as_slice(), [BuildError::MissingTerm { .. }])
+        ));
+        fs::write(&path, "# @extends missing\ntagline = Hello").unwrap();
+        assert!(matches!(
+            l10n.reload_file(&path),
+            Err(ParserError::Build(BuildErrors(errors)))
+                if matches!(errors.as_slice(), [BuildError::UnknownBaseMessage { .. }])
+        ));
+        assert_eq!(
+            l10n.try_translate_with_args(&en, "app", "tagline", None)
+                .unwrap(),
+            "Hello from Globex"
+        );
+    }
+
+    #[test]
+    fn preprocessor() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "version = Version %VERSION%",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::default()
+            .set_preprocessor(|path, source| {
+                assert!(path.ends_with("en/app.ftl"));
+                source.replace("%VERSION%", "1.2.0")
+            })
+            .parse_path(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "version", None)
+                .unwrap(),
+            "Version 1.2.0"
+        );
+    }
+
+    #[test]
+    fn resource_name_collision() {
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let sources = [
+            ("en/settings/account.ftl", "title = Account".to_string()),
+            ("en/Settings/account.ftl", "title = Account".to_string()),
+        ];
+        let err = L10nBuilder::from_sources(sources, Some(locales.clone())).unwrap_err();
+        match err {
+            ParserError::ResourceNameCollision {
+                name,
+                locale,
+                existing,
+                path,
+            } => {
+                assert_eq!(name, "settings/account");
+                assert_eq!(locale, langid!("en"));
+                assert_eq!(existing, Path::new("en/settings/account.ftl"));
+                assert_eq!(path, Path::new("en/Settings/account.ftl"));
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+
+        let sources = [
+            ("en/settings/account.ftl", "title = Account".to_string()),
+            ("en\\settings\\account.ftl", "title = Account".to_string()),
+        ];
+        let err = L10nBuilder::from_sources(sources, Some(locales)).unwrap_err();
+        assert!(matches!(
+            err,
+            ParserError::ResourceNameCollision { name, .. } if name == "settings/account"
+        ));
+
+        assert_eq!(
+            normalized_path(Path::new("settings\\account")),
+            normalized_path(Path::new("settings/account"))
+        );
+    }
+
+    #[test]
+    fn ignore() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "title = Title",
+                "app.wip.ftl": "title = Work in progress\ndraft = { $missing",
+                "drafts": {
+                    "page.ftl": "title = Draft",
+                },
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::default()
+            .set_ignore(vec!["*.wip.ftl".to_string(), "drafts".to_string()])
+            .parse_path(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "title", None)
+                .unwrap(),
+            "Title"
+        );
+        assert!(l10n
+            .try_translate_with_args(&langid!("en"), "drafts/page", "title", None)
+            .is_err());
+    }
+
+    #[test]
+    fn introspection() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "title = Welcome",
+                "settings": {
+                    "account.ftl": "title = Account",
+                },
+            },
+            "fr": {
+                "app.ftl": "title = Bienvenue",
+            },
+        })
+        .unwrap();
+
+        let locales =
+            Locales::try_from([("fr", Some("en")), ("en", None), ("en-GB", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.available_locales().collect::<Vec<_>>(),
+            [&langid!("fr"), &langid!("en"), &langid!("en-GB")]
+        );
+        let mut resource_names: Vec<_> = l10n.resource_names().collect();
+        resource_names.sort_unstable();
+        assert_eq!(resource_names, ["app", "settings/account"]);
+    }
+
+    #[test]
+    fn inherit_attributes() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "_fields.ftl": indoc! {r#"
+                    field =
+                        .required-error = { $field } is required
+                        .hint = Fill in { $field }
+                    # @extends field
+                    text-field =
+                        .too-long = { $field } is too long
+                "#},
+                "form.ftl": indoc! {r#"
+                    # @extends field
+                    email = Email
+                        .hint = Your email address
+
+                    # @extends text-field
+                    name = Name
+                "#},
+            },
+        })
+        .unwrap();
+
+        let locales = || Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("field", "Email");
+        let translate = |key| {
+            l10n.try_translate_with_args(&langid!("en"), "form", key, Some(&args))
+                .unwrap()
+                .replace(['\u{2068}', '\u{2069}'], "")
+        };
+        assert_eq!(translate("email.required-error"), "Email is required");
+        assert_eq!(translate("email.hint"), "Your email address");
+        assert_eq!(translate("name.too-long"), "Email is too long");
+        assert_eq!(translate("name.required-error"), "Email is required");
+        assert_eq!(
+            l10n.required_variables("form", "email.required-error"),
+            Ok(HashSet::from(["field"]))
+        );
+
+        fs::write(
+            temp_dir.path().join("en/form.ftl"),
+            "# @extends missing\nemail = Email\n\n# @extends name\nname = Name\n",
+        )
+        .unwrap();
+        let result = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            BuildErrors(vec![
+                BuildError::UnknownBaseMessage {
+                    message: "email".to_string(),
+                    base: "missing".to_string(),
+                },
+                BuildError::ExtendsCycle {
+                    message: "name".to_string(),
+                    cycle: vec!["name".to_string(), "name".to_string()],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn report_unused() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    brand = Acme
+                        .short = A
+                    title = Welcome to { brand }
+                    subtitle = { brand.short } app
+                    footer = Footer
+                "#},
+                "settings.ftl": "title = Settings",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let used = HashSet::from([("app".to_string(), "title".to_string())]);
+        assert_eq!(
+            l10n.report_unused(&used),
+            [
+                ("app".to_string(), "footer".to_string()),
+                ("app".to_string(), "subtitle".to_string()),
+                ("settings".to_string(), "title".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn messages() {
         let temp_dir = macro_files::create_temp!({
-            "_term.ftl": &global_term,
             "en": {
-                "nested": {
-                    "_term.ftl": &nested_term,
-                    "about.ftl": indoc! {r#"
-                        about-us = (Nested) About { -brand } a { -global-brand } subdivision [{ -lang-term }].
-                    "#}
-                },
-                "_term.ftl": &lang_term,
-                "about.ftl": indoc! {r#"
-                    about-us = About { -brand } [{ -lang-term }].
+                "app.ftl": indoc! {r#"
+                    title = Welcome
+                        .tooltip = Home
+                    menu =
+                        .label = Menu
+                    about = About
+                "#},
+            },
+            "fr": {
+                "app.ftl": indoc! {r#"
+                    title = Bienvenue
+                        .aria = Accueil
+                    menu =
+                        .label = Menu
+                "#},
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let message =
+            |id: &str, attributes: &[&str], locales: Vec<LanguageIdentifier>| MessageInfo {
+                id: id.to_string(),
+                attributes: attributes.iter().map(|name| name.to_string()).collect(),
+                locales,
+            };
+        assert_eq!(
+            l10n.messages("app").unwrap(),
+            vec![
+                message("about", &[], vec![langid!("en")]),
+                message("menu", &["label"], vec![]),
+                message(
+                    "title",
+                    &["aria", "tooltip"],
+                    vec![langid!("en"), langid!("fr")]
+                ),
+            ]
+        );
+        assert_eq!(
+            l10n.messages("unknown"),
+            Err(TranslateError::ResourceNotExists("unknown".to_string()))
+        );
+    }
+
+    #[test]
+    fn translate_variant() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    busy = Busy ({ $reason }) for { $hours ->
+                        [1] one hour
+                       *[other] { $hours } hours
+                    }
+                "#},
+            },
+            "fr": {
+                "app.ftl": indoc! {r#"
+                    busy = { $gender ->
+                        [male] Occupé
+                        [female] Occupée
+                       *[other] Non disponible
+                    } ({ $reason })
+                "#},
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let args = FluentArgs::from_iter([("reason", "Meeting"), ("gender", "male")]);
+        let translate = |locale, variable, variant| {
+            l10n.translate_variant(&locale, "app", "busy", variable, variant, Some(&args))
+        };
+        for (variant, expected) in [
+            ("male", "Occupé (Meeting)"),
+            ("female", "Occupée (Meeting)"),
+            ("other", "Non disponible (Meeting)"),
+        ] {
+            assert_eq!(
+                translate(langid!("fr"), "gender", variant).unwrap(),
+                expected
+            );
+        }
+        assert_eq!(
+            translate(langid!("en"), "hours", "1").unwrap(),
+            "Busy (Meeting) for one hour"
+        );
+        assert_eq!(
+            translate(langid!("fr"), "gender", "none"),
+            Err(TranslateError::VariantNotExists {
+                variant: "none".to_string(),
+                variable: "gender".to_string(),
+                key: "busy".to_string(),
+                locale: langid!("fr"),
+            })
+        );
+        assert!(matches!(
+            translate(langid!("en"), "gender", "male"),
+            Err(TranslateError::VariantNotExists { .. })
+        ));
+    }
+
+    #[test]
+    fn all_locales_translation() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "title = Welcome\ngreeting = Hello { $name }",
+            },
+            "fr": {
+                "app.ftl": "title = Bienvenue",
+            },
+            "fr-CA": {
+                "app.ftl": "greeting = Bonjour { $name }",
+            },
+        })
+        .unwrap();
+
+        // `fr` is only the fallback of `fr-CA`, it is not translated alone
+        let locales = Locales::try_from([("en", None), ("fr-CA", Some("fr"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let translations = l10n.all_locales_translation("app", "title");
+        assert_eq!(
+            translations.into_iter().collect::<Vec<_>>(),
+            vec![
+                (langid!("en"), Ok("Welcome".to_string())),
+                (langid!("fr-CA"), Ok("Bienvenue".to_string())),
+            ]
+        );
+
+        let translations = l10n.all_locales_translation("app", "greeting");
+        assert_eq!(translations.len(), 2);
+        assert!(translations.values().all(Result::is_err));
+    }
+
+    #[test]
+    fn message_provenance() {
+        let temp_dir = macro_files::create_temp!({
+            "_common.ftl": "brand = Acme",
+            "en": {
+                "_shared.ftl": "tagline = Have fun",
+                "app.ftl": "title = Title\nbrand = Acme Inc.",
+            },
+            "en-GB": {
+                "app.ftl": "title = Title (GB)",
+            },
+        })
+        .unwrap();
+
+        let locales = || Locales::try_from([("en", None), ("en-GB", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .record_provenance(true)
+            .build()
+            .unwrap();
+
+        let en = langid!("en");
+        let en_gb = langid!("en-GB");
+        let provenance = |key, locale| l10n.message_provenance("app", key, locale).cloned();
+        assert_eq!(
+            provenance("title", &en_gb),
+            Some(MessageProvenance {
+                locale: Some(en_gb.clone()),
+                path: Some(temp_dir.path().join("en-GB/app.ftl")),
+            })
+        );
+        assert_eq!(
+            provenance("title.attribute", &en),
+            Some(MessageProvenance {
+                locale: Some(en.clone()),
+                path: Some(temp_dir.path().join("en/app.ftl")),
+            })
+        );
+        assert_eq!(
+            provenance("brand", &en_gb),
+            Some(MessageProvenance {
+                locale: Some(en.clone()),
+                path: Some(temp_dir.path().join("en/app.ftl")),
+            })
+        );
+        assert_eq!(
+            provenance("tagline", &en_gb),
+            Some(MessageProvenance {
+                locale: Some(en.clone()),
+                path: Some(temp_dir.path().join("en/_shared.ftl")),
+            })
+        );
+        assert_eq!(provenance("unknown", &en), None);
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(l10n.message_provenance("app", "title", &en), None);
+    }
+
+    #[test]
+    fn embedded_defaults() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "title = Runtime title",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("en-GB", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .with_embedded_defaults([
+                (langid!("en"), "app", "title = Embedded title"),
+                (langid!("en"), "settings", "title = Settings"),
+                (langid!("en-GB"), "settings", "title = Settings (GB)"),
+                (langid!("fr"), "settings", "title = Paramètres"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let translate = |locale: &LanguageIdentifier, resource| {
+            l10n.try_translate_with_args(locale, resource, "title", None)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(translate(&langid!("en"), "app"), "Runtime title");
+        assert_eq!(translate(&langid!("en-GB"), "app"), "Runtime title");
+        assert_eq!(translate(&langid!("en"), "settings"), "Settings");
+        assert_eq!(translate(&langid!("en-GB"), "settings"), "Settings (GB)");
+        assert_eq!(
+            l10n.resource_locales("settings"),
+            [&langid!("en"), &langid!("en-GB")]
+        );
+
+        assert!(matches!(
+            L10nBuilder::default().with_embedded_defaults([(langid!("en"), "app", "title =")]),
+            Err(ParserError::FluentParser { .. })
+        ));
+    }
+
+    #[test]
+    fn add_named_resource_overriding() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    title = Title
+                    tagline = Have fun
+                "#},
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let mut builder = L10nBuilder::parse(temp_dir.path(), Some(locales)).unwrap();
+        let resource = || FluentResource::try_new("title = New title".to_string()).unwrap();
+
+        assert!(matches!(
+            builder.add_named_resource("app", Path::new(""), &langid!("en"), resource()),
+            Err(ParserError::NamedResourceExists { name, locale })
+                if name == "app" && locale == langid!("en")
+        ));
+
+        builder.add_named_resource_overriding("app", Path::new(""), &langid!("en"), resource());
+        builder.add_named_resource_overriding(
+            "settings",
+            Path::new(""),
+            &langid!("en"),
+            resource(),
+        );
+        let l10n = builder.build().unwrap();
+
+        let translate = |resource, key| {
+            l10n.try_translate_with_args(&langid!("en"), resource, key, None)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(translate("app", "title"), "New title");
+        assert_eq!(translate("app", "tagline"), "Have fun");
+        assert_eq!(translate("settings", "title"), "New title");
+    }
+
+    #[test]
+    fn translate_raw() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    -brand = Acme
+                    title = Welcome
+                    greeting = Hello { $name }
+                    tagline = Welcome to { -brand }
+                    status = Status
+                        .online = Online
+                        .busy = Busy ({ $reason })
+                "#}
+            },
+            "en-GB": {
+                "app.ftl": "title = Welcome (GB)",
+            },
+        })
+        .unwrap();
+
+        let locales = || Locales::try_from([("en", None), ("en-GB", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .resolve_literals(true)
+            .build()
+            .unwrap();
+
+        let en = langid!("en");
+        let en_gb = langid!("en-GB");
+        assert_eq!(l10n.translate_raw("app", "title", &en), Some("Welcome"));
+        assert_eq!(
+            l10n.translate_raw("app", "title", &en_gb),
+            Some("Welcome (GB)")
+        );
+        assert_eq!(l10n.translate_raw("app", "status", &en_gb), Some("Status"));
+        assert_eq!(
+            l10n.translate_raw("app", "status.online", &en),
+            Some("Online")
+        );
+        assert_eq!(l10n.translate_raw("app", "status.busy", &en), None);
+        assert_eq!(l10n.translate_raw("app", "greeting", &en), None);
+        assert_eq!(l10n.translate_raw("app", "tagline", &en), None);
+        assert_eq!(l10n.translate_raw("app", "unknown", &en), None);
+        assert_eq!(l10n.translate_raw("unknown", "title", &en), None);
+        assert_eq!(l10n.translate_raw("app", "title", &langid!("fr")), None);
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_postprocess(Some(|_, translation| translation.to_uppercase()))
+            .resolve_literals(true)
+            .build()
+            .unwrap();
+        assert_eq!(l10n.translate_raw("app", "title", &en), Some("WELCOME"));
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(l10n.translate_raw("app", "title", &en), None);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn hot_messages() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    title = Title
+                    status = Status
+                        .online = Online
                 "#}
             },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .cache_if(|_, key| key == "status")
+            .build()
+            .unwrap();
+        assert_eq!(l10n.hot_messages(), []);
+
+        let en = langid!("en");
+        for _ in 0..3 {
+            l10n.try_translate_with_args(&en, "app", "status.online", None)
+                .unwrap();
+            l10n.try_translate_with_args(&en, "app", "status", None)
+                .unwrap();
+        }
+        let mut out = String::new();
+        l10n.write_translation(&mut out, &en, "app", "title", None)
+            .unwrap();
+        assert_eq!(
+            l10n.hot_messages(),
+            [
+                (("app".to_string(), "status.online".to_string()), 3),
+                (("app".to_string(), "title".to_string()), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_for() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "title = Welcome",
+            },
             "fr": {
-                "nested": {
-                    "_term.ftl": &nested_term,
-                    "about.ftl": indoc! {r#"
-                        about-us = (Nested) À propos de { -brand } une sous division de { -global-brand } [{ -lang-term }].
-                    "#}
-                },
-                "_term.ftl": &lang_term,
-                "about.ftl": indoc! {r#"
-                    about-us = À propos de { -brand } [{ -lang-term }].
+                "app.ftl": "title = Bienvenue",
+            },
+            "sr": {
+                "app.ftl": "title = Dobrodošli",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None), ("sr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_transform(Some(|text| Cow::Owned(text.to_uppercase())))
+            .set_transform_for(langid!("sr"), |text| Cow::Owned(text.replace("š", "sh")))
+            .build()
+            .unwrap();
+
+        let translate = |locale| {
+            l10n.try_translate_with_args(&locale, "app", "title", None)
+                .unwrap()
+                .into_owned()
+        };
+        assert_eq!(translate(langid!("en")), "WELCOME");
+        assert_eq!(translate(langid!("fr")), "BIENVENUE");
+        assert_eq!(translate(langid!("sr")), "Dobrodoshli");
+    }
+
+    #[test]
+    fn pseudolocalize() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    account = Account
+                    greeting = Hello { $name }!
                 "#}
             },
         })
         .unwrap();
 
-        let locales = Locales::try_from([
-            ("en", None),
-            ("en-GB", Some("en")),
-            ("en-CA", Some("en-GB")),
-            ("fr", None),
-            ("fr-CA", Some("fr")),
-        ])
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_pseudo_localize(Some(PseudoOptions::default()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "account", None)
+                .unwrap(),
+            "⟦Ȧƈƈǿŭƞŧ !!!⟧"
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice");
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "greeting", Some(&args))
+                .unwrap(),
+            "⟦Ħḗŀŀǿ \u{2068}Alice\u{2069}! !!!!!⟧"
+        );
+
+        let mut out = String::new();
+        l10n.write_translation(&mut out, &langid!("en"), "app", "greeting", Some(&args))
+            .unwrap();
+        assert_eq!(out, "⟦Ħḗŀŀǿ \u{2068}Alice\u{2069}! !!!!!⟧");
+    }
+
+    #[test]
+    fn case_insensitive_lookup() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "Settings": {
+                    "Account.ftl": "title = Account",
+                },
+            },
+        })
         .unwrap();
 
-        let translator_builder = L10nBuilder::parse(temp_dir.path(), Some(locales)).unwrap();
-        let translator = translator_builder.build().unwrap();
+        let locales = || Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_case_insensitive_lookup(true)
+            .build()
+            .unwrap();
+        for resource in ["Settings/Account", "settings/account", "SETTINGS\\account"] {
+            assert_eq!(
+                l10n.try_translate_with_args(&langid!("en"), resource, "title", None)
+                    .unwrap(),
+                "Account"
+            );
+        }
+        assert_eq!(
+            l10n.resource_names().collect::<Vec<_>>(),
+            vec!["settings/account"]
+        );
 
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .build()
+            .unwrap();
         assert_eq!(
-            translator
-                .try_translate_with_args(&langid!("en-CA"), "about", "about-us", None)
+            l10n.try_translate_with_args(&langid!("en"), "settings/account", "title", None),
+            Err(TranslateError::ResourceNotExists(
+                "settings/account".to_string()
+            ))
+        );
+
+        let mut builder = L10nBuilder::parse(temp_dir.path(), Some(locales())).unwrap();
+        builder
+            .add_named_resource(
+                "settings/account",
+                Path::new(""),
+                &langid!("en"),
+                FluentResource::try_new("title = Other".to_string()).unwrap(),
+            )
+            .unwrap();
+        let result = builder.set_case_insensitive_lookup(true).build();
+        assert_eq!(
+            result.unwrap_err(),
+            BuildErrors(vec![BuildError::CaseInsensitiveConflict {
+                resource: "settings/account".to_string(),
+                locale: langid!("en"),
+            }])
+        );
+    }
+
+    #[test]
+    fn shared_resources() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "_unnamed.ftl": "app-name = Unnamed",
+                "common.ftl": "app-name = Shop\ngreeting = Hello from common",
+                "settings": {
+                    "account.ftl": "title = { app-name } account\ngreeting = Hello from account",
+                },
+            },
+        })
+        .unwrap();
+
+        let locales = || Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_shared_resources(vec!["common".to_string()])
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+        let translate = |resource, key| {
+            l10n.try_translate_with_args(&langid!("en"), resource, key, None)
+                .unwrap()
+        };
+        assert_eq!(translate("settings/account", "title"), "Shop account");
+        assert_eq!(
+            translate("settings/account", "greeting"),
+            "Hello from account"
+        );
+        assert_eq!(translate("common", "greeting"), "Hello from common");
+
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "settings/account", "title", None)
                 .unwrap(),
-            "About Root Brand [Lang term]."
+            "Unnamed account"
+        );
+
+        let result = L10nBuilder::parse(temp_dir.path(), Some(locales()))
+            .unwrap()
+            .set_shared_resources(vec!["commons".to_string()])
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            BuildErrors(vec![BuildError::UnknownSharedResource {
+                resource: "commons".to_string(),
+            }])
         );
+    }
+
+    #[test]
+    fn set_pseudo_localize() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    account = Account
+                    greeting = Hello { $name }!
+                "#}
+            },
+        })
+        .unwrap();
+
+        let build = |options: PseudoOptions| {
+            let locales = Locales::try_from([("en", None)]).unwrap();
+            L10nBuilder::parse(temp_dir.path(), Some(locales))
+                .unwrap()
+                .set_use_isolating(false)
+                .set_pseudo_localize(Some(options))
+                .build()
+                .unwrap()
+        };
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice");
+
+        let l10n = build(PseudoOptions {
+            expansion: 0.5,
+            brackets: ("[", "]"),
+            ..Default::default()
+        });
         assert_eq!(
-            translator
-                .try_translate_with_args(&langid!("en-CA"), "nested/about", "about-us", None)
+            l10n.try_translate_with_args(&langid!("en"), "app", "greeting", Some(&args))
                 .unwrap(),
-            "(Nested) About Nested Brand a Global Brand subdivision [Lang term]."
+            "[Ħḗŀŀǿ Alice! !!!!!!]"
         );
+
+        let l10n = build(PseudoOptions {
+            accent: false,
+            expansion: 0.0,
+            brackets: ("⟦", "⟧"),
+        });
         assert_eq!(
-            translator
-                .try_translate_with_args(&langid!("fr"), "about", "about-us", None)
+            l10n.try_translate_with_args(&langid!("en"), "app", "account", None)
                 .unwrap(),
-            "À propos de Root Brand [Lang term]."
+            "⟦Account⟧"
         );
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let result = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_transform_for(langid!("en"), |text| Cow::Owned(text.to_uppercase()))
+            .set_pseudo_localize(Some(PseudoOptions::default()))
+            .build();
         assert_eq!(
-            translator
-                .try_translate_with_args(&langid!("fr"), "nested/about", "about-us", None)
-                .unwrap(),
-            "(Nested) À propos de Nested Brand une sous division de Global Brand [Lang term]."
+            result.unwrap_err(),
+            BuildErrors(vec![BuildError::PseudoLocalizeTransform])
         );
     }
 
     #[test]
-    fn parse_missing_resource() {
+    fn postprocess() {
         let temp_dir = macro_files::create_temp!({
             "en": {
-                "resource-1.ftl": indoc! {r#"
-                    first-key = First key [en]
-                "#},
-                "resource-2.ftl": indoc! {r#"
-                    first-key = First key [en]
+                "app.ftl": indoc! {r#"
+                    greeting = Hello { $name }!
                 "#}
             },
             "fr": {
-                "resource-1.ftl": indoc! {r#"
-                    first-key = First key [fr]
+                "app.ftl": indoc! {r#"
+                    greeting = Bonjour { $name } !
                 "#}
             },
         })
         .unwrap();
 
-        let locales =
-            Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap();
+        fn non_breaking_spaces(locale: &LanguageIdentifier, translation: String) -> String {
+            match locale.language.as_str() {
+                "fr" => translation.replace(" !", "\u{a0}!"),
+                _ => translation,
+            }
+        }
 
-        let translator_builder = L10nBuilder::parse(temp_dir.path(), Some(locales)).unwrap();
-        let actual_err = translator_builder.build().unwrap_err();
-        let expected_err = BuildErrors(vec![BuildError::MissingResource {
-            resource: "resource-2".to_string(),
-            locales: vec![langid!("fr")],
-        }]);
-        assert_eq!(actual_err, expected_err);
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .set_postprocess(Some(non_breaking_spaces))
+            .build()
+            .unwrap();
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Alice");
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("fr"), "app", "greeting", Some(&args))
+                .unwrap(),
+            "Bonjour Alice\u{a0}!"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "greeting", Some(&args))
+                .unwrap(),
+            "Hello Alice!"
+        );
+
+        let mut out = String::new();
+        l10n.write_translation(&mut out, &langid!("fr"), "app", "greeting", Some(&args))
+            .unwrap();
+        assert_eq!(out, "Bonjour Alice\u{a0}!");
     }
 
     #[test]
@@ -875,10 +5264,160 @@ mod tests {
         let _ = translator_builder.build().unwrap();
     }
 
+    #[test]
+    fn from_sources() {
+        let sources = [
+            ("_terms.ftl", "-brand = Brand"),
+            ("en/app.ftl", "title = { -brand } app"),
+            ("en/_common.ftl", "cancel = Cancel"),
+            ("en/settings/account.ftl", "title = Account { cancel }"),
+            ("en/notes.txt", "not a fluent file"),
+            ("fr/app.ftl", "title = Appli { -brand }"),
+            ("fr/settings/account.ftl", "title = Compte"),
+            ("de/app.ftl", "title = { -brand } App"),
+        ]
+        .map(|(path, source)| (path, source.to_string()));
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::from_sources(sources.clone(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+        let translate = |locale, resource| {
+            l10n.try_translate_with_args(&locale, resource, "title", None)
+                .unwrap()
+                .into_owned()
+        };
+        assert_eq!(translate(langid!("en"), "app"), "Brand app");
+        assert_eq!(translate(langid!("fr"), "app"), "Appli Brand");
+        assert_eq!(
+            translate(langid!("en"), "settings/account"),
+            "Account Cancel"
+        );
+        assert_eq!(translate(langid!("fr"), "settings/account"), "Compte");
+        assert_eq!(
+            l10n.available_locales().collect::<Vec<_>>(),
+            [&langid!("en"), &langid!("fr")]
+        );
+
+        let locales = Locales::try_from([("en", None), ("es", None)]).unwrap();
+        assert!(matches!(
+            L10nBuilder::from_sources(sources, Some(locales)),
+            Err(ParserError::MissingLocales(locales)) if locales == [langid!("es")]
+        ));
+        assert!(matches!(
+            L10nBuilder::from_sources([("app.ftl", String::new())], None),
+            Err(ParserError::GlobalNamedResource { .. })
+        ));
+    }
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn from_embedded() {
+        use include_dir::{Dir, DirEntry, File};
+
+        static DIR: Dir = Dir::new(
+            "",
+            &[
+                DirEntry::File(File::new("_terms.ftl", b"-brand = Brand")),
+                DirEntry::Dir(Dir::new(
+                    "en",
+                    &[
+                        DirEntry::File(File::new("en/app.ftl", b"title = { -brand } app")),
+                        DirEntry::Dir(Dir::new(
+                            "en/settings",
+                            &[DirEntry::File(File::new(
+                                "en/settings/account.ftl",
+                                b"title = Account",
+                            ))],
+                        )),
+                        DirEntry::File(File::new("en/notes.txt", b"not a fluent file")),
+                    ],
+                )),
+                DirEntry::Dir(Dir::new(
+                    "fr",
+                    &[
+                        DirEntry::File(File::new("fr/app.ftl", b"title = Appli { -brand }")),
+                        DirEntry::Dir(Dir::new(
+                            "fr/settings",
+                            &[DirEntry::File(File::new(
+                                "fr/settings/account.ftl",
+                                b"title = Compte",
+                            ))],
+                        )),
+                    ],
+                )),
+            ],
+        );
+
+        let l10n = L10nBuilder::from_embedded(&DIR, None)
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "title", None)
+                .unwrap(),
+            "Brand app"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("fr"), "app", "title", None)
+                .unwrap(),
+            "Appli Brand"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "settings/account", "title", None)
+                .unwrap(),
+            "Account"
+        );
+
+        let locales = Locales::try_from([("en", None), ("de", None)]).unwrap();
+        assert!(matches!(
+            L10nBuilder::from_embedded(&DIR, Some(locales)),
+            Err(ParserError::MissingLocales(locales)) if locales == vec![langid!("de")]
+        ));
+    }
+
+    #[test]
+    fn parse_with_progress() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static FILES: AtomicUsize = AtomicUsize::new(0);
+
+        let temp_dir = macro_files::create_temp!({
+            "_brand.ftl": true,
+            "README.md": true,
+            "en": {
+                "_terms.ftl": true,
+                "app.ftl": true,
+                "settings": {
+                    "account.ftl": true,
+                    "ignored_file": true,
+                },
+            },
+            "fr": {
+                "app.ftl": true,
+                "settings": {
+                    "account.ftl": true,
+                },
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let _ = L10nBuilder::parse_with_progress(temp_dir.path(), Some(locales), |_| {
+            FILES.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        assert_eq!(FILES.load(Ordering::SeqCst), 6);
+    }
+
     #[test]
     fn required_functions() {
         let temp_dir = macro_files::create_temp!({
-            "_term.ftl": "-brand-creation-date = 2000",
+            "_term.ftl": "-brand-creation = 2000",
             "en": {
                 "_term.ftl": indoc! {r#"
                     -brand = { LANG_TERM_EN_FUNCTION("Brand") }
@@ -935,4 +5474,422 @@ mod tests {
         ]);
         assert_eq!(translator.required_functions(), expected);
     }
+
+    #[test]
+    fn check_plural_categories() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    emails = { $count ->
+                        [0] No emails
+                        [1] One email
+                       *[other] { $count } emails
+                    }
+                    status = Status
+                        .busy = { $gender ->
+                            [male] He is busy
+                           *[other] They are busy
+                        }
+                    place = { ORDINAL($place) ->
+                        [one] { $place }st
+                        [two] { $place }nd
+                       *[other] { $place }th
+                    }
+                    rank = { NUMBER($rank, type: "ordinal") ->
+                        [one] { $rank }st
+                        [two] { $rank }nd
+                        [few] { $rank }rd
+                       *[other] { $rank }th
+                    }
+                "#},
+            },
+            "pl": {
+                "app.ftl": indoc! {r#"
+                    emails = { $count ->
+                        [one] Jeden e-mail
+                       *[other] { $count } e-maili
+                    }
+                    status = Status
+                        .busy = Zajęty ({ $hours ->
+                            [one] godzina
+                            [few] godziny
+                           *[many] godzin
+                        })
+                    place = { ORDINAL($place) ->
+                        [one] pierwsze
+                       *[other] { $place }.
+                    }
+                    rank = { "one" ->
+                        [one] jeden
+                       *[other] inny
+                    }
+                "#},
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("pl", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let missing = |locale, message: &str, category: &str| BuildError::MissingPluralCategory {
+            resource: "app".to_string(),
+            message: message.to_string(),
+            locale,
+            category: category.to_string(),
+        };
+        // The ordinal selectors use the ordinal rules, the string selectors
+        // are not checked
+        assert_eq!(
+            l10n.check_plural_categories(),
+            Err(BuildErrors(vec![
+                missing(langid!("pl"), "emails", "few"),
+                missing(langid!("pl"), "emails", "many"),
+                missing(langid!("en"), "place", "few"),
+                missing(langid!("pl"), "status.busy", "other"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn required_functions_without_unused_terms() {
+        let temp_dir = macro_files::create_temp!({
+            "_terms.ftl": indoc! {r#"
+                -unused = { UNUSED_FUNCTION("Unused") }
+                -brand = { BRAND_FUNCTION("Brand") }
+                -company = { -brand } { COMPANY_FUNCTION() }
+                -nested = { NESTED_FUNCTION() }
+            "#},
+            "en": {
+                "about.ftl": indoc! {r#"
+                    about-us = About { -company }
+                    contact = { PHONE($country, -nested) }
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(l10n.required_functions().contains("UNUSED_FUNCTION"));
+        assert_eq!(
+            l10n.required_functions_without_unused_terms(),
+            HashSet::from([
+                "BRAND_FUNCTION",
+                "COMPANY_FUNCTION",
+                "NESTED_FUNCTION",
+                "PHONE"
+            ])
+        );
+    }
+
+    #[test]
+    fn defined_variables() {
+        let temp_dir = macro_files::create_temp!({
+            "_common.ftl": "shared = { $shared }",
+            "en": {
+                "app.ftl": indoc! {r#"
+                    greeting = Hello { $first-name }!
+                    status =
+                        .busy = Busy ({ $reason })
+                    welcome = { greeting } Welcome on { $app }.
+                "#}
+            },
+            "fr": {
+                "app.ftl": indoc! {r#"
+                    greeting = Bonjour { $first-name } { $last-name } !
+                    status =
+                        .busy = Occupé ({ $reason })
+                    welcome = { greeting } Bienvenue sur { $app }.
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let expected = BTreeMap::from([
+            ("greeting", HashSet::from(["first-name", "last-name"])),
+            ("status.busy", HashSet::from(["reason"])),
+            ("welcome", HashSet::from(["first-name", "last-name", "app"])),
+        ]);
+        assert_eq!(l10n.defined_variables("app").unwrap(), expected);
+        assert_eq!(
+            l10n.defined_variables("unknown"),
+            Err(TranslateError::ResourceNotExists("unknown".to_string()))
+        );
+    }
+
+    #[test]
+    fn args_diff() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    notification = { $user } sent { $count } messages to { $channel }
+                    notification-title = { $user } in { $channel }
+                    notification-body = { $count } new messages since { $date }
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let (only_a, only_b, shared) = l10n
+            .args_diff("app", "notification", "notification-body")
+            .unwrap();
+        assert_eq!(only_a, HashSet::from(["user", "channel"]));
+        assert_eq!(only_b, HashSet::from(["date"]));
+        assert_eq!(shared, HashSet::from(["count"]));
+
+        let (only_a, only_b, shared) = l10n
+            .args_diff("app", "notification", "notification-title")
+            .unwrap();
+        assert_eq!(only_a, HashSet::from(["count"]));
+        assert!(only_b.is_empty());
+        assert_eq!(shared, HashSet::from(["user", "channel"]));
+
+        assert!(matches!(
+            l10n.args_diff("app", "notification", "unknown"),
+            Err(TranslateError::MessageIdNotExists { .. })
+        ));
+    }
+
+    #[test]
+    fn function_with_locale() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "today = Today is { DATE() }",
+            },
+            "en-GB": {
+                "app.ftl": "tomorrow = Tomorrow",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("en-GB", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .add_function_with_locale("DATE", |locale, _, _| {
+                FluentValue::from(match locale.region.as_ref().map(|region| region.as_str()) {
+                    Some("GB") => "22/02/2023",
+                    _ => "02/22/2023",
+                })
+            })
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "today", None)
+                .unwrap(),
+            "Today is 02/22/2023"
+        );
+        // The message comes from the fallback locale but the function receives
+        // the main locale of the bundle.
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en-GB"), "app", "today", None)
+                .unwrap(),
+            "Today is 22/02/2023"
+        );
+    }
+
+    #[test]
+    fn bundle_function() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "_terms.ftl": "-brand = Acme",
+                "app.ftl": indoc! {r#"
+                    footer = { COPYRIGHT() }
+                    rights = All rights reserved
+                    signed = { SIGNED() }
+                    loop = { LOOP() }
+                "#}
+            },
+            "fr": {
+                "_terms.ftl": "-brand = Acmé",
+                "app.ftl": "rights = Tous droits réservés",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", Some("en"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .add_bundle_function("COPYRIGHT", |resolver, _, _| {
+                match (resolver.format("-brand"), resolver.format("rights")) {
+                    (Some(brand), Some(rights)) => FluentValue::from(format!(
+                        "© {} ({}). {}",
+                        brand,
+                        resolver.locale(),
+                        rights
+                    )),
+                    _ => FluentValue::Error,
+                }
+            })
+            .add_bundle_function("SIGNED", |resolver, _, _| match resolver.format("footer") {
+                Some(footer) => FluentValue::from(format!("{} Signed.", footer)),
+                None => FluentValue::Error,
+            })
+            .add_bundle_function("LOOP", |resolver, _, _| match resolver.format("loop") {
+                Some(value) => FluentValue::from(value),
+                None => FluentValue::Error,
+            })
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "signed", None)
+                .unwrap(),
+            "© Acme (en). All rights reserved Signed."
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "loop", None)
+                .unwrap(),
+            "LOOP()"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("en"), "app", "footer", None)
+                .unwrap(),
+            "© Acme (en). All rights reserved"
+        );
+        assert_eq!(
+            l10n.try_translate_with_args(&langid!("fr"), "app", "footer", None)
+                .unwrap(),
+            "© Acmé (fr). Tous droits réservés"
+        );
+        assert!(l10n.registered_functions().contains(&"COPYRIGHT"));
+    }
+
+    #[test]
+    fn estimated_memory() {
+        let temp_dir = macro_files::create_temp!({
+            "_common.ftl": "brand = Acme",
+            "en": {
+                "app.ftl": "title = Title",
+                "settings.ftl": "title = Settings",
+            },
+            "fr": {
+                "app.ftl": "title = Titre",
+                "settings.ftl": "title = Paramètres",
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let estimate = l10n.estimated_memory();
+        assert_eq!(
+            estimate.source_bytes,
+            [
+                "brand = Acme",
+                "title = Title",
+                "title = Settings",
+                "title = Titre",
+                "title = Paramètres",
+            ]
+            .iter()
+            .map(|source| source.len())
+            .sum::<usize>()
+        );
+        assert_eq!(estimate.bundles, 4);
+    }
+
+    #[test]
+    fn registered_functions() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": "contact-us = Contact us at { PHONE_NUMBER($country) }."
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .add_function("PHONE_NUMBER", |_, _| FluentValue::None)
+            .add_function("DATETIME", |_, _| FluentValue::None)
+            .build()
+            .unwrap();
+
+        let mut expected = vec!["DATETIME", "PHONE_NUMBER"];
+        expected.extend(crate::builtins::FUNCTIONS);
+        expected.sort_unstable();
+        assert_eq!(l10n.registered_functions(), expected);
+        assert_eq!(l10n.required_functions(), HashSet::from(["PHONE_NUMBER"]));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serialize_round_trip() {
+        let temp_dir = macro_files::create_temp!({
+            "_brand.ftl": "-brand = Brand",
+            "en": {
+                "_common.ftl": "common = Common",
+                "app.ftl": "greeting = Hello { $name } from { -brand }, { common }"
+            },
+            "fr": {
+                "_common.ftl": "common = Commun",
+                "app.ftl": "greeting = Bonjour { $name } de { -brand }, { common }"
+            },
+        })
+        .unwrap();
+
+        let locales =
+            Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        let bytes = l10n.serialize().unwrap();
+        let deserialized = L10nBuilder::deserialize(&bytes)
+            .unwrap()
+            .set_use_isolating(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(deserialized.locales, l10n.locales);
+        let args = FluentArgs::from_iter([("name", "Alice")]);
+        for locale in [langid!("en"), langid!("fr"), langid!("fr-CA")] {
+            assert_eq!(
+                deserialized
+                    .try_translate_with_args(&locale, "app", "greeting", Some(&args))
+                    .unwrap(),
+                l10n.try_translate_with_args(&locale, "app", "greeting", Some(&args))
+                    .unwrap()
+            );
+        }
+        assert_eq!(
+            deserialized
+                .try_translate_with_args(&langid!("fr-CA"), "app", "greeting", Some(&args))
+                .unwrap(),
+            "Bonjour Alice de Brand, Commun"
+        );
+
+        assert!(matches!(
+            L10nBuilder::deserialize(&bytes[..bytes.len() / 2]),
+            Err(SerializeError::Bincode(_))
+        ));
+    }
 }
@@ -1,7 +1,10 @@
-use crate::l10n::TranslateError;
+use crate::l10n::{L10n, TranslateError};
 use crate::UNEXPECTED_MESSAGE;
 use fluent_bundle::FluentArgs;
+use serde::{Serialize, Serializer};
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
 use unic_langid::LanguageIdentifier;
 
 pub trait L10nMessage<'s, 'r> {
@@ -28,7 +31,116 @@ pub trait L10nMessage<'s, 'r> {
     }
 
     fn translate(&'s self, locale: &LanguageIdentifier) -> Cow<'r, str> {
-        self.try_translate_with_args(locale, None)
-            .unwrap_or_else(|_| Cow::from(UNEXPECTED_MESSAGE))
+        self.translate_or(locale, |_| Cow::from(UNEXPECTED_MESSAGE))
+    }
+
+    /// Same as [`L10nMessage::translate`] but the translation on error is
+    /// returned by `f`, e.g. to log the error.
+    fn translate_or<F>(&'s self, locale: &LanguageIdentifier, f: F) -> Cow<'r, str>
+    where
+        F: FnOnce(TranslateError) -> Cow<'r, str>,
+    {
+        self.try_translate_with_args(locale, None).unwrap_or_else(f)
+    }
+
+    /// Same as [`L10nMessage::translate`] without the bidi isolation marks, for
+    /// plain text contexts like emails or log lines.
+    fn translate_stripped(&'s self, locale: &LanguageIdentifier) -> Cow<'r, str> {
+        strip_isolation(self.translate(locale))
+    }
+
+    /// Translation in every main locale of `l10n`, e.g. to preview a message.
+    /// A derived type is always translated with the `L10N` of its crate.
+    fn translate_all_locales(&'s self, l10n: &L10n) -> BTreeMap<LanguageIdentifier, Cow<'r, str>> {
+        l10n.main_locales_translations(|locale| self.translate(locale))
+    }
+
+    /// Translates and escapes for HTML, except the occurrences of the trusted
+    /// `markup` values.
+    #[cfg(feature = "html")]
+    fn try_translate_html_with_markup(
+        &'s self,
+        locale: &LanguageIdentifier,
+        markup: &[&str],
+    ) -> Result<String, TranslateError> {
+        self.try_translate(locale)
+            .map(|translation| crate::html::escape(&translation, markup))
+    }
+
+    #[cfg(feature = "html")]
+    fn translate_html_with_markup(
+        &'s self,
+        locale: &LanguageIdentifier,
+        markup: &[&str],
+    ) -> String {
+        self.try_translate_html_with_markup(locale, markup)
+            .unwrap_or_else(|_| UNEXPECTED_MESSAGE.to_string())
+    }
+
+    #[cfg(feature = "html")]
+    fn try_translate_html(&'s self, locale: &LanguageIdentifier) -> Result<String, TranslateError> {
+        self.try_translate_html_with_markup(locale, &[])
     }
+
+    #[cfg(feature = "html")]
+    fn translate_html(&'s self, locale: &LanguageIdentifier) -> String {
+        self.translate_html_with_markup(locale, &[])
+    }
+}
+
+/// Resources and keys translated by a type, implemented by deriving
+/// `L10nMessage` with `#[l10n_keys]`, see [`L10n::check_message_keys`](crate::l10n::L10n::check_message_keys).
+pub trait L10nMessageKeys {
+    fn message_keys() -> Vec<(&'static str, &'static str)>;
+}
+
+impl<T: L10nMessageKeys + ?Sized> L10nMessageKeys for &T {
+    fn message_keys() -> Vec<(&'static str, &'static str)> {
+        T::message_keys()
+    }
+}
+
+/// Removes the Unicode bidi isolation marks (U+2066 to U+2069) fluent adds
+/// around placeables.
+pub fn strip_isolation<'a>(translation: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
+    let translation = translation.into();
+    if translation.contains(is_isolation_mark) {
+        Cow::Owned(translation.replace(is_isolation_mark, ""))
+    } else {
+        translation
+    }
+}
+
+fn is_isolation_mark(c: char) -> bool {
+    matches!(c, '\u{2066}'..='\u{2069}')
+}
+
+/// Serializes the translation of a message in a locale as a string, e.g. in a
+/// JSON response, serde having no locale context. A translation error is a
+/// serialization error.
+pub struct Localized<'a, T>(pub &'a T, pub &'a LanguageIdentifier);
+
+impl<'a, 'r, T: L10nMessage<'a, 'r>> Serialize for Localized<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let translation = self
+            .0
+            .try_translate(self.1)
+            .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&translation)
+    }
+}
+
+// Translates every message of the map with the same locale.
+pub fn translate_map<'s, 'r, K, M>(
+    locale: &LanguageIdentifier,
+    messages: &'s HashMap<K, M>,
+) -> HashMap<K, String>
+where
+    K: Clone + Eq + Hash,
+    M: L10nMessage<'s, 'r>,
+{
+    messages
+        .iter()
+        .map(|(key, message)| (key.clone(), message.translate(locale).into_owned()))
+        .collect()
 }
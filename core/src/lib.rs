@@ -2,12 +2,27 @@ pub use fluent_bundle;
 pub use intl_memoizer;
 pub use unic_langid;
 
+#[cfg(feature = "ast")]
+pub mod ast;
+pub mod builtins;
 pub mod config;
+#[cfg(feature = "extract")]
+pub mod extract;
+#[cfg(feature = "http")]
+pub mod http;
 pub mod l10n;
 pub mod l10n_message;
 pub mod locales;
 pub mod message;
+pub mod pseudo;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod resolver;
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+pub mod watch;
 
+#[cfg(feature = "html")]
+mod html;
 mod resource;
 mod utils;
 
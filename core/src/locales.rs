@@ -1,15 +1,16 @@
 use crate::utils::locales_to_string;
 use serde::{de, Deserialize, Deserializer};
-use std::{collections::HashSet, fmt, marker::PhantomData};
+use std::{collections::HashSet, fmt, fs, io, marker::PhantomData, path::Path};
 use thiserror::Error;
 use unic_langid::{LanguageIdentifier, LanguageIdentifierError};
 
-#[derive(Default, PartialEq, Eq, Debug)]
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
 pub struct Locales {
     locales: Vec<LocaleEntry>,
+    primary: Option<LanguageIdentifier>,
 }
 
-#[derive(PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct LocaleEntry {
     main: LanguageIdentifier,
     fallback: Option<LanguageIdentifier>,
@@ -21,6 +22,12 @@ pub enum InvariantError {
     InfiniteFallbackLoop(Vec<LanguageIdentifier>),
     #[error("main locale duplicate: {0}")]
     MainLocaleDuplicate(LanguageIdentifier),
+    #[error("primary locale is not a main locale: {0}")]
+    PrimaryNotMainLocale(LanguageIdentifier),
+    #[error("cannot remove the primary locale: {0}")]
+    RemovePrimary(LanguageIdentifier),
+    #[error("cannot remove {0}, it is the fallback of: {}", locales_to_string(.1, ", "))]
+    RemoveFallback(LanguageIdentifier, Vec<LanguageIdentifier>),
     #[error("empty")]
     Empty,
 }
@@ -33,9 +40,72 @@ pub enum TryFromLocalesError {
     Invariant(#[from] InvariantError),
 }
 
+#[derive(Error, Debug)]
+pub enum FromDirError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    ParseLocale(#[from] LanguageIdentifierError),
+    #[error(transparent)]
+    Invariant(#[from] InvariantError),
+}
+
 impl Locales {
+    /// Discovers the locale directories of `dir` like [`Locales::from`] but
+    /// the fallback of a locale is set by the first matching rule
+    /// `(pattern, fallback)`, a pattern is a locale (e.g. `en-CA`) or a
+    /// prefix followed by `*` (e.g. `en-*`).
+    pub fn from_dir_with_rules(
+        dir: impl AsRef<Path>,
+        rules: &[(&str, &str)],
+    ) -> Result<Self, FromDirError> {
+        let mut discovered_locales = HashSet::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if let Some(Ok(locale)) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.parse::<LanguageIdentifier>())
+            {
+                discovered_locales.insert(locale);
+            }
+        }
+
+        let rules = rules
+            .iter()
+            .map(|(pattern, fallback)| Ok((*pattern, fallback.parse()?)))
+            .collect::<Result<Vec<(&str, LanguageIdentifier)>, LanguageIdentifierError>>()?;
+
+        let mut this = Self::from(discovered_locales);
+        for entry in &mut this.locales {
+            let locale = entry.main.to_string();
+            let rule = rules
+                .iter()
+                .find(|(pattern, _)| match pattern.strip_suffix('*') {
+                    Some(prefix) => locale.starts_with(prefix),
+                    None => locale == *pattern,
+                });
+            if let Some((_, fallback)) = rule {
+                entry.fallback = (fallback != &entry.main).then(|| fallback.clone());
+            }
+        }
+        this.check_invariants()?;
+        Ok(this)
+    }
+
+    /// `Locales::builder().add("en", None).add("fr-CA", Some("fr")).build()`
+    pub fn builder() -> LocalesBuilder {
+        LocalesBuilder::default()
+    }
+
     pub fn try_new(locales: Vec<LocaleEntry>) -> Result<Self, InvariantError> {
-        let this = Self { locales };
+        let this = Self {
+            locales,
+            primary: None,
+        };
         this.check_invariants()?;
         Ok(this)
     }
@@ -79,6 +149,60 @@ impl Locales {
         self.locales.is_empty()
     }
 
+    /// The explicitly set primary locale or else the first main locale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no locale, which cannot happen for the locales of a
+    /// built [`L10n`](crate::L10n).
+    pub fn primary(&self) -> &LanguageIdentifier {
+        self.primary
+            .as_ref()
+            .unwrap_or_else(|| &self.locales[0].main)
+    }
+
+    /// Whether `locale` is a main locale.
+    pub fn contains(&self, locale: &LanguageIdentifier) -> bool {
+        self.find_with_main_locale(locale).is_some()
+    }
+
+    /// Removes the main locale `locale`, returns `None` if it is not a main
+    /// locale. The removal is rejected if the locale is the fallback of other
+    /// locales, the explicitly set primary locale or the last one.
+    pub fn remove(
+        &mut self,
+        locale: &LanguageIdentifier,
+    ) -> Result<Option<LocaleEntry>, InvariantError> {
+        let index = match self.locales.iter().position(|entry| &entry.main == locale) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let dependents: Vec<_> = self
+            .locales
+            .iter()
+            .filter(|entry| entry.fallback.as_ref() == Some(locale))
+            .map(|entry| entry.main.clone())
+            .collect();
+        if !dependents.is_empty() {
+            return Err(InvariantError::RemoveFallback(locale.clone(), dependents));
+        }
+        if self.primary.as_ref() == Some(locale) {
+            return Err(InvariantError::RemovePrimary(locale.clone()));
+        }
+        if self.locales.len() == 1 {
+            return Err(InvariantError::Empty);
+        }
+        Ok(Some(self.locales.remove(index)))
+    }
+
+    pub fn set_primary(&mut self, locale: LanguageIdentifier) -> Result<(), InvariantError> {
+        if self.find_with_main_locale(&locale).is_none() {
+            return Err(InvariantError::PrimaryNotMainLocale(locale));
+        }
+        self.primary = Some(locale);
+        Ok(())
+    }
+
     fn find_with_main_locale<'a, 'b>(
         &'a self,
         locale: &'b LanguageIdentifier,
@@ -141,7 +265,53 @@ impl Locales {
             .collect()
     }
 
-    // Only for main locales
+    /// Returns the main locale to use for the `requested` locale: the same
+    /// locale, or else the one without region matching `requested` with its
+    /// region removed (`fr` for `fr-CH`), or else the first one with the same
+    /// language and script. With the "likely-subtags" feature the locales are
+    /// compared once maximized, so `zh` matches `zh-Hans` and `zh-TW` matches
+    /// `zh-Hant`.
+    pub fn negotiate(&self, requested: &LanguageIdentifier) -> Option<&LanguageIdentifier> {
+        if let Some(entry) = self.find_with_main_locale(requested) {
+            return Some(&entry.main);
+        }
+
+        let requested = maximized(requested);
+        let candidates: Vec<_> = self
+            .locales
+            .iter()
+            .map(|entry| (&entry.main, maximized(&entry.main)))
+            .collect();
+        candidates
+            .iter()
+            .find(|(_, candidate)| *candidate == requested)
+            .or_else(|| {
+                candidates.iter().find(|(locale, candidate)| {
+                    locale.region.is_none()
+                        && without_region(candidate) == without_region(&requested)
+                })
+            })
+            .or_else(|| {
+                candidates.iter().find(|(_, candidate)| {
+                    candidate.language == requested.language && candidate.script == requested.script
+                })
+            })
+            .map(|(locale, _)| *locale)
+    }
+
+    /// Returns the main locale to use for the first `requested` locale that can
+    /// be negotiated, e.g. the locales of an `Accept-Language` header ordered by
+    /// preference.
+    pub fn negotiate_preferences(
+        &self,
+        requested: &[LanguageIdentifier],
+    ) -> Option<&LanguageIdentifier> {
+        requested.iter().find_map(|locale| self.negotiate(locale))
+    }
+
+    /// The `locale` followed by its fallbacks, `None` if `locale` is not a
+    /// main locale. A locale has at most one fallback, so the route only
+    /// depends on the entries and is the same on every run.
     pub fn locale_resolution_route<'a, 'b>(
         &'a self,
         locale: &'b LanguageIdentifier,
@@ -161,6 +331,20 @@ impl Locales {
     }
 }
 
+fn maximized(locale: &LanguageIdentifier) -> LanguageIdentifier {
+    #[allow(unused_mut)]
+    let mut locale = locale.clone();
+    #[cfg(feature = "likely-subtags")]
+    locale.maximize();
+    locale
+}
+
+fn without_region(locale: &LanguageIdentifier) -> LanguageIdentifier {
+    let mut locale = locale.clone();
+    locale.region = None;
+    locale
+}
+
 impl<'de> Deserialize<'de> for Locales {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         #[derive(Deserialize)]
@@ -189,27 +373,63 @@ where
     type Error = TryFromLocalesError;
 
     fn try_from(values: [(T, Option<T>); N]) -> Result<Self, Self::Error> {
-        let locales = values
-            .into_iter()
-            .map(|(main_str, fallback_str)| {
-                Ok(LocaleEntry {
-                    main: main_str.as_ref().parse()?,
-                    fallback: fallback_str.map(|str| str.as_ref().parse()).transpose()?,
-                })
+        try_from_strings(values)
+    }
+}
+
+fn try_from_strings<T: AsRef<str>>(
+    values: impl IntoIterator<Item = (T, Option<T>)>,
+) -> Result<Locales, TryFromLocalesError> {
+    let locales = values
+        .into_iter()
+        .map(|(main_str, fallback_str)| {
+            Ok(LocaleEntry {
+                main: main_str.as_ref().parse()?,
+                fallback: fallback_str.map(|str| str.as_ref().parse()).transpose()?,
             })
-            .collect::<Result<Vec<_>, LanguageIdentifierError>>()?;
+        })
+        .collect::<Result<Vec<_>, LanguageIdentifierError>>()?;
+
+    Ok(Locales::try_new(locales)?)
+}
+
+/// Builds [`Locales`] one locale at a time, see [`Locales::builder`].
+#[derive(Default, Debug)]
+pub struct LocalesBuilder {
+    locales: Vec<(String, Option<String>)>,
+}
+
+impl LocalesBuilder {
+    pub fn add(mut self, main: &str, fallback: Option<&str>) -> Self {
+        self.locales
+            .push((main.to_string(), fallback.map(str::to_string)));
+        self
+    }
 
-        Ok(Self::try_new(locales)?)
+    /// Parses the locales and checks the invariants like
+    /// [`Locales::try_from`].
+    pub fn build(self) -> Result<Locales, TryFromLocalesError> {
+        try_from_strings(self.locales)
     }
 }
 
+/// Infers the fallbacks: a locale with a region (e.g. `en-GB`, `zh-Hant-TW`)
+/// falls back to the same locale without region if it is in the set. The
+/// locales without region come first, each group sorted by their string
+/// representation, so the result (and the primary locale) does not depend on
+/// the iteration order of the set.
 impl From<HashSet<LanguageIdentifier>> for Locales {
     fn from(locales: HashSet<LanguageIdentifier>) -> Self {
+        let mut locales: Vec<_> = locales.into_iter().collect();
+        // Sorted to have a deterministic order (and therefore primary locale),
+        // the string representation of a locale is canonical so there is no tie.
+        locales.sort_by_key(|locale| locale.to_string());
+
         let (primary_locales, secondary_locales): (Vec<_>, Vec<_>) = locales
             .into_iter()
             .partition(|locale| locale.region.is_none());
 
-        let mut locales: Vec<_> = secondary_locales
+        let secondary_locales: Vec<_> = secondary_locales
             .into_iter()
             .map(|secondary_locale| {
                 let mut stripped_locale = secondary_locale.clone();
@@ -221,19 +441,21 @@ impl From<HashSet<LanguageIdentifier>> for Locales {
             })
             .collect();
 
-        locales.extend(
-            primary_locales
-                .into_iter()
-                .map(|locale| LocaleEntry::new(locale, None))
-                .collect::<Vec<_>>(),
-        );
+        let mut locales: Vec<_> = primary_locales
+            .into_iter()
+            .map(|locale| LocaleEntry::new(locale, None))
+            .collect();
+        locales.extend(secondary_locales);
 
-        Self { locales }
+        Self {
+            locales,
+            primary: None,
+        }
     }
 }
 
 impl LocaleEntry {
-    fn new(main: LanguageIdentifier, fallback: Option<LanguageIdentifier>) -> Self {
+    pub(crate) fn new(main: LanguageIdentifier, fallback: Option<LanguageIdentifier>) -> Self {
         Self { main, fallback }
     }
 
@@ -437,6 +659,7 @@ mod tests {
                 LocaleEntry::new(langid!("fr-CA"), Some(langid!("fr"))),
                 LocaleEntry::new(langid!("de"), None),
             ],
+            primary: None,
         };
         assert_eq!(actual, expected);
     }
@@ -480,6 +703,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_dir_with_rules() {
+        let temp_dir = macro_files::create_temp!({
+            "en": { "app.ftl": "" },
+            "en-GB": { "app.ftl": "" },
+            "en-AU": { "app.ftl": "" },
+            "en-CA": { "app.ftl": "" },
+            "fr": { "app.ftl": "" },
+            "fr-CA": { "app.ftl": "" },
+            "not-a-locale!": { "app.ftl": "" },
+            "de.ftl": "",
+        })
+        .unwrap();
+
+        let actual = Locales::from_dir_with_rules(
+            temp_dir.path(),
+            &[("en-CA", "en"), ("en-*", "en-GB"), ("fr-CA", "fr-CA")],
+        )
+        .unwrap();
+        let expected_locales = HashSet::from([
+            LocaleEntry::new(langid!("en"), None),
+            LocaleEntry::new(langid!("en-GB"), None),
+            LocaleEntry::new(langid!("en-AU"), Some(langid!("en-GB"))),
+            LocaleEntry::new(langid!("en-CA"), Some(langid!("en"))),
+            LocaleEntry::new(langid!("fr"), None),
+            LocaleEntry::new(langid!("fr-CA"), None),
+        ]);
+        assert_eq!(
+            actual.locales.into_iter().collect::<HashSet<_>>(),
+            expected_locales
+        );
+
+        let err = Locales::from_dir_with_rules(temp_dir.path(), &[("en", "en-GB")]).unwrap_err();
+        assert!(matches!(
+            err,
+            FromDirError::Invariant(InvariantError::InfiniteFallbackLoop(_))
+        ));
+    }
+
     #[test]
     fn locales_deserialize() {
         let source = toml::toml! {
@@ -626,6 +888,116 @@ mod tests {
         assert_eq!(translator_locales.main_locales(), expected);
     }
 
+    #[test]
+    fn primary() {
+        let mut locales =
+            Locales::try_from([("en-GB", Some("en")), ("fr", None), ("de", None)]).unwrap();
+        assert_eq!(locales.primary(), &langid!("en-GB"));
+
+        locales.set_primary(langid!("fr")).unwrap();
+        assert_eq!(locales.primary(), &langid!("fr"));
+
+        let err = locales.set_primary(langid!("en")).unwrap_err();
+        assert_eq!(err.to_string(), "primary locale is not a main locale: en");
+        assert_eq!(locales.primary(), &langid!("fr"));
+
+        let locales = Locales::from(HashSet::from([
+            langid!("fr-CA"),
+            langid!("fr"),
+            langid!("en-GB"),
+            langid!("en"),
+        ]));
+        assert_eq!(locales.primary(), &langid!("en"));
+    }
+
+    #[test]
+    fn builder() {
+        let locales = Locales::builder()
+            .add("en", None)
+            .add("fr", None)
+            .add("fr-CA", Some("fr"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            locales,
+            Locales::try_from([("en", None), ("fr", None), ("fr-CA", Some("fr"))]).unwrap()
+        );
+        assert_eq!(locales.primary(), &langid!("en"));
+
+        assert!(matches!(
+            Locales::builder().add("en", None).add("en", None).build(),
+            Err(TryFromLocalesError::Invariant(InvariantError::MainLocaleDuplicate(locale)))
+                if locale == langid!("en")
+        ));
+        assert!(matches!(
+            Locales::builder().add("en", Some("?")).build(),
+            Err(TryFromLocalesError::ParseLocale(_))
+        ));
+        assert!(matches!(
+            Locales::builder().build(),
+            Err(TryFromLocalesError::Invariant(InvariantError::Empty))
+        ));
+    }
+
+    #[test]
+    fn contains() {
+        let locales = Locales::try_from([("en-GB", Some("en")), ("fr", None)]).unwrap();
+        assert!(locales.contains(&langid!("en-GB")));
+        assert!(locales.contains(&langid!("fr")));
+        assert!(!locales.contains(&langid!("en")));
+        assert!(!locales.contains(&langid!("de")));
+    }
+
+    #[test]
+    fn remove() {
+        let mut locales = Locales::try_from([
+            ("en", None),
+            ("en-GB", Some("en")),
+            ("fr", None),
+            ("de", None),
+        ])
+        .unwrap();
+        locales.set_primary(langid!("fr")).unwrap();
+
+        assert_eq!(
+            locales.remove(&langid!("en-GB")).unwrap(),
+            Some(LocaleEntry::new(langid!("en-GB"), Some(langid!("en"))))
+        );
+        assert!(!locales.contains(&langid!("en-GB")));
+        assert_eq!(locales.remove(&langid!("en-GB")).unwrap(), None);
+        assert_eq!(
+            locales.remove(&langid!("de")).unwrap(),
+            Some(LocaleEntry::new(langid!("de"), None))
+        );
+
+        let err = locales.remove(&langid!("fr")).unwrap_err();
+        assert_eq!(err.to_string(), "cannot remove the primary locale: fr");
+
+        let mut locales = Locales::try_from([
+            ("en", None),
+            ("en-GB", Some("en")),
+            ("en-AU", Some("en-GB")),
+        ])
+        .unwrap();
+        let err = locales.remove(&langid!("en")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot remove en, it is the fallback of: en-GB"
+        );
+        let err = locales.remove(&langid!("en-GB")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot remove en-GB, it is the fallback of: en-AU"
+        );
+        assert!(locales.contains(&langid!("en-GB")));
+
+        let mut locales = Locales::try_from([("en", None)]).unwrap();
+        assert!(matches!(
+            locales.remove(&langid!("en")),
+            Err(InvariantError::Empty)
+        ));
+    }
+
     #[test]
     fn locale_resolution_route() {
         let en = langid!("en");
@@ -658,4 +1030,134 @@ mod tests {
             assert_eq!(translator_locales.locale_resolution_route(locale), expected);
         }
     }
+
+    #[test]
+    fn deterministic_from_hash_set() {
+        let locales = [
+            langid!("zh-Hant-TW"),
+            langid!("en-GB"),
+            langid!("zh-Hant"),
+            langid!("en"),
+            langid!("fr-CA"),
+            langid!("zh"),
+        ];
+        let expected = Locales::try_from([
+            ("en", None),
+            ("zh", None),
+            ("zh-Hant", None),
+            ("en-GB", Some("en")),
+            ("fr-CA", None),
+            ("zh-Hant-TW", Some("zh-Hant")),
+        ])
+        .unwrap();
+
+        // Every permutation, each set also has its own random hasher state.
+        fn permutations(
+            locales: &mut Vec<LanguageIdentifier>,
+            k: usize,
+            f: &mut impl FnMut(&[LanguageIdentifier]),
+        ) {
+            if k == locales.len() {
+                return f(locales);
+            }
+            for i in k..locales.len() {
+                locales.swap(k, i);
+                permutations(locales, k + 1, f);
+                locales.swap(k, i);
+            }
+        }
+        let mut count = 0;
+        permutations(&mut locales.to_vec(), 0, &mut |locales| {
+            let actual = Locales::from(locales.iter().cloned().collect::<HashSet<_>>());
+            assert_eq!(actual, expected);
+            assert_eq!(actual.primary(), &langid!("en"));
+            for locale in locales {
+                assert_eq!(
+                    actual.locale_resolution_route(locale),
+                    expected.locale_resolution_route(locale)
+                );
+            }
+            count += 1;
+        });
+        assert_eq!(count, 720);
+    }
+
+    #[test]
+    fn negotiate() {
+        let locales = Locales::try_from([("en", None), ("fr-CA", None), ("de-DE", None)]).unwrap();
+
+        assert_eq!(locales.negotiate(&langid!("en")), Some(&langid!("en")));
+        assert_eq!(
+            locales.negotiate(&langid!("fr-CA")),
+            Some(&langid!("fr-CA"))
+        );
+        assert_eq!(locales.negotiate(&langid!("fr")), Some(&langid!("fr-CA")));
+        assert_eq!(
+            locales.negotiate(&langid!("de-AT")),
+            Some(&langid!("de-DE"))
+        );
+        assert_eq!(locales.negotiate(&langid!("it")), None);
+
+        let locales = Locales::try_from([("fr-CA", None), ("fr", None)]).unwrap();
+        assert_eq!(locales.negotiate(&langid!("fr-CH")), Some(&langid!("fr")));
+        assert_eq!(
+            locales.negotiate(&langid!("fr-CA")),
+            Some(&langid!("fr-CA"))
+        );
+    }
+
+    #[test]
+    fn negotiate_preferences() {
+        let locales = Locales::try_from([("en", None), ("fr", None), ("de-DE", None)]).unwrap();
+
+        assert_eq!(
+            locales.negotiate_preferences(&[langid!("fr-CH"), langid!("fr"), langid!("en")]),
+            Some(&langid!("fr"))
+        );
+        assert_eq!(
+            locales.negotiate_preferences(&[langid!("it"), langid!("de-AT"), langid!("en")]),
+            Some(&langid!("de-DE"))
+        );
+        assert_eq!(
+            locales.negotiate_preferences(&[langid!("it"), langid!("es")]),
+            None
+        );
+        assert_eq!(locales.negotiate_preferences(&[]), None);
+    }
+
+    #[cfg(feature = "likely-subtags")]
+    #[test]
+    fn negotiate_likely_subtags() {
+        let locales = Locales::try_from([
+            ("zh-Hant", None),
+            ("zh-Hans", None),
+            ("sr-Latn", None),
+            ("sr-Cyrl", None),
+            ("pt-PT", None),
+            ("pt-BR", None),
+        ])
+        .unwrap();
+
+        let tests = [
+            ("zh", "zh-Hans"),
+            ("zh-CN", "zh-Hans"),
+            ("zh-TW", "zh-Hant"),
+            ("zh-HK", "zh-Hant"),
+            ("sr", "sr-Cyrl"),
+            ("sr-RS", "sr-Cyrl"),
+            ("sr-Latn-RS", "sr-Latn"),
+            ("pt", "pt-BR"),
+            ("pt-PT", "pt-PT"),
+            ("pt-AO", "pt-PT"),
+        ];
+        for (requested, expected) in tests {
+            assert_eq!(
+                locales.negotiate(&requested.parse().unwrap()),
+                Some(&expected.parse().unwrap()),
+                "requested: {}",
+                requested
+            );
+        }
+        assert_eq!(locales.negotiate(&langid!("en")), None);
+    }
 }
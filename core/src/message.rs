@@ -1,5 +1,6 @@
 use crate::l10n::{L10n, TranslateError};
 use crate::l10n_message::L10nMessage;
+use crate::UNEXPECTED_MESSAGE;
 use fluent_bundle::FluentArgs;
 use std::{borrow::Cow, fmt::Debug};
 use unic_langid::LanguageIdentifier;
@@ -10,6 +11,7 @@ pub struct Message<'l10n, 'args> {
     resource: &'args str,
     key: &'args str,
     args: Option<FluentArgs<'args>>,
+    locale: Option<LanguageIdentifier>,
 }
 
 impl<'l10n, 'args> Message<'l10n, 'args> {
@@ -24,8 +26,34 @@ impl<'l10n, 'args> Message<'l10n, 'args> {
             resource,
             key,
             args,
+            locale: None,
         }
     }
+
+    /// Pins the locale of the message, it is used instead of the locale given
+    /// to the translate methods.
+    pub fn with_locale(mut self, locale: LanguageIdentifier) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    pub fn locale(&self) -> Option<&LanguageIdentifier> {
+        self.locale.as_ref()
+    }
+
+    /// Translates with the pinned locale.
+    pub fn try_translate_pinned(&'args self) -> Result<Cow<'l10n, str>, TranslateError> {
+        let locale = self
+            .locale
+            .as_ref()
+            .ok_or(TranslateError::LocaleNotPinned)?;
+        self.try_translate(locale)
+    }
+
+    pub fn translate_pinned(&'args self) -> Cow<'l10n, str> {
+        self.try_translate_pinned()
+            .unwrap_or_else(|_| Cow::from(UNEXPECTED_MESSAGE))
+    }
 }
 
 impl<'l10n, 'args> L10nMessage<'args, 'l10n> for Message<'l10n, 'args> {
@@ -34,6 +62,7 @@ impl<'l10n, 'args> L10nMessage<'args, 'l10n> for Message<'l10n, 'args> {
         locale: &LanguageIdentifier,
         args: Option<&'args FluentArgs<'args>>,
     ) -> Result<Cow<'l10n, str>, TranslateError> {
+        let locale = self.locale.as_ref().unwrap_or(locale);
         match (self.args.as_ref(), args) {
             (Some(local_args), Some(overriding_args)) => {
                 // TODO There is probably something better to do performance wise,
@@ -58,31 +87,79 @@ impl<'l10n, 'args> L10nMessage<'args, 'l10n> for Message<'l10n, 'args> {
     }
 }
 
+// A message without arguments that can be stored in a `const`.
+#[derive(Debug, Clone, Copy)]
+pub struct StaticMessage {
+    l10n: fn() -> &'static L10n,
+    resource: &'static str,
+    key: &'static str,
+}
+
+impl StaticMessage {
+    pub const fn new(
+        l10n: fn() -> &'static L10n,
+        resource: &'static str,
+        key: &'static str,
+    ) -> Self {
+        Self {
+            l10n,
+            resource,
+            key,
+        }
+    }
+}
+
+impl<'s> L10nMessage<'s, 'static> for StaticMessage {
+    fn try_translate_with_args(
+        &'s self,
+        locale: &LanguageIdentifier,
+        args: Option<&'s FluentArgs<'s>>,
+    ) -> Result<Cow<'static, str>, TranslateError> {
+        (self.l10n)().try_translate_with_args(locale, self.resource, self.key, args)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::l10n::L10nBuilder;
+    use crate::l10n_message::{translate_map, Localized};
     use crate::locales::Locales;
     use fluent_bundle::FluentResource;
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use unic_langid::langid;
 
+    // Builds `builder` with the named resources `(locale, name, source)`.
+    fn build(mut builder: L10nBuilder, resources: &[(&str, &str, &str)]) -> L10n {
+        for (locale, name, source) in resources {
+            let resource = FluentResource::try_new(source.to_string()).unwrap();
+            builder
+                .add_named_resource(
+                    name,
+                    &PathBuf::default(),
+                    &locale.parse().unwrap(),
+                    resource,
+                )
+                .unwrap();
+        }
+        builder.build().unwrap()
+    }
+
     #[test]
     fn test() {
         let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
-        let mut builder = L10nBuilder::new(locales);
-
-        let en_home =
-            FluentResource::try_new("welcome = Welcome { $first-name }!".to_string()).unwrap();
-        builder.add_named_resource("home", &PathBuf::default(), &langid!("en"), en_home);
-
-        let fr_home = FluentResource::try_new(
-            "welcome = Bienvenue { $first-name } { $last-name }.".to_string(),
-        )
-        .unwrap();
-        builder.add_named_resource("home", &PathBuf::default(), &langid!("fr"), fr_home);
-
-        let l10n = builder.build().unwrap();
+        let l10n = build(
+            L10nBuilder::new(locales),
+            &[
+                ("en", "home", "welcome = Welcome { $first-name }!"),
+                (
+                    "fr",
+                    "home",
+                    "welcome = Bienvenue { $first-name } { $last-name }.",
+                ),
+            ],
+        );
 
         let mut args = FluentArgs::new();
         args.set("first-name", "Alan Mathison");
@@ -110,4 +187,174 @@ mod tests {
             "Welcome \u{2068}Alan\u{2069}!"
         );
     }
+
+    #[test]
+    fn translate_all_locales() {
+        // `en` is only the fallback of `en-GB`, it is not translated alone
+        let locales = Locales::try_from([("en-GB", Some("en")), ("fr", None)]).unwrap();
+        let l10n = build(
+            L10nBuilder::new(locales),
+            &[
+                ("en", "home", "title = Home"),
+                ("fr", "home", "title = Accueil"),
+            ],
+        );
+
+        let message = Message::new(&l10n, "home", "title", None);
+        assert_eq!(
+            message
+                .translate_all_locales(&l10n)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
+                (langid!("en-GB"), Cow::from("Home")),
+                (langid!("fr"), Cow::from("Accueil")),
+            ]
+        );
+    }
+
+    #[test]
+    fn translate_stripped() {
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = build(
+            L10nBuilder::new(locales),
+            &[(
+                "en",
+                "home",
+                "welcome = Welcome { $first-name }!\ntitle = Home",
+            )],
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("first-name", "Alan");
+        let message = Message::new(&l10n, "home", "welcome", Some(args));
+        assert_eq!(
+            message.translate(&langid!("en")),
+            "Welcome \u{2068}Alan\u{2069}!"
+        );
+        assert_eq!(message.translate_stripped(&langid!("en")), "Welcome Alan!");
+
+        let message = Message::new(&l10n, "home", "title", None);
+        assert_eq!(message.translate_stripped(&langid!("en")), "Home");
+        assert!(matches!(
+            crate::l10n_message::strip_isolation("Home"),
+            Cow::Borrowed("Home")
+        ));
+    }
+
+    #[test]
+    fn translate_or() {
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = build(L10nBuilder::new(locales), &[("en", "home", "title = Home")]);
+
+        let message = Message::new(&l10n, "home", "title", None);
+        assert_eq!(
+            message.translate_or(&langid!("en"), |_| unreachable!()),
+            "Home"
+        );
+
+        let message = Message::new(&l10n, "home", "subtitle", None);
+        let mut error = None;
+        let translation = message.translate_or(&langid!("en"), |err| {
+            error = Some(err);
+            Cow::from("subtitle")
+        });
+        assert_eq!(translation, "subtitle");
+        assert_eq!(
+            error,
+            Some(TranslateError::MessageIdNotExists {
+                id: "subtitle".to_string(),
+                locale: langid!("en"),
+            })
+        );
+        assert_eq!(message.translate(&langid!("en")), crate::UNEXPECTED_MESSAGE);
+    }
+
+    #[test]
+    fn with_locale() {
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = build(
+            L10nBuilder::new(locales),
+            &[
+                ("en", "home", "goodbye = Goodbye!"),
+                ("fr", "home", "goodbye = Au revoir !"),
+            ],
+        );
+
+        let message = Message::new(&l10n, "home", "goodbye", None);
+        assert_eq!(message.locale(), None);
+        assert_eq!(
+            message.try_translate_pinned(),
+            Err(TranslateError::LocaleNotPinned)
+        );
+
+        let message = message.with_locale(langid!("fr"));
+        assert_eq!(message.locale(), Some(&langid!("fr")));
+        assert_eq!(message.translate_pinned(), "Au revoir !");
+        assert_eq!(message.translate(&langid!("en")), "Au revoir !");
+    }
+
+    #[test]
+    fn translate_map_of_messages() {
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = build(
+            L10nBuilder::new(locales).set_use_isolating(false),
+            &[(
+                "en",
+                "home",
+                "welcome = Welcome { $first-name }!\ngoodbye = Goodbye!",
+            )],
+        );
+
+        let mut args = FluentArgs::new();
+        args.set("first-name", "Alan");
+        let messages = HashMap::from([
+            (
+                "title".to_string(),
+                Message::new(&l10n, "home", "welcome", Some(args)),
+            ),
+            (
+                "footer".to_string(),
+                Message::new(&l10n, "home", "goodbye", None),
+            ),
+        ]);
+
+        assert_eq!(
+            translate_map(&langid!("en"), &messages),
+            HashMap::from([
+                ("title".to_string(), "Welcome Alan!".to_string()),
+                ("footer".to_string(), "Goodbye!".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn localized() {
+        #[derive(serde::Serialize)]
+        struct Response<'a> {
+            status: Localized<'a, Message<'a, 'a>>,
+        }
+
+        let locales = Locales::try_from([("en", None), ("fr", None)]).unwrap();
+        let l10n = build(
+            L10nBuilder::new(locales),
+            &[
+                ("en", "status", "online = Online"),
+                ("fr", "status", "online = En ligne"),
+            ],
+        );
+
+        let online = Message::new(&l10n, "status", "online", None);
+        let locale = langid!("fr");
+        let response = Response {
+            status: Localized(&online, &locale),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"status":"En ligne"}"#
+        );
+
+        let missing = Message::new(&l10n, "status", "missing", None);
+        assert!(serde_json::to_string(&Localized(&missing, &locale)).is_err());
+    }
 }
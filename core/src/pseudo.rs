@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+const ASCII_UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ASCII_LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const ACCENTED_UPPERCASE: &str = "ȦƁƇḒḖƑƓĦĪĴĶĿḾȠǾƤɊŘŞŦŬṼẆẊẎẐ";
+const ACCENTED_LOWERCASE: &str = "ȧƀƈḓḗƒɠħīĵķŀḿƞǿƥɋřşŧŭṽẇẋẏẑ";
+
+/// Options of the pseudo-localization, see
+/// [`L10nBuilder::set_pseudo_localize`](crate::l10n::L10nBuilder::set_pseudo_localize).
+/// By default the letters are accented, padded by ~30% and bracketed with
+/// `⟦ ⟧`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PseudoOptions {
+    /// Replaces the ASCII letters with accented ones, e.g. `a` with `ȧ`.
+    pub accent: bool,
+    /// Length of the `!` padding relative to the translation, `0.3` adds
+    /// ~30%, `0.0` disables the padding.
+    pub expansion: f64,
+    /// Opening and closing text around each translation, e.g. `("⟦", "⟧")`.
+    pub brackets: (&'static str, &'static str),
+}
+
+impl Default for PseudoOptions {
+    fn default() -> Self {
+        Self {
+            accent: true,
+            expansion: 0.3,
+            brackets: ("⟦", "⟧"),
+        }
+    }
+}
+
+// Used as a fluent transform, so only text elements are accented and
+// placeables are preserved.
+pub(crate) fn transform(text: &str) -> Cow<'_, str> {
+    text.chars().map(accent).collect::<String>().into()
+}
+
+// Applied after formatting, the expansion helps catching layout issues and
+// the brackets truncated or concatenated strings.
+pub(crate) fn expand(text: &str, options: &PseudoOptions) -> String {
+    let (open, close) = options.brackets;
+    let padding = match options.expansion > 0.0 {
+        true => {
+            // Rounded first so that e.g. 10 * 0.3 doesn't end up above 3.
+            let expansion = text.chars().count() as f64 * options.expansion;
+            let expansion = ((expansion * 1e6).round() / 1e6).ceil() as usize;
+            format!(" {}", "!".repeat(expansion.max(1)))
+        }
+        false => String::new(),
+    };
+    format!("{}{}{}{}", open, text, padding, close)
+}
+
+fn accent(c: char) -> char {
+    let position = |letters: &str| letters.chars().position(|letter| letter == c);
+    if let Some(index) = position(ASCII_LOWERCASE) {
+        ACCENTED_LOWERCASE.chars().nth(index).unwrap_or(c)
+    } else if let Some(index) = position(ASCII_UPPERCASE) {
+        ACCENTED_UPPERCASE.chars().nth(index).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pseudolocalize() {
+        assert_eq!(transform("Account"), "Ȧƈƈǿŭƞŧ");
+        assert_eq!(transform("1 Hour!"), "1 Ħǿŭř!");
+        let options = PseudoOptions::default();
+        assert_eq!(expand("Ȧƈƈǿŭƞŧ", &options), "⟦Ȧƈƈǿŭƞŧ !!!⟧");
+        assert_eq!(expand("Ȧƈƈǿŭƞŧ Ȧƈƈǿŭ", &options), "⟦Ȧƈƈǿŭƞŧ Ȧƈƈǿŭ !!!!⟧");
+        assert_eq!(expand("Ȧƈƈǿŭƞŧ Ȧƈ", &options), "⟦Ȧƈƈǿŭƞŧ Ȧƈ !!!⟧");
+        assert_eq!(expand("", &options), "⟦ !⟧");
+    }
+
+    #[test]
+    fn expand_options() {
+        let options = PseudoOptions {
+            accent: true,
+            expansion: 0.5,
+            brackets: ("⟦", "⟧"),
+        };
+        assert_eq!(expand("Account", &options), "⟦Account !!!!⟧");
+
+        let options = PseudoOptions {
+            expansion: 0.0,
+            ..options
+        };
+        assert_eq!(expand("Account", &options), "⟦Account⟧");
+    }
+}
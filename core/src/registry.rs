@@ -0,0 +1,72 @@
+#[doc(hidden)]
+pub use inventory;
+use std::collections::HashSet;
+
+/// A message of a type deriving `L10nMessage`, every message of the derived
+/// types is registered when the `registry` feature is enabled.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RegisteredMessage {
+    /// The path of the type, i.e. `module_path!()::TypeName`.
+    pub type_name: &'static str,
+    pub resource: &'static str,
+    pub key: &'static str,
+}
+
+inventory::collect!(RegisteredMessage);
+
+/// A message used by `message!`, `const_message!` or `assert_translation!`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsedMessage {
+    pub resource: &'static str,
+    pub key: &'static str,
+}
+
+inventory::collect!(UsedMessage);
+
+/// The registered messages of all the linked crates, in no particular order.
+pub fn registered_messages() -> impl Iterator<Item = &'static RegisteredMessage> {
+    inventory::iter::<RegisteredMessage>.into_iter()
+}
+
+/// The `(resource, key)` of the messages used by the macros and the derived
+/// types of all the linked crates, see [`L10n::report_unused`].
+///
+/// [`L10n::report_unused`]: crate::l10n::L10n::report_unused
+pub fn used_messages() -> HashSet<(String, String)> {
+    inventory::iter::<UsedMessage>
+        .into_iter()
+        .map(|message| (message.resource, message.key))
+        .chain(registered_messages().map(|message| (message.resource, message.key)))
+        .map(|(resource, key)| (resource.to_string(), key.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    inventory::submit! {
+        RegisteredMessage { type_name: "tests::Status", resource: "app", key: "status" }
+    }
+
+    inventory::submit! {
+        UsedMessage { resource: "app", key: "title" }
+    }
+
+    #[test]
+    fn registered_messages() {
+        assert!(super::registered_messages().any(|message| *message
+            == RegisteredMessage {
+                type_name: "tests::Status",
+                resource: "app",
+                key: "status",
+            }));
+    }
+
+    #[test]
+    fn used_messages() {
+        let used_messages = super::used_messages();
+        assert!(used_messages.contains(&("app".to_string(), "status".to_string())));
+        assert!(used_messages.contains(&("app".to_string(), "title".to_string())));
+    }
+}
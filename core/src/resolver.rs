@@ -0,0 +1,57 @@
+use fluent_bundle::{bundle::FluentBundle, FluentResource};
+use fluent_syntax::ast::Entry;
+use intl_memoizer::concurrent::IntlLangMemoizer;
+use std::cell::Cell;
+use std::sync::Arc;
+use unic_langid::LanguageIdentifier;
+
+// Nested `format` calls, a bundle function formatting a message calling it
+// would never end.
+const MAX_DEPTH: usize = 16;
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Read access to the messages and terms of the bundle a function is called
+/// from, see [`L10nBuilder::add_bundle_function`](crate::l10n::L10nBuilder::add_bundle_function).
+pub struct BundleResolver {
+    // A second bundle sharing the resources, the bundle cannot be borrowed by
+    // its own functions.
+    bundle: FluentBundle<Arc<FluentResource>, IntlLangMemoizer>,
+}
+
+impl BundleResolver {
+    pub(crate) fn new(bundle: FluentBundle<Arc<FluentResource>, IntlLangMemoizer>) -> Self {
+        Self { bundle }
+    }
+
+    pub fn locale(&self) -> &LanguageIdentifier {
+        &self.bundle.locales[0]
+    }
+
+    /// Formats without arguments a term (`-brand`), a message (`title`) or a
+    /// message attribute (`status.online`), `None` if it does not exist or
+    /// cannot be resolved or references itself through a bundle function.
+    pub fn format(&self, reference: &str) -> Option<String> {
+        let depth = DEPTH.with(Cell::get);
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+        DEPTH.with(|cell| cell.set(depth + 1));
+        let value = self.format_reference(reference);
+        DEPTH.with(|cell| cell.set(depth));
+        value
+    }
+
+    fn format_reference(&self, reference: &str) -> Option<String> {
+        let resource = FluentResource::try_new(format!("resolved = {{ {} }}", reference)).ok()?;
+        let pattern = match resource.entries().next()? {
+            Entry::Message(message) => message.value.as_ref()?,
+            _ => return None,
+        };
+        let mut errors = vec![];
+        let value = self.bundle.format_pattern(pattern, None, &mut errors);
+        errors.is_empty().then(|| value.into_owned())
+    }
+}
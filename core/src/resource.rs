@@ -1,27 +1,58 @@
-use crate::l10n::TranslateError;
-use fluent_bundle::FluentArgs;
+use crate::l10n::{FormatError, MessageInfo, TranslateError};
 use fluent_bundle::{bundle::FluentBundle, FluentResource};
-use fluent_syntax::ast::{Expression, InlineExpression, Pattern, PatternElement};
+use fluent_bundle::{FluentArgs, FluentError};
+use fluent_syntax::ast::{
+    Entry, Expression, InlineExpression, Pattern, PatternElement, VariantKey,
+};
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use std::borrow::{Borrow, Cow};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use unic_langid::LanguageIdentifier;
 
+// Functions formatting their first positional argument as a number.
+const NUMERIC_FUNCTIONS: [&str; 3] = ["NUMBER", "ORDINAL", "RAW_NUMBER"];
+
 pub struct L10nResource<R> {
+    name: String,
     bundles: HashMap<LanguageIdentifier, FluentBundle<R, IntlLangMemoizer>>,
+    // Keys of the messages and attributes defined by the named fluent resources
+    keys: BTreeSet<String>,
+    // Translations of the messages without placeables, by locale and key
+    literals: HashMap<LanguageIdentifier, HashMap<String, String>>,
+    // Translations without arguments cached when building, by locale and key
+    cached: HashMap<LanguageIdentifier, HashMap<String, String>>,
 }
 
 impl<R> L10nResource<R> {
-    pub fn new() -> Self {
+    pub fn new(name: &str) -> Self {
         Self {
+            name: name.to_string(),
             bundles: HashMap::new(),
+            keys: BTreeSet::new(),
+            literals: HashMap::new(),
+            cached: HashMap::new(),
         }
     }
-}
 
-impl<R> Default for L10nResource<R> {
-    fn default() -> Self {
-        Self::new()
+    // The fluent errors with the message they occurred in.
+    fn format_errors(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        errors: Vec<FluentError>,
+    ) -> TranslateError {
+        TranslateError::FormatErrors(
+            errors
+                .into_iter()
+                .map(|source| FormatError {
+                    resource: self.name.clone(),
+                    key: key.to_string(),
+                    locale: locale.to_owned(),
+                    source,
+                })
+                .collect(),
+        )
     }
 }
 
@@ -37,6 +68,146 @@ where
         self.bundles.insert(lang, bundle);
     }
 
+    // Also forgets the literals and the cached translations, the keys of a
+    // reloaded resource can change.
+    pub fn clear_keys(&mut self) {
+        self.keys.clear();
+        self.literals.clear();
+        self.cached.clear();
+    }
+
+    pub fn add_keys(&mut self, resource: &FluentResource) {
+        for entry in resource.entries() {
+            if let Entry::Message(message) = entry {
+                if message.value.is_some() {
+                    self.keys.insert(message.id.name.to_string());
+                }
+                for attribute in &message.attributes {
+                    self.keys
+                        .insert(format!("{}.{}", message.id.name, attribute.id.name));
+                }
+            }
+        }
+    }
+
+    // Resolves once the messages made only of text so `translate_raw` can
+    // return them without formatting, to call once the bundles are added.
+    pub fn add_literals(&mut self, finish: impl Fn(&LanguageIdentifier, String) -> String) {
+        for (locale, bundle) in &self.bundles {
+            for key in &self.keys {
+                let pattern = match bundle.get_pattern_from_key(key) {
+                    Ok(pattern) => pattern,
+                    Err(_) => continue,
+                };
+                if pattern
+                    .elements
+                    .iter()
+                    .all(|element| matches!(element, PatternElement::TextElement { .. }))
+                {
+                    let mut errors = vec![];
+                    let translation = bundle.format_pattern(pattern, None, &mut errors);
+                    if errors.is_empty() {
+                        self.literals
+                            .entry(locale.to_owned())
+                            .or_default()
+                            .insert(key.to_owned(), finish(locale, translation.into_owned()));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn add_cached(&mut self, locale: LanguageIdentifier, key: String, translation: String) {
+        self.cached
+            .entry(locale)
+            .or_default()
+            .insert(key, translation);
+    }
+
+    pub fn cached(&self, locale: &LanguageIdentifier, key: &str) -> Option<&str> {
+        self.cached.get(locale)?.get(key).map(String::as_str)
+    }
+
+    pub fn translate_raw(&self, locale: &LanguageIdentifier, key: &str) -> Option<&str> {
+        self.literals.get(locale)?.get(key).map(String::as_str)
+    }
+
+    // Variables referenced by each message and attribute in any locale.
+    pub fn defined_variables(&self) -> BTreeMap<&str, HashSet<&str>> {
+        self.keys
+            .iter()
+            .map(|key| {
+                let variables = self
+                    .bundles
+                    .values()
+                    .filter_map(|bundle| bundle.parse_variables(key).ok())
+                    .flatten()
+                    .collect();
+                (key.as_str(), variables)
+            })
+            .collect()
+    }
+
+    pub fn locales(&self) -> Vec<&LanguageIdentifier> {
+        let mut locales: Vec<_> = self.bundles.keys().collect();
+        locales.sort_by_key(|locale| locale.to_string());
+        locales
+    }
+
+    pub fn messages(&self) -> Vec<MessageInfo> {
+        let mut messages: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for key in &self.keys {
+            match key.split_once('.') {
+                Some((id, attribute)) => {
+                    messages
+                        .entry(id)
+                        .or_default()
+                        .insert(attribute.to_string());
+                }
+                None => {
+                    messages.entry(key).or_default();
+                }
+            }
+        }
+
+        let locales = self.locales();
+        messages
+            .into_iter()
+            .map(|(id, attributes)| MessageInfo {
+                id: id.to_string(),
+                attributes,
+                locales: locales
+                    .iter()
+                    .filter(|locale| {
+                        self.bundles[**locale]
+                            .get_message(id)
+                            .and_then(|message| message.value())
+                            .is_some()
+                    })
+                    .map(|locale| (*locale).to_owned())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    // Keys of the messages and attributes defined by the named resources.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    // Keys referenced by the messages of any locale, e.g. `{ brand.short }`.
+    pub fn referenced_keys(&self) -> HashSet<String> {
+        let mut referenced_keys = HashSet::new();
+        for bundle in self.bundles.values() {
+            for key in &self.keys {
+                if let Ok(pattern) = bundle.get_pattern_from_key(key) {
+                    pattern_message_references(pattern, &mut referenced_keys);
+                }
+            }
+        }
+        referenced_keys
+    }
+
     pub fn translate<'a, 'args>(
         &'a self,
         locale: &LanguageIdentifier,
@@ -90,11 +261,35 @@ where
         let mut errors = vec![];
         let translation = bundle.format_pattern(pattern, args, &mut errors);
         if !errors.is_empty() {
-            return Err(TranslateError::FormatErrors(errors));
+            return Err(self.format_errors(locale, key, errors));
         }
         Ok(translation)
     }
 
+    pub fn write<'a, W: fmt::Write>(
+        &'a self,
+        out: &mut W,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&'a FluentArgs>,
+    ) -> Result<(), TranslateError> {
+        let bundle =
+            self.bundles
+                .get(locale)
+                .ok_or_else(|| TranslateError::LocaleNotSupported {
+                    locale: locale.to_owned(),
+                })?;
+
+        let pattern = bundle.get_pattern_from_key(key)?;
+
+        let mut errors = vec![];
+        bundle.write_pattern(out, pattern, args, &mut errors)?;
+        if !errors.is_empty() {
+            return Err(self.format_errors(locale, key, errors));
+        }
+        Ok(())
+    }
+
     pub fn required_variables(&self, key: &str) -> Result<HashSet<&str>, TranslateError> {
         let mut variables = HashSet::new();
 
@@ -143,6 +338,62 @@ where
 
         Ok(variables)
     }
+
+    pub fn locales_required_variables(
+        &self,
+        key: &str,
+    ) -> Result<HashMap<&LanguageIdentifier, HashSet<&str>>, TranslateError> {
+        let mut bundles: Vec<_> = self.bundles.iter().collect();
+        bundles.sort_by_key(|(locale, _)| locale.to_string());
+        bundles
+            .into_iter()
+            .map(|(locale, bundle)| Ok((locale, bundle.parse_variables(key)?)))
+            .collect()
+    }
+
+    // Variables formatted as a number (e.g. `NUMBER($count)`) in any locale.
+    pub fn numeric_variables(&self, key: &str) -> Result<HashSet<&str>, TranslateError> {
+        let mut variables = HashSet::new();
+        for bundle in self.bundles.values() {
+            let pattern = bundle.get_pattern_from_key(key)?;
+            pattern_numeric_variables(bundle, pattern, &mut variables)?;
+        }
+        Ok(variables)
+    }
+
+    pub fn selector_variants(
+        &self,
+        key: &str,
+        variable: &str,
+    ) -> Result<Vec<String>, TranslateError> {
+        let mut variants = HashSet::new();
+        for bundle in self.bundles.values() {
+            let pattern = bundle.get_pattern_from_key(key)?;
+            bundle.parse_pattern_selector_variants(pattern, variable, &mut variants)?;
+        }
+
+        let mut variants: Vec<_> = variants.into_iter().map(str::to_string).collect();
+        variants.sort();
+        Ok(variants)
+    }
+
+    pub fn locale_selector_variants(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        variable: &str,
+    ) -> Result<HashSet<&str>, TranslateError> {
+        let bundle =
+            self.bundles
+                .get(locale)
+                .ok_or_else(|| TranslateError::LocaleNotSupported {
+                    locale: locale.to_owned(),
+                })?;
+        let mut variants = HashSet::new();
+        let pattern = bundle.get_pattern_from_key(key)?;
+        bundle.parse_pattern_selector_variants(pattern, variable, &mut variants)?;
+        Ok(variants)
+    }
 }
 
 trait ParseVariables {
@@ -175,6 +426,27 @@ trait ParseVariables {
         inline_expression: &InlineExpression<&'a str>,
         variables: &mut HashSet<&'a str>,
     ) -> Result<(), TranslateError>;
+
+    fn parse_pattern_selector_variants<'a>(
+        &'a self,
+        pattern: &Pattern<&'a str>,
+        variable: &str,
+        variants: &mut HashSet<&'a str>,
+    ) -> Result<(), TranslateError>;
+
+    fn parse_expression_selector_variants<'a>(
+        &'a self,
+        expression: &Expression<&'a str>,
+        variable: &str,
+        variants: &mut HashSet<&'a str>,
+    ) -> Result<(), TranslateError>;
+
+    fn parse_inline_expression_selector_variants<'a>(
+        &'a self,
+        inline_expression: &InlineExpression<&'a str>,
+        variable: &str,
+        variants: &mut HashSet<&'a str>,
+    ) -> Result<(), TranslateError>;
 }
 
 impl<R, M> ParseVariables for FluentBundle<R, M>
@@ -342,12 +614,198 @@ where
 
         Ok(())
     }
+
+    fn parse_pattern_selector_variants<'a>(
+        &'a self,
+        pattern: &Pattern<&'a str>,
+        variable: &str,
+        variants: &mut HashSet<&'a str>,
+    ) -> Result<(), TranslateError> {
+        for element in &pattern.elements {
+            if let PatternElement::Placeable { expression } = element {
+                self.parse_expression_selector_variants(expression, variable, variants)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_expression_selector_variants<'a>(
+        &'a self,
+        expression: &Expression<&'a str>,
+        variable: &str,
+        variants: &mut HashSet<&'a str>,
+    ) -> Result<(), TranslateError> {
+        match expression {
+            Expression::Select {
+                selector,
+                variants: select_variants,
+            } => {
+                if let InlineExpression::VariableReference { id } = selector {
+                    if id.name == variable {
+                        variants.extend(select_variants.iter().map(|variant| match variant.key {
+                            VariantKey::Identifier { name } => name,
+                            VariantKey::NumberLiteral { value } => value,
+                        }));
+                    }
+                }
+                for variant in select_variants {
+                    self.parse_pattern_selector_variants(&variant.value, variable, variants)?;
+                }
+            }
+            Expression::Inline(inline_expression) => {
+                self.parse_inline_expression_selector_variants(
+                    inline_expression,
+                    variable,
+                    variants,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_inline_expression_selector_variants<'a>(
+        &'a self,
+        inline_expression: &InlineExpression<&'a str>,
+        variable: &str,
+        variants: &mut HashSet<&'a str>,
+    ) -> Result<(), TranslateError> {
+        match inline_expression {
+            InlineExpression::MessageReference { id, attribute } => {
+                let pattern =
+                    self.get_pattern(id.name, attribute.as_ref().map(|attribute| attribute.name))?;
+                self.parse_pattern_selector_variants(pattern, variable, variants)?;
+            }
+            InlineExpression::Placeable { expression } => {
+                self.parse_expression_selector_variants(expression, variable, variants)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn pattern_message_references(pattern: &Pattern<&str>, keys: &mut HashSet<String>) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            expression_message_references(expression, keys);
+        }
+    }
+}
+
+fn expression_message_references(expression: &Expression<&str>, keys: &mut HashSet<String>) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            inline_expression_message_references(selector, keys);
+            for variant in variants {
+                pattern_message_references(&variant.value, keys);
+            }
+        }
+        Expression::Inline(inline_expression) => {
+            inline_expression_message_references(inline_expression, keys);
+        }
+    }
+}
+
+fn inline_expression_message_references(
+    inline_expression: &InlineExpression<&str>,
+    keys: &mut HashSet<String>,
+) {
+    match inline_expression {
+        InlineExpression::MessageReference { id, attribute } => {
+            keys.insert(match attribute {
+                Some(attribute) => format!("{}.{}", id.name, attribute.name),
+                None => id.name.to_string(),
+            });
+        }
+        InlineExpression::FunctionReference { arguments, .. }
+        | InlineExpression::TermReference {
+            arguments: Some(arguments),
+            ..
+        } => {
+            for argument in &arguments.positional {
+                inline_expression_message_references(argument, keys);
+            }
+            for argument in &arguments.named {
+                inline_expression_message_references(&argument.value, keys);
+            }
+        }
+        InlineExpression::Placeable { expression } => {
+            expression_message_references(expression, keys);
+        }
+        _ => {}
+    }
+}
+
+fn pattern_numeric_variables<'a, B: ParseVariables>(
+    bundle: &'a B,
+    pattern: &Pattern<&'a str>,
+    variables: &mut HashSet<&'a str>,
+) -> Result<(), TranslateError> {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            expression_numeric_variables(bundle, expression, variables)?;
+        }
+    }
+    Ok(())
+}
+
+fn expression_numeric_variables<'a, B: ParseVariables>(
+    bundle: &'a B,
+    expression: &Expression<&'a str>,
+    variables: &mut HashSet<&'a str>,
+) -> Result<(), TranslateError> {
+    match expression {
+        Expression::Select { selector, variants } => {
+            inline_expression_numeric_variables(bundle, selector, variables)?;
+            for variant in variants {
+                pattern_numeric_variables(bundle, &variant.value, variables)?;
+            }
+        }
+        Expression::Inline(inline_expression) => {
+            inline_expression_numeric_variables(bundle, inline_expression, variables)?;
+        }
+    }
+    Ok(())
+}
+
+fn inline_expression_numeric_variables<'a, B: ParseVariables>(
+    bundle: &'a B,
+    inline_expression: &InlineExpression<&'a str>,
+    variables: &mut HashSet<&'a str>,
+) -> Result<(), TranslateError> {
+    match inline_expression {
+        InlineExpression::FunctionReference { id, arguments } => {
+            if NUMERIC_FUNCTIONS.contains(&id.name) {
+                if let Some(InlineExpression::VariableReference { id }) =
+                    arguments.positional.first()
+                {
+                    variables.insert(id.name);
+                }
+            }
+            for positional_argument in &arguments.positional {
+                inline_expression_numeric_variables(bundle, positional_argument, variables)?;
+            }
+        }
+        InlineExpression::MessageReference { id, attribute } => {
+            let pattern =
+                bundle.get_pattern(id.name, attribute.as_ref().map(|attribute| attribute.name))?;
+            pattern_numeric_variables(bundle, pattern, variables)?;
+        }
+        InlineExpression::Placeable { expression } => {
+            expression_numeric_variables(bundle, expression, variables)?;
+        }
+        _ => {}
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use unic_langid::langid;
 
     #[test]
     fn required_variables() {
@@ -454,11 +912,120 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn locales_required_variables() {
+        let source_en = indoc! {r#"
+            hello = Hello { $first_name }
+        "#};
+        let source_fr = indoc! {r#"
+            hello = Bonjour { $first_name } { $last_name }
+        "#};
+
+        let resource = utils::build_resource(vec![
+            ("en", source_en.to_string()),
+            ("fr", source_fr.to_string()),
+        ]);
+        let actual = resource.locales_required_variables("hello").unwrap();
+        let (en, fr) = (langid!("en"), langid!("fr"));
+        let expected = HashMap::from([
+            (&en, HashSet::from(["first_name"])),
+            (&fr, HashSet::from(["first_name", "last_name"])),
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn selector_variants() {
+        let source_en = indoc! {r#"
+            busy = { $gender ->
+                [male] He is busy
+                [female] She is busy
+               *[other] Busy
+            } for { $hours ->
+                [1] one hour
+               *[other] { $hours } hours
+            }
+        "#};
+        let source_fr = indoc! {r#"
+            busy = { $gender ->
+                [female] Occupée
+               *[other] Occupé
+            } { status }
+            status = { $gender ->
+                [non-binary] (non binaire)
+               *[other] {""}
+            }
+        "#};
+
+        let resource = utils::build_resource(vec![
+            ("en", source_en.to_string()),
+            ("fr", source_fr.to_string()),
+        ]);
+        let actual = resource.selector_variants("busy", "gender").unwrap();
+        assert_eq!(actual, vec!["female", "male", "non-binary", "other"]);
+        let actual = resource.selector_variants("busy", "hours").unwrap();
+        assert_eq!(actual, vec!["1", "other"]);
+        let actual = resource.selector_variants("busy", "unknown").unwrap();
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn numeric_variables() {
+        let source_en = indoc! {r#"
+            total = { $count ->
+                [one] { NUMBER($count) } item for { $name }
+               *[other] { NUMBER($count, minimumFractionDigits: 2) } items
+            }
+            place = { place-number }
+            place-number = { ORDINAL($place) }
+        "#};
+        let source_fr = indoc! {r#"
+            total = { RAW_NUMBER($total) } { $count }
+            place = { $place }
+            place-number = { $place }
+        "#};
+        let resource = utils::build_resource(vec![
+            ("en", source_en.to_string()),
+            ("fr", source_fr.to_string()),
+        ]);
+        assert_eq!(
+            resource.numeric_variables("total").unwrap(),
+            HashSet::from(["count", "total"])
+        );
+        assert_eq!(
+            resource.numeric_variables("place").unwrap(),
+            HashSet::from(["place"])
+        );
+    }
+
+    #[test]
+    fn dotted_key() {
+        // Fluent identifiers cannot contain a dot, so the first dot of a key
+        // always separates the message id from the attribute.
+        assert!(FluentResource::try_new("app.title = Title".to_string()).is_err());
+
+        let source = indoc! {r#"
+            app = App
+                .title = Title
+        "#};
+        let resource = utils::build_resource(vec![("en", source.to_string())]);
+        let en = langid!("en");
+        assert_eq!(resource.translate(&en, "app.title", None).unwrap(), "Title");
+        assert_eq!(
+            resource.translate(&en, "app.title.long", None).unwrap_err(),
+            TranslateError::MessageAttributeNotExists {
+                attribute: "title.long".to_string(),
+                id: "app".to_string(),
+                locale: en,
+            }
+        );
+    }
+
     mod utils {
         use super::*;
 
         pub fn build_resource(sources: Vec<(&str, String)>) -> L10nResource<FluentResource> {
-            let mut resource = L10nResource::new();
+            let mut resource = L10nResource::new("app");
 
             for (lang, source) in sources {
                 let lang_id = lang.parse().unwrap();
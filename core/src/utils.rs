@@ -24,6 +24,37 @@ pub fn for_locales(values: &[LanguageIdentifier]) -> String {
     )
 }
 
+// Whether `name` matches `pattern` where `*` matches any sequence of characters
+// and `?` any single character.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<_> = pattern.chars().collect();
+    let name: Vec<_> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and of the name character it matched up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 pub fn grammar_number<T, S: ToString>(values: &[T], singular: S, plural: S) -> S {
     if values.len() == 1 {
         singular
@@ -31,3 +62,20 @@ pub fn grammar_number<T, S: ToString>(values: &[T], singular: S, plural: S) -> S
         plural
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_patterns() {
+        assert!(matches_pattern("*.wip.ftl", "home.wip.ftl"));
+        assert!(!matches_pattern("*.wip.ftl", "home.ftl"));
+        assert!(matches_pattern("drafts", "drafts"));
+        assert!(!matches_pattern("drafts", "drafts-old"));
+        assert!(matches_pattern("draft?", "drafts"));
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("a*b*c", "aXbYbZc"));
+        assert!(!matches_pattern("a*b*c", "aXbYbZ"));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::l10n::{BuildErrors, L10n, L10nBuilder, ParserError, Rebuilder};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard, Weak};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// A [`L10n`] whose fluent files are reloaded when they change, see
+/// [`L10nBuilder::watch`]. Only the translations read through
+/// [`WatchedL10n::read`] are reloaded, not the `L10N` static of `init!` nor
+/// the code generated by the macros.
+pub struct WatchedL10n {
+    l10n: Arc<RwLock<L10n>>,
+}
+
+impl WatchedL10n {
+    /// The translations are not reloaded while the guard is held.
+    pub fn read(&self) -> RwLockReadGuard<'_, L10n> {
+        self.l10n.read().unwrap()
+    }
+}
+
+impl L10nBuilder {
+    /// Builds and checks every `interval` if the fluent files changed, a
+    /// changed file is reloaded with [`L10n::reload_file`] and an added or
+    /// removed file builds the parsed directory again with the same options.
+    /// On error the translations are kept as is and `on_error` is called
+    /// with the file, or the directory, that could not be loaded. The
+    /// watching thread stops when the [`WatchedL10n`] is dropped. Only
+    /// available in debug builds, the files are polled for development.
+    pub fn watch(
+        self,
+        interval: Duration,
+        on_error: fn(&Path, &ParserError),
+    ) -> Result<WatchedL10n, BuildErrors> {
+        let rebuilder = self.rebuilder();
+        let l10n = self.build()?;
+        let mut watcher = Watcher {
+            rebuilder,
+            on_error,
+            states: HashMap::new(),
+        };
+        watcher.states = watcher.file_states(&l10n);
+        let l10n = Arc::new(RwLock::new(l10n));
+        let weak_l10n = Arc::downgrade(&l10n);
+        thread::spawn(move || watcher.run(weak_l10n, interval));
+        Ok(WatchedL10n { l10n })
+    }
+}
+
+// The modification time is not precise enough on every file system, the
+// length catches most of the quick edits.
+type FileState = (SystemTime, u64);
+
+fn file_state(path: &Path) -> Option<FileState> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+struct Watcher {
+    // The parsed directory and how to build it again, `None` when the
+    // resources were not parsed from a directory
+    rebuilder: Option<(PathBuf, Rebuilder)>,
+    on_error: fn(&Path, &ParserError),
+    // The fluent files of the directory, or the loaded ones without directory
+    states: HashMap<PathBuf, Option<FileState>>,
+}
+
+impl Watcher {
+    fn run(mut self, l10n: Weak<RwLock<L10n>>, interval: Duration) {
+        loop {
+            thread::sleep(interval);
+            let l10n = match l10n.upgrade() {
+                Some(l10n) => l10n,
+                None => return,
+            };
+            self.check(&l10n);
+        }
+    }
+
+    fn check(&mut self, l10n: &RwLock<L10n>) {
+        let (states, loaded) = {
+            let l10n = l10n.read().unwrap();
+            let loaded: HashSet<PathBuf> = l10n.file_paths().map(Path::to_path_buf).collect();
+            (self.file_states(&l10n), loaded)
+        };
+        let changed: Vec<_> = states
+            .iter()
+            .filter(|(path, state)| self.states.get(*path) != Some(*state))
+            .map(|(path, _)| path.to_owned())
+            .collect();
+        let removed = self.states.keys().any(|path| !states.contains_key(path));
+        self.states = states;
+
+        match &self.rebuilder {
+            // A file not loaded yet, e.g. a new one, needs the whole directory
+            Some((root, rebuild))
+                if removed || changed.iter().any(|path| !loaded.contains(path)) =>
+            {
+                match rebuild() {
+                    Ok(rebuilt) => *l10n.write().unwrap() = rebuilt,
+                    Err(err) => (self.on_error)(root, &err),
+                }
+            }
+            _ => {
+                for path in changed {
+                    if let Err(err) = l10n.write().unwrap().reload_file(&path) {
+                        (self.on_error)(&path, &err);
+                    }
+                }
+            }
+        }
+    }
+
+    fn file_states(&self, l10n: &L10n) -> HashMap<PathBuf, Option<FileState>> {
+        let paths = match &self.rebuilder {
+            Some((root, _)) => {
+                let mut paths = vec![];
+                fluent_files(root, &mut paths);
+                paths
+            }
+            None => l10n.file_paths().map(Path::to_path_buf).collect(),
+        };
+        paths
+            .into_iter()
+            .map(|path| {
+                let state = file_state(&path);
+                (path, state)
+            })
+            .collect()
+    }
+}
+
+// Same paths as the ones recorded when parsing, ignored files included.
+fn fluent_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            fluent_files(&path, paths);
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension == "ftl")
+        {
+            paths.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::l10n::{L10nBuilder, ParserError};
+    use crate::locales::Locales;
+    use indoc::indoc;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+    use unic_langid::langid;
+
+    static ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_error(_: &Path, _: &ParserError) {
+        ERRORS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // Renamed into place, the watcher must not read a partially written file.
+    fn write(path: &Path, contents: &str) {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents).unwrap();
+        std::fs::rename(tmp_path, path).unwrap();
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        let start = Instant::now();
+        while !condition() && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn watch() {
+        let temp_dir = macro_files::create_temp!({
+            "en": {
+                "app.ftl": indoc! {r#"
+                    tagline = Have fun
+                "#}
+            },
+        })
+        .unwrap();
+
+        let locales = Locales::try_from([("en", None)]).unwrap();
+        let l10n = L10nBuilder::parse(temp_dir.path(), Some(locales))
+            .unwrap()
+            .check_terms(true)
+            .watch(Duration::from_millis(10), count_error)
+            .unwrap();
+        let translate = |resource, key| {
+            l10n.read()
+                .try_translate_with_args(&langid!("en"), resource, key, None)
+                .map(|translation| translation.into_owned())
+        };
+        assert_eq!(translate("app", "tagline").unwrap(), "Have fun");
+
+        write(
+            &temp_dir.path().join("en/app.ftl"),
+            "tagline = Have a lot of fun\n",
+        );
+        wait_until(|| translate("app", "tagline").unwrap() != "Have fun");
+        assert_eq!(translate("app", "tagline").unwrap(), "Have a lot of fun");
+
+        write(
+            &temp_dir.path().join("en/settings.ftl"),
+            "title = Settings\n",
+        );
+        wait_until(|| translate("settings", "title").is_ok());
+        assert_eq!(translate("settings", "title").unwrap(), "Settings");
+
+        write(
+            &temp_dir.path().join("en/app.ftl"),
+            "tagline = { -missing }\n",
+        );
+        wait_until(|| ERRORS.load(Ordering::SeqCst) > 0);
+        assert_eq!(ERRORS.load(Ordering::SeqCst), 1);
+        assert_eq!(translate("app", "tagline").unwrap(), "Have a lot of fun");
+    }
+}
@@ -1,6 +1,6 @@
 use l10n::fluent_bundle::{FluentArgs, FluentValue};
 use l10n::unic_langid::langid;
-use l10n::L10nMessage;
+use l10n::{L10nMessage, L10nMessageKeys};
 use std::borrow::Cow;
 
 fn l10n_transform(s: &str) -> Cow<str> {
@@ -31,10 +31,15 @@ fn main() {
         time: Time::minutes(30),
     };
     assert_eq!(status.translate(&lang), "OcCuPéE🚫 (Meeting) [30m🕒]");
+
+    // Checks the keys against the translations loaded at runtime
+    let errors = L10N.check_message_keys(Status::<Time>::message_keys());
+    assert!(errors.is_empty(), "{:?}", errors);
 }
 
 #[derive(L10nMessage)]
 #[l10n_message('a, "settings", "status")]
+#[l10n_keys]
 pub enum Status<'a, T>
 where
     &'a T: 'a + Into<FluentValue<'a>>,
@@ -57,6 +62,7 @@ where
 
 #[derive(L10nMessage)]
 #[l10n_message("settings", "status.online")]
+#[l10n_keys]
 pub struct Other;
 
 pub enum Gender {
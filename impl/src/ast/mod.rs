@@ -1,5 +0,0 @@
-mod args;
-mod key;
-
-pub use args::{Argument, MessageArgs};
-pub use key::MessageKey;
@@ -1,10 +1,7 @@
-use crate::ast::MessageArgs;
-use proc_macro2::Span;
-use syn::parse::ParseStream;
+use l10n_core::ast::{parse_l10n_attribute, L10nAttribute};
 use syn::spanned::Spanned;
 use syn::{
-    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, Ident, Index, Lifetime,
-    LitStr, Member, Result, Token, Type,
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, Index, Member, Result, Type,
 };
 
 pub enum Input<'a> {
@@ -16,12 +13,16 @@ pub struct Struct<'a> {
     pub derive_input: &'a DeriveInput,
     pub fields: Vec<Field<'a>>,
     pub l10n_attribute: L10nAttribute<'a>,
+    pub display: Option<&'a Attribute>,
+    pub keys: Option<&'a Attribute>,
 }
 
 pub struct Enum<'a> {
     pub derive_input: &'a DeriveInput,
     pub variants: Vec<Variant<'a>>,
     pub l10n_attribute: L10nAttribute<'a>,
+    pub display: Option<&'a Attribute>,
+    pub keys: Option<&'a Attribute>,
 }
 
 pub struct Variant<'a> {
@@ -36,16 +37,7 @@ pub struct Field<'a> {
     pub member: Member,
     pub ty: &'a Type,
     pub from: Option<&'a Attribute>,
-}
-
-#[derive(Default)]
-pub struct L10nAttribute<'a> {
-    pub attribute: Option<&'a Attribute>,
-    pub transparent: Option<Span>,
-    pub self_lifetime: Option<Lifetime>,
-    pub first_literal: Option<LitStr>,
-    pub second_literal: Option<LitStr>,
-    pub arguments: MessageArgs,
+    pub locale: Option<&'a Attribute>,
 }
 
 impl<'a> Input<'a> {
@@ -56,6 +48,20 @@ impl<'a> Input<'a> {
             Data::Union(_) => Err(Error::new_spanned(derive_input, "union is not supported")),
         }
     }
+
+    pub fn display(&self) -> Option<&'a Attribute> {
+        match self {
+            Input::Struct(input) => input.display,
+            Input::Enum(input) => input.display,
+        }
+    }
+
+    pub fn keys(&self) -> Option<&'a Attribute> {
+        match self {
+            Input::Struct(input) => input.keys,
+            Input::Enum(input) => input.keys,
+        }
+    }
 }
 
 impl<'a> Struct<'a> {
@@ -64,10 +70,14 @@ impl<'a> Struct<'a> {
             Error::new_spanned(derive_input, r#"missing #[l10n_message("...")] attribute"#)
         })?;
         let fields = Field::multiple_from_syn(&data.fields)?;
+        let display = parse_flag_attribute(&derive_input.attrs, "l10n_display")?;
+        let keys = parse_flag_attribute(&derive_input.attrs, "l10n_keys")?;
         Ok(Struct {
             derive_input,
             fields,
             l10n_attribute,
+            display,
+            keys,
         })
     }
 }
@@ -81,11 +91,15 @@ impl<'a> Enum<'a> {
             .iter()
             .map(Variant::from_syn)
             .collect::<Result<_>>()?;
+        let display = parse_flag_attribute(&derive_input.attrs, "l10n_display")?;
+        let keys = parse_flag_attribute(&derive_input.attrs, "l10n_keys")?;
 
         Ok(Enum {
             derive_input,
             variants,
             l10n_attribute,
+            display,
+            keys,
         })
     }
 }
@@ -102,79 +116,27 @@ impl<'a> Variant<'a> {
     }
 }
 
-fn parse_l10n_attribute(attrs: &[Attribute]) -> Result<Option<L10nAttribute<'_>>> {
-    let mut l10n_attribute: Option<L10nAttribute> = None;
+// An attribute without arguments like `#[l10n_display]`.
+fn parse_flag_attribute<'a>(attrs: &'a [Attribute], name: &str) -> Result<Option<&'a Attribute>> {
+    let mut flag: Option<&Attribute> = None;
     for attr in attrs {
-        if attr.path.is_ident("l10n_message") {
-            if l10n_attribute.is_some() {
+        if attr.path.is_ident(name) {
+            if flag.is_some() {
                 return Err(Error::new_spanned(
                     attr,
-                    "only one #[l10n_message(...)] attribute is allowed",
+                    format!("only one #[{}] attribute is allowed", name),
                 ));
             }
-            l10n_attribute = Some(_parse_l10n_attribute(attr)?);
-        }
-    }
-    Ok(l10n_attribute)
-}
-
-fn _parse_l10n_attribute(attr: &Attribute) -> Result<L10nAttribute<'_>> {
-    syn::custom_keyword!(transparent);
-
-    attr.parse_args_with(|input: ParseStream| {
-        let mut l10n_attribute = L10nAttribute {
-            attribute: Some(attr),
-            transparent: None,
-            self_lifetime: None,
-            first_literal: None,
-            second_literal: None,
-            arguments: Default::default(),
-        };
-
-        l10n_attribute.transparent = input
-            .parse::<Option<transparent>>()
-            .map(|r| r.map(|kw| kw.span()))?;
-        if l10n_attribute.transparent.is_some() {
-            return Ok(l10n_attribute);
-        }
-
-        l10n_attribute.self_lifetime = input.parse()?;
-        if input.is_empty() {
-            return Ok(l10n_attribute);
-        } else if l10n_attribute.self_lifetime.is_some() {
-            input.parse::<Token![,]>()?;
-        }
-
-        if !peek_potential_argument(input) {
-            l10n_attribute.first_literal = input.parse()?;
-            if input.is_empty() {
-                return Ok(l10n_attribute);
-            } else if l10n_attribute.first_literal.is_some() {
-                input.parse::<Token![,]>()?;
-            }
-        }
-
-        if !peek_potential_argument(input) {
-            l10n_attribute.second_literal = input.parse()?;
-            if input.is_empty() {
-                return Ok(l10n_attribute);
-            } else if l10n_attribute.second_literal.is_some() {
-                input.parse::<Token![,]>()?;
+            if !attr.tokens.is_empty() {
+                return Err(Error::new_spanned(
+                    &attr.tokens,
+                    format!("#[{}] does not take any argument", name),
+                ));
             }
+            flag = Some(attr);
         }
-
-        if !input.is_empty() {
-            l10n_attribute.arguments = input.parse()?;
-        }
-
-        Ok(l10n_attribute)
-    })
-}
-
-fn peek_potential_argument(input: ParseStream) -> bool {
-    (input.peek(LitStr) && input.peek2(Token![=]))
-        || input.peek(Ident)
-        || (input.peek(Token![*]) && input.peek2(Ident))
+    }
+    Ok(flag)
 }
 
 impl<'a> Field<'a> {
@@ -204,6 +166,10 @@ impl<'a> Field<'a> {
                 .attrs
                 .iter()
                 .find(|attr| attr.path.is_ident("l10n_from")),
+            locale: field_input
+                .attrs
+                .iter()
+                .find(|attr| attr.path.is_ident("l10n_locale")),
         })
     }
 }
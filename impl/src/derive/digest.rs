@@ -1,13 +1,14 @@
 use super::ast::{Enum, Input, Struct, Variant};
-use super::{field_to_ident, Field};
-use crate::ast::{MessageArgs, MessageKey};
-use crate::valid::validate_l10n;
+use super::{field_to_ident, unoptional_type, Field};
+use crate::instance::CONFIG;
+use crate::valid::{validate_argument_types, validate_l10n};
+use l10n_core::ast::{MessageArgs, MessageKey};
 use proc_macro2::{Span, TokenStream, TokenTree};
 use syn::spanned::Spanned;
 use syn::{Attribute, DeriveInput, Error, Ident, Lifetime, LitStr, Result};
 
 pub enum Digest<'a> {
-    Struct(StructDigest<'a>),
+    Struct(Box<StructDigest<'a>>),
     Enum(EnumDigest<'a>),
 }
 
@@ -16,7 +17,9 @@ pub struct StructDigest<'a> {
     pub fields: Vec<Field<'a>>,
     pub self_lifetime: Option<Lifetime>,
     pub from_field: Option<Field<'a>>,
+    pub locale_field: Option<Field<'a>>,
     pub message: Message,
+    pub warnings: TokenStream,
 }
 
 pub struct EnumDigest<'a> {
@@ -30,6 +33,7 @@ pub struct VariantDigest<'a> {
     pub fields: Vec<Field<'a>>,
     pub from_field: Option<Field<'a>>,
     pub message: Message,
+    pub warnings: TokenStream,
 }
 
 pub enum Message {
@@ -46,7 +50,7 @@ pub enum Message {
 impl<'a> Digest<'a> {
     pub fn from_input(input: Input<'a>) -> Result<Digest<'a>> {
         match input {
-            Input::Struct(input) => Ok(Digest::Struct(StructDigest::from_input(input)?)),
+            Input::Struct(input) => Ok(Digest::Struct(Box::new(StructDigest::from_input(input)?))),
             Input::Enum(input) => Ok(Digest::Enum(EnumDigest::from_input(input)?)),
         }
     }
@@ -55,6 +59,7 @@ impl<'a> Digest<'a> {
 impl<'a> StructDigest<'a> {
     fn from_input(input: Struct<'a>) -> Result<StructDigest<'a>> {
         let from = get_from(&input.fields)?;
+        let locale = get_locale(&input.fields)?;
 
         if let Some(span) = input.l10n_attribute.transparent {
             return if input.fields.len() == 1 {
@@ -64,7 +69,9 @@ impl<'a> StructDigest<'a> {
                     fields: input.fields,
                     self_lifetime: input.l10n_attribute.self_lifetime,
                     from_field: from,
+                    locale_field: locale,
                     message: Message::Transparent { field },
+                    warnings: TokenStream::new(),
                 })
             } else {
                 Err(Error::new(
@@ -81,38 +88,65 @@ impl<'a> StructDigest<'a> {
             )
         })?;
 
+        if let Some(key) = &input.l10n_attribute.key {
+            return Err(key_only_on_enums(key));
+        }
+
         let argument_ts = input.l10n_attribute.arguments.first_to_token_stream();
-        let resource = input.l10n_attribute.first_literal.ok_or_else(|| {
-            missing_literal_message(l10n_attribute, &argument_ts, "resource", "main")
-        })?;
-        let key = input
-            .l10n_attribute
-            .second_literal
-            .ok_or_else(|| {
-                missing_literal_message(l10n_attribute, &argument_ts, "key", "id.attribute")
-            })?
-            .into();
+        let (resource, key) = match (
+            input.l10n_attribute.first_literal,
+            input.l10n_attribute.second_literal,
+        ) {
+            (Some(resource), Some(key)) => (resource, key.into()),
+            (Some(key), None) => match default_resource(key.span())? {
+                Some(resource) => (resource, key.into()),
+                None => {
+                    return Err(missing_literal_message(
+                        l10n_attribute,
+                        &argument_ts,
+                        "key",
+                        "id.attribute",
+                    ))
+                }
+            },
+            (None, _) => {
+                return Err(missing_literal_message(
+                    l10n_attribute,
+                    &argument_ts,
+                    "resource",
+                    "main",
+                ))
+            }
+        };
 
         let arguments = input.l10n_attribute.arguments;
         arguments.validate()?;
 
-        validate_l10n(
+        let mut warnings = validate_l10n(
             &resource,
             &key,
             &arguments,
             attribute_closing_span(l10n_attribute),
         )?;
+        warnings.extend(validate_argument_types(
+            &resource,
+            &key,
+            &arguments,
+            &field_types(&input.fields),
+        ));
 
         Ok(StructDigest {
             derive_input: input.derive_input,
             fields: input.fields,
             self_lifetime: input.l10n_attribute.self_lifetime,
             from_field: from,
+            locale_field: locale,
             message: Message::Params {
                 resource,
                 key,
                 arguments,
             },
+            warnings,
         })
     }
 }
@@ -120,11 +154,46 @@ impl<'a> StructDigest<'a> {
 impl<'a> EnumDigest<'a> {
     fn from_input(mut input: Enum<'a>) -> Result<EnumDigest<'a>> {
         input.l10n_attribute.arguments.validate_for_enum()?;
+
+        // A single literal on the enum is the resource, `key = "..."` sets the
+        // key and uses the default resource unless a resource is given. With a
+        // default resource a single literal is rejected, it is a key on structs.
+        if let Some(key) = input.l10n_attribute.key.take() {
+            if let Some(literal) = &input.l10n_attribute.second_literal {
+                return Err(Error::new_spanned(
+                    literal,
+                    r#"the key is already set with `key = "..."`"#,
+                ));
+            }
+            if input.l10n_attribute.first_literal.is_none() {
+                let resource = default_resource(key.span())?.ok_or_else(|| {
+                    Error::new_spanned(
+                        &key,
+                        r#"missing l10n resource, set it before `key = "..."` or configure a `default_resource`"#,
+                    )
+                })?;
+                input.l10n_attribute.first_literal = Some(resource);
+            }
+            input.l10n_attribute.second_literal = Some(key);
+        } else if input.l10n_attribute.first_literal.is_none() {
+            input.l10n_attribute.first_literal = default_resource(Span::call_site())?;
+        } else if let (Some(literal), None) = (
+            &input.l10n_attribute.first_literal,
+            &input.l10n_attribute.second_literal,
+        ) {
+            if default_resource(literal.span())?.is_some() {
+                return Err(Error::new_spanned(
+                    literal,
+                    r#"ambiguous single literal with a default resource configured, set the resource and the key, or the key with `key = "..."`"#,
+                ));
+            }
+        }
         let input_variants = std::mem::take(&mut input.variants);
         let variants = input_variants
             .into_iter()
             .map(|variant_input| VariantDigest::from_input(variant_input, &input))
-            .collect::<Result<_>>()?;
+            .collect::<Result<Vec<_>>>()?;
+        check_from_sources(&variants)?;
 
         Ok(EnumDigest {
             derive_input: input.derive_input,
@@ -137,6 +206,12 @@ impl<'a> EnumDigest<'a> {
 impl<'a> VariantDigest<'a> {
     fn from_input(variant_input: Variant<'a>, enum_input: &Enum<'a>) -> Result<VariantDigest<'a>> {
         let from = get_from(&variant_input.fields)?;
+        if let Some(attribute) = variant_input.fields.iter().find_map(|field| field.locale) {
+            return Err(Error::new_spanned(
+                attribute,
+                "#[l10n_locale] is only supported on structs",
+            ));
+        }
 
         if let Some(span) = variant_input.l10n_attribute.transparent.or(
             match variant_input.l10n_attribute.attribute {
@@ -151,6 +226,7 @@ impl<'a> VariantDigest<'a> {
                     fields: variant_input.fields,
                     from_field: from,
                     message: Message::Transparent { field },
+                    warnings: TokenStream::new(),
                 })
             } else {
                 Err(Error::new(
@@ -160,6 +236,10 @@ impl<'a> VariantDigest<'a> {
             };
         }
 
+        if let Some(key) = &variant_input.l10n_attribute.key {
+            return Err(key_only_on_enums(key));
+        }
+
         if let Some(self_lifetime) = variant_input.l10n_attribute.self_lifetime {
             return Err(Error::new_spanned(
                 self_lifetime,
@@ -209,10 +289,12 @@ impl<'a> VariantDigest<'a> {
         };
 
         let mut arguments = variant_input.l10n_attribute.arguments;
+        // The enum arguments are set in each match arm where `self` is in scope,
+        // so a value like `self.gender()` is shared by all the variants.
         arguments.merge_enum_arguments(&enum_input.l10n_attribute.arguments);
         arguments.validate()?;
 
-        validate_l10n(
+        let mut warnings = validate_l10n(
             &resource,
             &key,
             &arguments,
@@ -222,6 +304,12 @@ impl<'a> VariantDigest<'a> {
                 .map(attribute_closing_span)
                 .unwrap_or_else(|| variant_input.variant_input.ident.span()),
         )?;
+        warnings.extend(validate_argument_types(
+            &resource,
+            &key,
+            &arguments,
+            &field_types(&variant_input.fields),
+        ));
 
         Ok(VariantDigest {
             variant_input: variant_input.variant_input,
@@ -232,10 +320,18 @@ impl<'a> VariantDigest<'a> {
                 key,
                 arguments,
             },
+            warnings,
         })
     }
 }
 
+fn field_types<'a>(fields: &[Field<'a>]) -> Vec<(Ident, &'a syn::Type)> {
+    fields
+        .iter()
+        .map(|field| (field_to_ident(field), field.ty))
+        .collect()
+}
+
 fn get_from<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
     let mut from: Option<Field> = None;
 
@@ -247,6 +343,12 @@ fn get_from<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
                     "duplicate #[l10n_message_from] attribute",
                 ));
             }
+            if fields.len() > 1 {
+                return Err(Error::new_spanned(
+                    attribute,
+                    "#[l10n_from] requires the field to be the only one, the generated `From` implementation cannot set the other fields",
+                ));
+            }
             from = Some(field.clone());
         }
     }
@@ -254,6 +356,64 @@ fn get_from<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
     Ok(from)
 }
 
+// Two variants from the same source type would generate conflicting `From`
+// implementations.
+fn check_from_sources(variants: &[VariantDigest]) -> Result<()> {
+    let mut sources: Vec<(String, &Attribute)> = vec![];
+    for field in variants
+        .iter()
+        .filter_map(|variant| variant.from_field.as_ref())
+    {
+        let attribute = field.from.unwrap();
+        let source = unoptional_type(field.ty).to_string();
+        if let Some((_, first)) = sources.iter().find(|(other, _)| *other == source) {
+            let mut error = Error::new_spanned(
+                attribute,
+                "duplicate #[l10n_from] source type, the generated `From` implementations would conflict",
+            );
+            error.combine(Error::new_spanned(
+                first,
+                "first #[l10n_from] with this source type",
+            ));
+            return Err(error);
+        }
+        sources.push((source, attribute));
+    }
+    Ok(())
+}
+
+fn get_locale<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
+    let mut locale: Option<Field> = None;
+
+    for field in fields {
+        if let Some(attribute) = field.locale {
+            if locale.is_some() {
+                return Err(Error::new_spanned(
+                    attribute,
+                    "duplicate #[l10n_locale] attribute",
+                ));
+            }
+            locale = Some(field.clone());
+        }
+    }
+
+    Ok(locale)
+}
+
+fn key_only_on_enums(key: &LitStr) -> Error {
+    Error::new_spanned(key, r#"`key = "..."` is only supported on enums"#)
+}
+
+fn default_resource(span: Span) -> Result<Option<LitStr>> {
+    let config = CONFIG
+        .as_ref()
+        .map_err(|err| Error::new(Span::call_site(), err))?;
+    Ok(config
+        .default_resource
+        .as_ref()
+        .map(|resource| LitStr::new(resource, span)))
+}
+
 fn missing_literal_message(
     attribute: &Attribute,
     argument_ts: &Option<TokenStream>,
@@ -1,11 +1,12 @@
 use self::ast::{Field, Input};
 use self::digest::{Digest, EnumDigest, Message, StructDigest};
-use proc_macro2::{Span, TokenStream};
+use l10n_core::ast::{MessageArgs, MessageKey};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
-    Data, DeriveInput, GenericArgument, GenericParam, Ident, Lifetime, LifetimeDef, Member,
-    PathArguments, Result, Type, Visibility,
+    Attribute, Data, DeriveInput, GenericArgument, GenericParam, Ident, Lifetime, LifetimeDef,
+    LitBool, LitStr, Member, PathArguments, Result, Type, Visibility,
 };
 
 mod ast;
@@ -13,13 +14,107 @@ mod digest;
 
 pub fn expand(derive_input: DeriveInput) -> Result<TokenStream> {
     let input = Input::from_syn(&derive_input)?;
-    Ok(match Digest::from_input(input)? {
-        Digest::Struct(digest) => expand_struct(digest),
+    let display_impl = input
+        .display()
+        .map(|attr| expand_display(&derive_input, attr));
+    let keys = input.keys();
+    let digest = Digest::from_input(input)?;
+    let keys_impl = keys.map(|attr| expand_keys(&digest, attr));
+    let registry = if cfg!(feature = "registry") {
+        Some(expand_registry(&digest))
+    } else {
+        None
+    };
+    let l10n_impl = match digest {
+        Digest::Struct(digest) => expand_struct(*digest),
         Digest::Enum(digest) => expand_enum(digest),
+    };
+    Ok(quote! {
+        #l10n_impl
+        #display_impl
+        #keys_impl
+        #registry
     })
 }
 
+// Transparent messages are registered by the type of their field.
+fn expand_registry(digest: &Digest) -> TokenStream {
+    let (derive_input, messages) = match digest {
+        Digest::Struct(digest) => (digest.derive_input, vec![&digest.message]),
+        Digest::Enum(digest) => (
+            digest.derive_input,
+            digest
+                .variants
+                .iter()
+                .map(|variant| &variant.message)
+                .collect(),
+        ),
+    };
+    let type_name = derive_input.ident.to_string();
+    let submissions = messages.into_iter().filter_map(|message| match message {
+        Message::Params { resource, key, .. } => Some(quote! {
+            ::l10n::inventory::submit! {
+                ::l10n::RegisteredMessage {
+                    type_name: concat!(module_path!(), "::", #type_name),
+                    resource: #resource,
+                    key: #key,
+                }
+            }
+        }),
+        Message::Transparent { .. } => None,
+    });
+    quote!(#(#submissions)*)
+}
+
+fn expand_display(derive_input: &DeriveInput, attr: &Attribute) -> TokenStream {
+    let ty = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    quote_spanned! {attr.span()=>
+        impl #impl_generics std::fmt::Display for #ty #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let locale = crate::L10N.locales.primary();
+                f.write_str(&::l10n::L10nMessage::translate(self, locale))
+            }
+        }
+    }
+}
+
+// The keys of a transparent message are the ones of the field type, which
+// must also derive `#[l10n_keys]`.
+fn expand_keys(digest: &Digest, attr: &Attribute) -> TokenStream {
+    let (derive_input, messages) = match digest {
+        Digest::Struct(digest) => (digest.derive_input, vec![(&digest.message, &digest.fields)]),
+        Digest::Enum(digest) => (
+            digest.derive_input,
+            digest
+                .variants
+                .iter()
+                .map(|variant| (&variant.message, &variant.fields))
+                .collect(),
+        ),
+    };
+    let ty = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let keys = messages.into_iter().map(|(message, fields)| match message {
+        Message::Params { resource, key, .. } => quote!(std::iter::once((#resource, #key))),
+        Message::Transparent { .. } => {
+            let field_ty = fields[0].ty;
+            quote!(<#field_ty as ::l10n::L10nMessageKeys>::message_keys())
+        }
+    });
+    quote_spanned! {attr.span()=>
+        impl #impl_generics ::l10n::L10nMessageKeys for #ty #ty_generics #where_clause {
+            fn message_keys() -> std::vec::Vec<(&'static str, &'static str)> {
+                let mut keys = std::vec::Vec::new();
+                #(keys.extend(#keys);)*
+                keys
+            }
+        }
+    }
+}
+
 fn expand_struct(digest: StructDigest) -> TokenStream {
+    let self_lifetime = digest.self_lifetime.clone();
     let TraitData {
         impl_generics,
         impl_trait,
@@ -30,7 +125,10 @@ fn expand_struct(digest: StructDigest) -> TokenStream {
         original_impl_generics,
     } = get_trait_data(digest.derive_input, digest.self_lifetime);
     let pat = fields_pat(&digest.fields);
-    let translate_method_body = expand_translate_method_body(&digest.message, pat);
+    let warnings = digest.warnings;
+    let context = digest.derive_input.ident.to_string();
+    let translate_method_body =
+        expand_translate_method_body(&digest.message, &digest.fields, pat, &context);
     let translate_method = quote! {
         fn try_translate_with_args(
             &#l10n_self_lifetime self,
@@ -60,11 +158,31 @@ fn expand_struct(digest: StructDigest) -> TokenStream {
         }
     });
 
+    // The message is translated to the locale it carries.
+    let localized_impl = digest.locale_field.map(|field| {
+        let vis = &digest.derive_input.vis;
+        let locale_member = &field.member;
+        let self_ref = match &self_lifetime {
+            Some(lifetime) => quote!(&#lifetime self),
+            None => quote!(&self),
+        };
+        quote! {
+            impl #original_impl_generics #ty #ty_generics #where_clause {
+                #[allow(dead_code)]
+                #vis fn localized(#self_ref) -> std::borrow::Cow<'_, str> {
+                    ::l10n::L10nMessage::translate(self, &self.#locale_member)
+                }
+            }
+        }
+    });
+
     quote! {
         impl #impl_generics #impl_trait for #ty #ty_generics #where_clause {
             #translate_method
         }
         #from_impl
+        #localized_impl
+        #warnings
     }
 }
 
@@ -80,9 +198,11 @@ fn expand_enum(digest: EnumDigest) -> TokenStream {
     } = get_trait_data(digest.derive_input, digest.l10n_self_lifetime);
 
     let mut from_impls: Vec<TokenStream> = vec![];
+    let mut warnings: Vec<TokenStream> = vec![];
 
     let variant_arms = digest.variants.into_iter().map(|variant| {
         let ident = &variant.variant_input.ident;
+        warnings.push(variant.warnings);
         if let Some(field) = variant.from_field {
             let from = unoptional_type(field.ty);
             let from_member = &field.member;
@@ -102,7 +222,9 @@ fn expand_enum(digest: EnumDigest) -> TokenStream {
             });
         }
 
-        let translate_method_body = expand_translate_method_body(&variant.message, None);
+        let context = format!("{}::{}", digest.derive_input.ident, ident);
+        let translate_method_body =
+            expand_translate_method_body(&variant.message, &variant.fields, None, &context);
         let pat = fields_pat(&variant.fields);
         quote!(#ty::#ident #pat => { #translate_method_body },)
     });
@@ -125,6 +247,7 @@ fn expand_enum(digest: EnumDigest) -> TokenStream {
             #translate_method
         }
         #(#from_impls)*
+        #(#warnings)*
     }
 }
 
@@ -216,6 +339,25 @@ fn spanned_impl_trait(input: &DeriveInput, args_lifetime: &Lifetime) -> TokenStr
     quote!(#path #impl_trait)
 }
 
+// The field an argument value is made of, i.e. `field` or `.field`.
+fn value_field<'a, 'b>(value: &TokenStream, fields: &'b [Field<'a>]) -> Option<&'b Field<'a>> {
+    let mut tokens = value.clone().into_iter();
+    let ident = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ident)), None) => ident,
+        _ => return None,
+    };
+    fields.iter().find(|field| field_to_ident(field) == ident)
+}
+
+// A `FluentValue` field is bound by reference, it is cloned instead of being
+// converted (which is cheap when the value borrows its content).
+fn is_fluent_value_field(value: &TokenStream, fields: &[Field]) -> bool {
+    value_field(value, fields).map_or(false, |field| match field.ty {
+        Type::Path(ty) => ty.path.segments.last().unwrap().ident == "FluentValue",
+        _ => false,
+    })
+}
+
 fn type_is_option(ty: &Type) -> bool {
     type_parameter_of_option(ty).is_some()
 }
@@ -251,13 +393,25 @@ fn type_parameter_of_option(ty: &Type) -> Option<&Type> {
     }
 }
 
-fn expand_translate_method_body(l10n: &Message, pat: Option<TokenStream>) -> TokenStream {
+fn expand_translate_method_body(
+    l10n: &Message,
+    fields: &[Field],
+    pat: Option<TokenStream>,
+    context: &str,
+) -> TokenStream {
     match l10n {
         Message::Transparent { field } => {
             let pat = pat.map(|pat| quote!(let Self #pat = self;));
+            // The inner error is wrapped so the forwarding type or variant is
+            // part of the error chain.
             quote! {
                 #pat
-                #field.try_translate_with_args(locale, args)
+                #field.try_translate_with_args(locale, args).map_err(|source| {
+                    ::l10n::TranslateError::Transparent {
+                        context: #context,
+                        source: std::boxed::Box::new(source),
+                    }
+                })
             }
         }
         Message::Params {
@@ -265,39 +419,87 @@ fn expand_translate_method_body(l10n: &Message, pat: Option<TokenStream>) -> Tok
             key,
             arguments,
         } => {
-            if arguments.is_empty() {
-                quote!(crate::L10N.try_translate_with_args(locale, #resource, #key, args))
-            } else {
-                let local_args_set = arguments.iter().map(|arg| {
-                    let name = arg.name();
-                    let value = arg.value();
-                    quote!(local_args.set(#name, #value);)
-                });
-                let set_local_args = if let Some(pat) = pat {
-                    quote! {
-                        {
-                            let Self #pat = self;
-                            #(#local_args_set)*
-                        }
-                    }
-                } else {
-                    quote!(#(#local_args_set)*)
-                };
-                let local_args = quote! {
-                    let mut local_args = ::l10n::fluent_bundle::FluentArgs::new();
-                    #set_local_args
-                    if let std::option::Option::Some(args) = args {
-                        for (key, value) in args.iter() {
-                            local_args.set(key, value.to_owned());
-                        }
-                    }
-                };
-
-                quote!({
-                    #local_args
-                    crate::L10N.try_translate_with_args(locale, #resource, #key, std::option::Option::Some(&local_args))
-                })
+            let body = expand_params_body(resource, key, arguments, fields, pat);
+            match arguments.locale() {
+                Some(fixed_locale) => quote! {
+                    let locale = {
+                        let _ = locale;
+                        static LOCALE: ::l10n::once_cell::sync::Lazy<::l10n::unic_langid::LanguageIdentifier> =
+                            ::l10n::once_cell::sync::Lazy::new(|| {
+                                #fixed_locale.parse().expect("unexpected error parsing a locale")
+                            });
+                        &*LOCALE
+                    };
+                    #body
+                },
+                None => body,
             }
         }
     }
 }
+
+fn expand_params_body(
+    resource: &LitStr,
+    key: &MessageKey,
+    arguments: &MessageArgs,
+    fields: &[Field],
+    pat: Option<TokenStream>,
+) -> TokenStream {
+    if arguments.is_empty() {
+        quote!(crate::L10N.try_translate_with_args(locale, #resource, #key, args))
+    } else {
+        let skip_none = arguments.skip_none().map_or(false, LitBool::value);
+        let local_args_set = arguments.iter().filter_map(|arg| {
+            let name = arg.name();
+            let value = arg.value()?;
+            let is_option_field =
+                value_field(value, fields).map_or(false, |field| type_is_option(field.ty));
+            Some(if skip_none && is_option_field {
+                quote! {
+                    if let std::option::Option::Some(value) = #value {
+                        local_args.set(#name, value);
+                    }
+                }
+            } else if is_fluent_value_field(value, fields) {
+                quote!(local_args.set(#name, #value.clone());)
+            } else {
+                quote!(local_args.set(#name, #value);)
+            })
+        });
+        // Aliases are set last to also receive the overriding arguments.
+        let local_aliases_set = arguments.iter().filter_map(|arg| {
+            let name = arg.name();
+            let target = arg.alias_target()?;
+            Some(quote! {
+                if let std::option::Option::Some(value) = local_args.get(#target).cloned() {
+                    local_args.set(#name, value);
+                }
+            })
+        });
+        let set_local_args = if let Some(pat) = pat {
+            quote! {
+                {
+                    let Self #pat = self;
+                    #(#local_args_set)*
+                }
+            }
+        } else {
+            quote!(#(#local_args_set)*)
+        };
+        let local_args = quote! {
+            let mut local_args = ::l10n::fluent_bundle::FluentArgs::new();
+            #set_local_args
+            if let std::option::Option::Some(args) = args {
+                for (key, value) in args.iter() {
+                    local_args.set(key, value.to_owned());
+                }
+            }
+            #(#local_aliases_set)*
+        };
+
+        quote!({
+            #local_args
+            crate::L10N.try_translate_with_args(locale, #resource, #key, std::option::Option::Some(&local_args))
+        })
+    }
+}
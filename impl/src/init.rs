@@ -1,5 +1,6 @@
-use crate::instance::L10N;
-use l10n_core::config::get_config;
+use crate::instance::{skip_validation, CONFIG, L10N};
+use l10n_core::builtins;
+use l10n_core::unic_langid::LanguageIdentifier;
 use proc_macro2::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
@@ -7,10 +8,12 @@ use std::collections::HashSet;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{braced, Error, Expr, LitStr, Result, Token};
+use syn::{braced, parenthesized, token, Error, Expr, LitBool, LitStr, Result, Token};
 
 pub fn expand(input: InitInput) -> Result<TokenStream> {
-    let config = get_config().map_err(|err| Error::new(Span::call_site(), err))?;
+    let config = CONFIG
+        .as_ref()
+        .map_err(|err| Error::new(Span::call_site(), err))?;
 
     let l10n_instance = L10N
         .as_ref()
@@ -37,16 +40,67 @@ pub fn expand(input: InitInput) -> Result<TokenStream> {
     let config_path = config
         .path()
         .map_err(|err| Error::new(Span::call_site(), err))?;
-    let builder_path = config_path.to_string_lossy();
+    let builder_path = match &config.runtime_path {
+        Some(runtime_path) => {
+            let runtime_path = runtime_path.to_string_lossy();
+            quote!(::l10n::replace_exe_dir_var(#runtime_path))
+        }
+        None => {
+            let config_path = config_path.to_string_lossy();
+            quote!(#config_path)
+        }
+    };
+
+    let ignore = (!config.ignore.is_empty()).then(|| {
+        let patterns = &config.ignore;
+        quote!(.set_ignore(std::vec![#(std::string::String::from(#patterns)),*]))
+    });
+
+    let shared = (!config.shared.is_empty()).then(|| {
+        let names = &config.shared;
+        quote!(.set_shared_resources(std::vec![#(std::string::String::from(#names)),*]))
+    });
+
+    let complete = (!config.complete.is_empty()).then(|| {
+        let names = &config.complete;
+        quote!(.set_complete_resources(std::vec![#(std::string::String::from(#names)),*]))
+    });
+
+    let primary_locale = config.primary.as_ref().map(|primary| {
+        let primary = primary.to_string();
+        quote! {
+            .set_primary_locale(std::option::Option::Some(
+                #primary.parse().expect("unexpected error parsing a locale"),
+            ))
+        }
+    });
 
     let transform = input
         .transform
         .map(|transform| quote!(.set_transform(#transform)));
 
+    let locale_transforms = input.transforms.map(|transforms| {
+        let set_transforms = transforms.iter().map(|locale_transform| {
+            let locale = &locale_transform.locale;
+            let transform = &locale_transform.transform;
+            quote! {
+                .set_transform_for(
+                    #locale.parse().expect("unexpected error parsing a locale"),
+                    #transform,
+                )
+            }
+        });
+        quote!(#(#set_transforms)*)
+    });
+
     let formatter = input
         .formatter
         .map(|formatter| quote!(.set_formatter(#formatter)));
 
+    let postprocess = input
+        .postprocess
+        .map(|postprocess| quote!(.set_postprocess(#postprocess)));
+
     let use_isolating = input
         .use_isolating
         .map(|use_isolating| quote!(.set_use_isolating(#use_isolating)));
@@ -55,82 +109,194 @@ pub fn expand(input: InitInput) -> Result<TokenStream> {
         let add_functions = functions.iter().map(|function_input| {
             let name = &function_input.name;
             let function = &function_input.function;
-            quote!(.add_function(#name, #function))
+            if function_input.with_locale {
+                quote!(.add_function_with_locale(#name, #function))
+            } else {
+                quote!(.add_function(#name, #function))
+            }
+        });
+        quote!(#(#add_functions)*)
+    });
+
+    let add_bundle_functions = input.bundle_functions.map(|functions| {
+        let add_functions = functions.iter().map(|function_input| {
+            let name = &function_input.name;
+            let function = &function_input.function;
+            quote!(.add_bundle_function(#name, #function))
         });
         quote!(#(#add_functions)*)
     });
 
     let translator = quote! {
         {
-            ::l10n::L10nBuilder::parse(#builder_path, #builder_locales)
-                .expect("error parsing translation files")
+            std::result::Result::Ok(::l10n::L10nBuilder::default()
+                #ignore
+                .parse_path(#builder_path, #builder_locales)?
+                #shared
+                #complete
+                #primary_locale
                 #transform
+                #locale_transforms
                 #formatter
+                #postprocess
                 #use_isolating
                 #add_functions
-                .build()
-                .expect("error building translator")
+                #add_bundle_functions
+                .build()?)
         }
     };
 
+    // The result is kept so `ensure_initialized` can return the error
+    // instead of panicking on the first access to `L10N`. The `option_env!`
+    // compiles the crate again when `L10N_SKIP_VALIDATION` changes.
     Ok(quote! {
-        pub static L10N: ::l10n::once_cell::sync::Lazy<::l10n::L10n> = ::l10n::once_cell::sync::Lazy::new(|| #translator);
+        const _: std::option::Option<&str> = std::option_env!("L10N_SKIP_VALIDATION");
+
+        static L10N_RESULT: ::l10n::once_cell::sync::Lazy<
+            std::result::Result<::l10n::L10n, ::l10n::InitError>,
+        > = ::l10n::once_cell::sync::Lazy::new(|| #translator);
+
+        pub static L10N: ::l10n::once_cell::sync::Lazy<&'static ::l10n::L10n> =
+            ::l10n::once_cell::sync::Lazy::new(|| match &*L10N_RESULT {
+                std::result::Result::Ok(l10n) => l10n,
+                std::result::Result::Err(err) => panic!("error initializing translator: {}", err),
+            });
+
+        /// Parses and builds the translations, to fail at startup instead of
+        /// on the first translation.
+        pub fn ensure_initialized() -> std::result::Result<(), &'static ::l10n::InitError> {
+            L10N_RESULT.as_ref().map(|_| ())
+        }
     })
 }
 
 #[derive(Default)]
 pub struct InitInput {
     pub transform: Option<Expr>,
+    pub transforms: Option<Punctuated<LocaleTransform, Token![,]>>,
     pub formatter: Option<Expr>,
+    pub postprocess: Option<Expr>,
     pub use_isolating: Option<Expr>,
     pub functions_key: Option<Ident>,
     pub functions: Option<Punctuated<Function, Token![,]>>,
+    pub bundle_functions_key: Option<Ident>,
+    pub bundle_functions: Option<Punctuated<Function, Token![,]>>,
+    pub check_plural_categories: Option<LitBool>,
 }
 
 pub enum Field {
     Formatter(Ident, Expr),
     Transform(Ident, Expr),
+    Transforms(Ident, Punctuated<LocaleTransform, Token![,]>),
+    Postprocess(Ident, Expr),
     UseIsolating(Ident, Expr),
     Functions(Ident, Punctuated<Function, Token![,]>),
+    BundleFunctions(Ident, Punctuated<Function, Token![,]>),
+    CheckPluralCategories(Ident, LitBool),
 }
 
 pub struct Function {
     pub name: LitStr,
+    // `"NAME"(locale): function`, the function also receives the locale
+    pub with_locale: bool,
     pub function: Expr,
 }
 
+// `"fr": transform`, the transform of a single locale
+pub struct LocaleTransform {
+    pub locale: LitStr,
+    pub transform: Expr,
+}
+
 impl InitInput {
     pub fn validate(&self) -> Result<()> {
-        if let Some(functions) = &self.functions {
-            let mut duplicate_error: Option<Error> = None;
-            let mut visited_functions: HashSet<&LitStr> = HashSet::new();
-
-            for function in functions {
-                if !visited_functions.contains(&&function.name) {
-                    visited_functions.insert(&function.name);
-                } else {
-                    let err = Error::new_spanned(&function.name, "function duplicate");
-                    match duplicate_error {
-                        Some(ref mut duplicate_error) => duplicate_error.combine(err),
-                        _ => duplicate_error = Some(err),
-                    }
+        let all_functions = || {
+            self.functions
+                .iter()
+                .chain(self.bundle_functions.iter())
+                .flatten()
+        };
+
+        if let Some(function) = self
+            .bundle_functions
+            .iter()
+            .flatten()
+            .find(|function| function.with_locale)
+        {
+            return Err(Error::new_spanned(
+                &function.name,
+                "bundle functions get the locale from the resolver, remove `(locale)`",
+            ));
+        }
+
+        let mut duplicate_error: Option<Error> = None;
+        let mut visited_functions: HashSet<&LitStr> = HashSet::new();
+
+        for function in all_functions() {
+            if !visited_functions.contains(&&function.name) {
+                visited_functions.insert(&function.name);
+            } else {
+                let err = Error::new_spanned(&function.name, "function duplicate");
+                match duplicate_error {
+                    Some(ref mut duplicate_error) => duplicate_error.combine(err),
+                    _ => duplicate_error = Some(err),
                 }
             }
+        }
 
-            if let Some(err) = duplicate_error {
-                return Err(err);
+        if let Some(err) = duplicate_error {
+            return Err(err);
+        }
+
+        let mut transform_locales: Vec<(&LitStr, LanguageIdentifier)> = Vec::new();
+        for locale_transform in self.transforms.iter().flatten() {
+            let locale = &locale_transform.locale;
+            let langid: LanguageIdentifier = locale
+                .value()
+                .parse()
+                .map_err(|err| Error::new_spanned(locale, err))?;
+            if transform_locales
+                .iter()
+                .any(|(_, visited)| *visited == langid)
+            {
+                return Err(Error::new_spanned(locale, "transform duplicate"));
             }
+            transform_locales.push((locale, langid));
+        }
+
+        if skip_validation() {
+            return Ok(());
         }
 
-        let mut missing_functions = L10N
+        let l10n = L10N
             .as_ref()
-            .map_err(|err| Error::new(Span::call_site(), err))?
-            .required_functions();
+            .map_err(|err| Error::new(Span::call_site(), err))?;
 
-        if let Some(functions) = &self.functions {
-            let actual_functions: HashSet<_> = functions.iter().map(|f| f.name.value()).collect();
-            missing_functions.retain(|name| !actual_functions.contains(*name));
-        };
+        if let Some((locale, _)) = transform_locales
+            .iter()
+            .find(|(_, langid)| !l10n.locales.contains(langid))
+        {
+            return Err(Error::new_spanned(
+                locale,
+                format!("unknown locale \"{}\"", locale.value()),
+            ));
+        }
+
+        if let Some(check) = self
+            .check_plural_categories
+            .as_ref()
+            .filter(|check| check.value)
+        {
+            l10n.check_plural_categories()
+                .map_err(|err| Error::new_spanned(check, err))?;
+        }
+
+        let mut missing_functions = l10n.required_functions();
+
+        missing_functions.retain(|name| !builtins::FUNCTIONS.contains(name));
+
+        let actual_functions: HashSet<_> = all_functions().map(|f| f.name.value()).collect();
+        missing_functions.retain(|name| !actual_functions.contains(*name));
 
         if !missing_functions.is_empty() {
             let mut missing_functions: Vec<_> = missing_functions.into_iter().collect();
@@ -138,8 +304,22 @@ impl InitInput {
             let span = self
                 .functions_key
                 .as_ref()
+                .or(self.bundle_functions_key.as_ref())
                 .map(|v| v.span())
                 .unwrap_or_else(Span::call_site);
+            let missing_functions: Vec<_> = missing_functions
+                .into_iter()
+                .map(|name| {
+                    let registered = actual_functions
+                        .iter()
+                        .map(String::as_str)
+                        .chain(builtins::FUNCTIONS.iter().copied());
+                    match closest_function(name, registered) {
+                        Some(closest) => format!("{} (did you mean {}?)", name, closest),
+                        None => name.to_string(),
+                    }
+                })
+                .collect();
             return Err(Error::new(
                 span,
                 format!("missing functions: {}", missing_functions.join(", ")),
@@ -150,6 +330,29 @@ impl InitInput {
     }
 }
 
+// The registered function the closest to `name`, if it looks like a typo.
+fn closest_function<'a>(name: &str, registered: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    registered
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance > 0 && *distance <= 1 + name.len() / 4)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 impl Parse for InitInput {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut init_input = Self::default();
@@ -174,6 +377,20 @@ impl Parse for InitInput {
                             return Err(Error::new_spanned(ident, "duplicate `transform` field"));
                         }
                     }
+                    Field::Transforms(ident, transforms) => {
+                        if init_input.transforms.is_none() {
+                            init_input.transforms = Some(transforms);
+                        } else {
+                            return Err(Error::new_spanned(ident, "duplicate `transforms` field"));
+                        }
+                    }
+                    Field::Postprocess(ident, postprocess) => {
+                        if init_input.postprocess.is_none() {
+                            init_input.postprocess = Some(postprocess);
+                        } else {
+                            return Err(Error::new_spanned(ident, "duplicate `postprocess` field"));
+                        }
+                    }
                     Field::UseIsolating(ident, use_isolating) => {
                         if init_input.use_isolating.is_none() {
                             init_input.use_isolating = Some(use_isolating);
@@ -192,6 +409,27 @@ impl Parse for InitInput {
                             return Err(Error::new_spanned(ident, "duplicate `functions` field"));
                         }
                     }
+                    Field::BundleFunctions(ident, functions) => {
+                        if init_input.bundle_functions.is_none() {
+                            init_input.bundle_functions_key = Some(ident);
+                            init_input.bundle_functions = Some(functions);
+                        } else {
+                            return Err(Error::new_spanned(
+                                ident,
+                                "duplicate `bundle_functions` field",
+                            ));
+                        }
+                    }
+                    Field::CheckPluralCategories(ident, check) => {
+                        if init_input.check_plural_categories.is_none() {
+                            init_input.check_plural_categories = Some(check);
+                        } else {
+                            return Err(Error::new_spanned(
+                                ident,
+                                "duplicate `check_plural_categories` field",
+                            ));
+                        }
+                    }
                 }
             }
         }
@@ -210,6 +448,15 @@ impl Parse for Field {
         match ident.to_string().as_str() {
             "formatter" => Ok(Self::Formatter(ident, input.parse()?)),
             "transform" => Ok(Self::Transform(ident, input.parse()?)),
+            "transforms" => {
+                let content;
+                braced!(content in input);
+                Ok(Self::Transforms(
+                    ident,
+                    content.parse_terminated(LocaleTransform::parse)?,
+                ))
+            }
+            "postprocess" => Ok(Self::Postprocess(ident, input.parse()?)),
             "use_isolating" => Ok(Self::UseIsolating(ident, input.parse()?)),
             "functions" => {
                 let content;
@@ -219,9 +466,18 @@ impl Parse for Field {
                     content.parse_terminated(Function::parse)?,
                 ))
             }
+            "bundle_functions" => {
+                let content;
+                braced!(content in input);
+                Ok(Self::BundleFunctions(
+                    ident,
+                    content.parse_terminated(Function::parse)?,
+                ))
+            }
+            "check_plural_categories" => Ok(Self::CheckPluralCategories(ident, input.parse()?)),
             _ => Err(Error::new_spanned(
                 ident,
-                r#"invalid field (expected: "formatter", "transform", "use_isolating" or "functions")"#,
+                r#"invalid field (expected: "formatter", "transform", "transforms", "postprocess", "use_isolating", "functions", "bundle_functions" or "check_plural_categories")"#,
             )),
         }
     }
@@ -230,8 +486,33 @@ impl Parse for Field {
 impl Parse for Function {
     fn parse(input: ParseStream) -> Result<Self> {
         let name = input.parse()?;
+        let with_locale = input.peek(token::Paren);
+        if with_locale {
+            let content;
+            parenthesized!(content in input);
+            let ident: Ident = content.parse()?;
+            if ident != "locale" || !content.is_empty() {
+                return Err(Error::new_spanned(
+                    ident,
+                    r#"expected `locale`, example: `"DATE"(locale): date`"#,
+                ));
+            }
+        }
         input.parse::<Token![:]>()?;
         let function = input.parse()?;
-        Ok(Self { name, function })
+        Ok(Self {
+            name,
+            with_locale,
+            function,
+        })
+    }
+}
+
+impl Parse for LocaleTransform {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let locale = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let transform = input.parse()?;
+        Ok(Self { locale, transform })
     }
 }
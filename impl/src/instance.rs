@@ -1,9 +1,11 @@
-use l10n_core::config::{get_config, ConfigError};
+use l10n_core::config::{get_config, Config, ConfigError};
 use l10n_core::l10n::{BuildErrors, L10n, L10nBuilder, ParserError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
-pub enum InitError {
+pub enum InstanceError {
+    #[error(transparent)]
+    ReadConfig(#[from] &'static ConfigError),
     #[error(transparent)]
     Config(#[from] ConfigError),
     #[error(transparent)]
@@ -12,8 +14,29 @@ pub enum InitError {
     Build(#[from] BuildErrors),
 }
 
-pub static L10N: once_cell::sync::Lazy<Result<L10n, InitError>> =
+// Set `L10N_SKIP_VALIDATION` to `1` or `true` to skip checking messages,
+// arguments and functions against the translations, e.g. for faster
+// development builds.
+pub fn skip_validation() -> bool {
+    std::env::var("L10N_SKIP_VALIDATION")
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+// The `l10n.toml` of the crate, read once for all the macro calls.
+pub static CONFIG: once_cell::sync::Lazy<Result<Config, ConfigError>> =
+    once_cell::sync::Lazy::new(get_config);
+
+pub static L10N: once_cell::sync::Lazy<Result<L10n, InstanceError>> =
     once_cell::sync::Lazy::new(|| {
-        let config = get_config()?;
-        Ok(L10nBuilder::parse(config.path()?, config.locales)?.build()?)
+        let config = CONFIG.as_ref()?;
+        let path = config.path()?;
+        Ok(L10nBuilder::default()
+            .set_ignore(config.ignore.clone())
+            .parse_path(path, config.locales.clone())?
+            .set_primary_locale(config.primary.clone())
+            .set_shared_resources(config.shared.clone())
+            .set_complete_resources(config.complete.clone())
+            .check_terms(true)
+            .build()?)
     });
@@ -1,10 +1,9 @@
 extern crate proc_macro;
 use init::InitInput;
-use message::MessageInput;
+use message::{AssertTranslationInput, ConstMessageInput, MessageInput};
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
-mod ast;
 mod derive;
 mod init;
 mod instance;
@@ -25,7 +24,24 @@ pub fn message(item: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(L10nMessage, attributes(l10n_message, l10n_from))]
+#[proc_macro]
+pub fn const_message(item: TokenStream) -> TokenStream {
+    message::expand_const(parse_macro_input!(item as ConstMessageInput))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro]
+pub fn assert_translation(item: TokenStream) -> TokenStream {
+    message::expand_assert_translation(parse_macro_input!(item as AssertTranslationInput))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(
+    L10nMessage,
+    attributes(l10n_message, l10n_from, l10n_display, l10n_keys, l10n_locale)
+)]
 pub fn derive_l10n(token: TokenStream) -> TokenStream {
     derive::expand(parse_macro_input!(token as DeriveInput))
         .unwrap_or_else(|err| err.to_compile_error())
@@ -1,9 +1,11 @@
-use crate::ast::{MessageArgs, MessageKey};
 use crate::valid::validate_l10n;
+use l10n_core::ast::{
+    AssertTranslationSyntax, ConstMessageSyntax, MessageArgs, MessageKey, MessageSyntax,
+};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{LitStr, Result, Token};
+use syn::{Error, Expr, LitStr, Result};
 
 pub fn expand(input: MessageInput) -> Result<TokenStream> {
     let resource = input.resource;
@@ -12,28 +14,111 @@ pub fn expand(input: MessageInput) -> Result<TokenStream> {
     let args = if input.arguments.is_empty() {
         quote!(std::option::Option::None)
     } else {
-        let set_args = input.arguments.iter().map(|arg| {
+        let set_args = input.arguments.iter().filter_map(|arg| {
             let name = arg.name();
-            let value = arg.value();
-            quote!(args.set(#name, #value);)
+            let value = arg.value()?;
+            Some(quote!(args.set(#name, #value);))
+        });
+        let set_aliases = input.arguments.iter().filter_map(|arg| {
+            let name = arg.name();
+            let target = arg.alias_target()?;
+            Some(quote! {
+                if let std::option::Option::Some(value) = args.get(#target).cloned() {
+                    args.set(#name, value);
+                }
+            })
         });
 
         quote! {
             {
                 let mut args = ::l10n::fluent_bundle::FluentArgs::new();
                 #(#set_args)*
+                #(#set_aliases)*
                 std::option::Option::Some(args)
             }
         }
     };
 
-    Ok(quote! {
+    let message = quote! {
         ::l10n::Message::new(
             &crate::L10N,
             #resource,
             #key,
             #args
         )
+    };
+
+    let mut items = input.warnings;
+    items.extend(expand_used(&resource, &key));
+    Ok(if items.is_empty() {
+        message
+    } else {
+        quote!({
+            #items
+            #message
+        })
+    })
+}
+
+pub fn expand_const(input: ConstMessageInput) -> Result<TokenStream> {
+    let resource = input.resource;
+    let key = input.key;
+    let message = quote! {
+        ::l10n::StaticMessage::new(|| &*crate::L10N, #resource, #key)
+    };
+
+    Ok(match expand_used(&resource, &key) {
+        Some(used) => quote!({
+            #used
+            #message
+        }),
+        None => message,
+    })
+}
+
+// Records the message for `l10n::used_messages()`.
+fn expand_used(resource: &LitStr, key: &MessageKey) -> Option<TokenStream> {
+    cfg!(feature = "registry").then(|| {
+        quote! {
+            ::l10n::inventory::submit! {
+                ::l10n::UsedMessage { resource: #resource, key: #key }
+            }
+        }
+    })
+}
+
+// The translation is compared at runtime since functions and formatting are
+// only available at runtime, the message is still checked at compile time.
+pub fn expand_assert_translation(input: AssertTranslationInput) -> Result<TokenStream> {
+    let resource = input.message.resource.value();
+    let key = input.message.key.value();
+    let locale = input.locale;
+    let expected = input.expected;
+    let message = expand(input.message)?;
+
+    Ok(quote! {
+        {
+            let message = #message;
+            let locale: &::l10n::unic_langid::LanguageIdentifier = #locale;
+            let translation = ::l10n::L10nMessage::try_translate(&message, locale);
+            match translation {
+                std::result::Result::Ok(translation) => std::assert_eq!(
+                    translation,
+                    #expected,
+                    "translation of resource: {:?} and key: {:?} for locale: {}",
+                    #resource,
+                    #key,
+                    locale
+                ),
+                std::result::Result::Err(err) => std::panic!(
+                    "translation of resource: {:?} and key: {:?} for locale: {} failed: {}",
+                    #resource,
+                    #key,
+                    locale,
+                    err
+                ),
+            }
+        }
     })
 }
 
@@ -41,27 +126,101 @@ pub struct MessageInput {
     pub resource: LitStr,
     pub key: MessageKey,
     pub arguments: MessageArgs,
+    pub warnings: TokenStream,
 }
 
 impl Parse for MessageInput {
     fn parse(input: ParseStream) -> Result<Self> {
-        let resource = input.parse()?;
-        input.parse::<Token![,]>()?;
-        let key: MessageKey = input.parse::<LitStr>()?.into();
-
-        if !input.is_empty() {
-            input.parse::<Token![,]>()?;
-        }
-
-        let arguments: MessageArgs = input.parse()?;
+        let MessageSyntax {
+            resource,
+            key,
+            arguments,
+        } = input.parse()?;
         arguments.validate()?;
+        reject_derive_options(&arguments)?;
 
-        validate_l10n(&resource, &key, &arguments, key.span())?;
+        let warnings = validate_l10n(&resource, &key, &arguments, key.span())?;
 
         Ok(Self {
             resource,
             key,
             arguments,
+            warnings,
         })
     }
 }
+
+pub struct ConstMessageInput {
+    pub resource: LitStr,
+    pub key: MessageKey,
+}
+
+impl Parse for ConstMessageInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ConstMessageSyntax { resource, key } = input.parse()?;
+
+        validate_l10n(&resource, &key, &MessageArgs::default(), key.span())?;
+
+        Ok(Self { resource, key })
+    }
+}
+
+pub struct AssertTranslationInput {
+    pub message: MessageInput,
+    pub locale: Expr,
+    pub expected: Expr,
+}
+
+impl Parse for AssertTranslationInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let AssertTranslationSyntax {
+            message:
+                MessageSyntax {
+                    resource,
+                    key,
+                    arguments,
+                },
+            locale,
+            expected,
+        } = input.parse()?;
+        arguments.validate()?;
+        reject_derive_options(&arguments)?;
+
+        let warnings = validate_l10n(&resource, &key, &arguments, key.span())?;
+
+        Ok(Self {
+            message: MessageInput {
+                resource,
+                key,
+                arguments,
+                warnings,
+            },
+            locale,
+            expected,
+        })
+    }
+}
+
+// `skip_none` applies to the `Option` fields of a derived type and `locale`
+// to the type translations, `kebab_case` to the shorthand fields.
+fn reject_derive_options(arguments: &MessageArgs) -> Result<()> {
+    if let Some(skip_none) = arguments.skip_none() {
+        return Err(Error::new_spanned(
+            skip_none,
+            "`skip_none` is only supported by #[l10n_message(...)]",
+        ));
+    }
+    if let Some(locale) = arguments.locale() {
+        return Err(Error::new_spanned(
+            locale,
+            "`locale` is only supported by #[l10n_message(...)]",
+        ));
+    }
+    if let Some(kebab_case) = arguments.kebab_case() {
+        return Err(Error::new_spanned(
+            kebab_case,
+            "`kebab_case` is only supported by #[l10n_message(...)]",
+        ));
+    }
+    Ok(())
+}
@@ -1,41 +1,84 @@
-use crate::ast::{MessageArgs, MessageKey};
-use crate::instance::L10N;
+use crate::instance::{skip_validation, L10N};
+use l10n_core::ast::{MessageArgs, MessageKey};
 use l10n_core::l10n::TranslateError;
-use proc_macro2::Span;
-use quote::ToTokens;
+use l10n_core::unic_langid::LanguageIdentifier;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{quote_spanned, ToTokens};
 use std::collections::HashSet;
-use syn::{Error, LitStr, Result};
+use syn::{Error, LitStr, Result, Type};
 
+// Returns the tokens to emit in order to warn about unused arguments (or an
+// error if the feature flag "deny-unused-arguments" is set).
 pub fn validate_l10n(
     resource: &LitStr,
     key: &MessageKey,
     arguments: &MessageArgs,
     span_missing: Span,
-) -> Result<()> {
-    let required_arguments = L10N
+) -> Result<TokenStream> {
+    if skip_validation() {
+        return Ok(TokenStream::new());
+    }
+    let mut locales_required_arguments = L10N
         .as_ref()
         .map_err(|err| Error::new(Span::call_site(), err))?
-        .required_variables(&resource.value(), &key.value())
+        .locales_required_variables(&resource.value(), &key.value())
         .map_err(|err| match err {
             TranslateError::ResourceNotExists(_) => Error::new_spanned(&resource, err),
             TranslateError::MessageIdNotExists { .. } => Error::new(key.id_span(), err),
             _ => Error::new_spanned(&key, err),
         })?;
+    // A message translated to a fixed locale only needs the arguments of it.
+    if let Some(locale) = arguments.locale() {
+        let fixed_locale: LanguageIdentifier = locale
+            .value()
+            .parse()
+            .map_err(|err| Error::new_spanned(locale, err))?;
+        if !locales_required_arguments.contains_key(&fixed_locale) {
+            return Err(Error::new_spanned(
+                locale,
+                format!("locale {} is not a main locale", locale.to_token_stream()),
+            ));
+        }
+        locales_required_arguments.retain(|locale, _| **locale == fixed_locale);
+    }
+    let required_arguments: HashSet<&str> = locales_required_arguments
+        .values()
+        .flatten()
+        .copied()
+        .collect();
 
     if arguments.is_complete() {
         let actual_arguments: HashSet<_> = arguments.iter().map(|arg| arg.name().value()).collect();
         let mut missing_arguments: Vec<_> = required_arguments
-            .into_iter()
+            .iter()
+            .copied()
             .filter(|name| !actual_arguments.contains(*name))
             .collect();
 
         if !missing_arguments.is_empty() {
             missing_arguments.sort();
+            let missing_arguments: Vec<_> = missing_arguments
+                .into_iter()
+                .map(|name| {
+                    // Only name the locales when the argument is not required by all of them.
+                    let mut locales: Vec<_> = locales_required_arguments
+                        .iter()
+                        .filter(|(_, arguments)| arguments.contains(name))
+                        .map(|(locale, _)| locale.to_string())
+                        .collect();
+                    if locales.len() == locales_required_arguments.len() {
+                        format!(r#""{}""#, name)
+                    } else {
+                        locales.sort();
+                        format!(r#""{}" (required by: {})"#, name, locales.join(", "))
+                    }
+                })
+                .collect();
             return Err(Error::new(
                 span_missing,
                 format!(
-                    r#"missing arguments: "{}" for resource: {} and key: {}"#,
-                    missing_arguments.join("\", \""),
+                    r#"missing arguments: {} for resource: {} and key: {}"#,
+                    missing_arguments.join(", "),
                     resource.to_token_stream(),
                     key.to_token_stream()
                 ),
@@ -43,5 +86,121 @@ pub fn validate_l10n(
         }
     }
 
-    Ok(())
+    // An argument only used through its aliases is not unused.
+    let aliased_arguments: HashSet<_> = arguments
+        .iter()
+        .filter_map(|arg| arg.alias_target())
+        .map(|target| target.value())
+        .collect();
+
+    let mut unused_error: Option<Error> = None;
+    let mut warnings = TokenStream::new();
+    for argument in arguments.iter_own() {
+        let name = argument.name();
+        if required_arguments.contains(name.value().as_str())
+            || aliased_arguments.contains(&name.value())
+        {
+            continue;
+        }
+
+        let message = format!(
+            "unused argument {} for resource: {} and key: {}",
+            name.to_token_stream(),
+            resource.to_token_stream(),
+            key.to_token_stream()
+        );
+
+        if cfg!(feature = "deny-unused-arguments") {
+            let err = Error::new_spanned(argument.to_token_stream(), message);
+            match unused_error {
+                Some(ref mut unused_error) => unused_error.combine(err),
+                _ => unused_error = Some(err),
+            }
+        } else {
+            // There is no stable way to emit a warning from a proc macro,
+            // using a deprecated item does the trick.
+            warnings.extend(quote_spanned! {name.span()=>
+                const _: () = {
+                    #[deprecated(note = #message)]
+                    struct UnusedArgument;
+                    let _ = UnusedArgument;
+                };
+            });
+        }
+    }
+
+    match unused_error {
+        Some(err) => Err(err),
+        None => Ok(warnings),
+    }
+}
+
+// Returns the tokens to emit in order to warn about string fields passed as an
+// argument formatted as a number (e.g. `NUMBER($count)`). This is a best
+// effort, only the arguments set with a field as is have a known type.
+pub fn validate_argument_types(
+    resource: &LitStr,
+    key: &MessageKey,
+    arguments: &MessageArgs,
+    fields: &[(Ident, &Type)],
+) -> TokenStream {
+    if skip_validation() {
+        return TokenStream::new();
+    }
+    let l10n = match L10N.as_ref() {
+        Ok(l10n) => l10n,
+        Err(_) => return TokenStream::new(),
+    };
+    let numeric_variables = match l10n.numeric_variables(&resource.value(), &key.value()) {
+        Ok(numeric_variables) => numeric_variables,
+        Err(_) => return TokenStream::new(),
+    };
+
+    let mut warnings = TokenStream::new();
+    for argument in arguments.iter_own() {
+        let name = argument.name();
+        if !numeric_variables.contains(name.value().as_str()) {
+            continue;
+        }
+        let mut tokens = match argument.value() {
+            Some(value) => value.clone().into_iter(),
+            None => continue,
+        };
+        let ident = match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Ident(ident)), None) => ident,
+            _ => continue,
+        };
+        if !fields
+            .iter()
+            .any(|(field, ty)| *field == ident && is_string_type(ty))
+        {
+            continue;
+        }
+
+        let message = format!(
+            "argument {} is a string but it is formatted as a number for resource: {} and key: {}",
+            name.to_token_stream(),
+            resource.to_token_stream(),
+            key.to_token_stream()
+        );
+        warnings.extend(quote_spanned! {name.span()=>
+            const _: () = {
+                #[deprecated(note = #message)]
+                struct StringAsNumberArgument;
+                let _ = StringAsNumberArgument;
+            };
+        });
+    }
+    warnings
+}
+
+fn is_string_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_string_type(&reference.elem),
+        Type::Path(ty) => match ty.path.segments.last() {
+            Some(segment) => segment.ident == "String" || segment.ident == "str",
+            None => false,
+        },
+        _ => false,
+    }
 }
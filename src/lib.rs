@@ -145,16 +145,42 @@ pub use l10n_core::fluent_bundle;
 pub use l10n_core::intl_memoizer;
 pub use l10n_core::unic_langid;
 
-pub use l10n_core::l10n::{L10n, L10nBuilder, TranslateError};
-pub use l10n_core::l10n_message::L10nMessage;
-pub use l10n_core::locales::Locales;
-pub use l10n_core::message::Message;
+pub use l10n_core::config::replace_exe_dir_var;
+#[cfg(feature = "extract")]
+pub use l10n_core::extract;
+pub use l10n_core::l10n::{
+    FormatError, InitError, L10n, L10nBuilder, MemoryEstimate, MessageInfo, MessageProvenance,
+    TranslateError,
+};
+pub use l10n_core::l10n_message::{
+    strip_isolation, translate_map, L10nMessage, L10nMessageKeys, Localized,
+};
+pub use l10n_core::locales::{Locales, LocalesBuilder};
+pub use l10n_core::message::{Message, StaticMessage};
+pub use l10n_core::pseudo::PseudoOptions;
+#[cfg(feature = "registry")]
+pub use l10n_core::registry::{
+    inventory, registered_messages, used_messages, RegisteredMessage, UsedMessage,
+};
+pub use l10n_core::resolver::BundleResolver;
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+pub use l10n_core::watch::WatchedL10n;
 pub use l10n_core::UNEXPECTED_MESSAGE;
 
 pub use l10n_impl::*;
 
 #[macro_export]
 macro_rules! message_args {
+    (..$base:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        {
+            let mut args: $crate::fluent_bundle::FluentArgs = $crate::fluent_bundle::FluentArgs::new();
+            for (key, value) in ($base).iter() {
+                args.set(key, value.clone());
+            }
+            $(args.set($key, $value);)*
+            args
+        }
+    };
     ($($key:expr => $value:expr),* $(,)?) => {
         {
             let mut args: $crate::fluent_bundle::FluentArgs = $crate::fluent_bundle::FluentArgs::new();
@@ -164,6 +190,36 @@ macro_rules! message_args {
     };
 }
 
+/// Sets the arguments on an existing `&mut FluentArgs` instead of building a
+/// new one like [`message_args!`].
+#[macro_export]
+macro_rules! merge_args {
+    ($args:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        {
+            let args: &mut $crate::fluent_bundle::FluentArgs = $args;
+            $(args.set($key, $value);)*
+        }
+    };
+}
+
+/// Same as `message!` but the resource and the key are runtime expressions,
+/// e.g. keys from a `Vec<&str>`, so nothing is checked at compile time.
+/// The arguments are given like for [`message_args!`].
+#[macro_export]
+macro_rules! message_dyn {
+    ($l10n:expr, $resource:expr, $key:expr $(,)?) => {
+        $crate::Message::new($l10n, $resource, $key, std::option::Option::None)
+    };
+    ($l10n:expr, $resource:expr, $key:expr, $($args:tt)+) => {
+        $crate::Message::new(
+            $l10n,
+            $resource,
+            $key,
+            std::option::Option::Some($crate::message_args!($($args)+)),
+        )
+    };
+}
+
 #[cfg(doctest)]
 mod test_readme {
     macro_rules! external_doc_test {
@@ -34,6 +34,7 @@ fn ui() {
 
         for entry in entry_path.read_dir().unwrap() {
             env::remove_var("L10N_PATH_ENV");
+            env::remove_var("L10N_SKIP_VALIDATION");
 
             let entry_path = entry.unwrap().path();
             if let Some(name) = entry_path.file_name().map(|name| name.to_string_lossy()) {
@@ -44,6 +45,21 @@ fn ui() {
                                 continue;
                             }
                         }
+                        "deny-unused-arguments" => {
+                            if cfg!(not(feature = "deny-unused-arguments")) {
+                                continue;
+                            }
+                        }
+                        "builtins" => {
+                            if cfg!(not(feature = "builtins")) {
+                                continue;
+                            }
+                        }
+                        "registry" => {
+                            if cfg!(not(feature = "registry")) {
+                                continue;
+                            }
+                        }
                         _ => {
                             unimplemented!("unrecognized feature flag {}", feature);
                         }
@@ -70,6 +86,10 @@ fn ui() {
                     env::set_var("L10N_PATH_ENV", s.trim());
                 }
 
+                if entry_path.join(".l10n_skip_validation").exists() {
+                    env::set_var("L10N_SKIP_VALIDATION", "1");
+                }
+
                 entry_path
                     .read_dir()
                     .unwrap()
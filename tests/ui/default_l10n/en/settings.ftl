@@ -0,0 +1,3 @@
+status =
+    .online = Available
+    .offline = Away
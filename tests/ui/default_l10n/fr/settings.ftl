@@ -0,0 +1,3 @@
+status =
+    .online = Disponible
+    .offline = Absent
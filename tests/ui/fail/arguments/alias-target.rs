@@ -0,0 +1,12 @@
+use l10n::message;
+
+l10n::init!();
+
+fn main() {
+    let _ = message!(
+        "home",
+        "welcome",
+        "first-name" = "Alice",
+        alias("last-name" = "name")
+    );
+}
@@ -0,0 +1,8 @@
+use l10n::unic_langid::langid;
+
+l10n::init!();
+
+fn main() {
+    let en = langid!("en");
+    l10n::assert_translation!("home", "welcome", &en, "first-name" = "Alan" => "Welcome");
+}
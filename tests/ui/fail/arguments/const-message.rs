@@ -0,0 +1,7 @@
+use l10n::{const_message, StaticMessage};
+
+l10n::init!();
+
+const WELCOME: StaticMessage = const_message!("home", "welcome");
+
+fn main() {}
@@ -0,0 +1,8 @@
+use l10n::message;
+
+l10n::init!();
+
+fn main() {
+    // "last-name" is only used by the "fr" message (and "fr-CA" falling back to "fr").
+    let _ = message!("home", "welcome", "first-name" = "Alan");
+}
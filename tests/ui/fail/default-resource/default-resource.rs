@@ -0,0 +1,12 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+#[derive(L10nMessage)]
+#[l10n_message("settings")]
+enum Status {
+    #[l10n_message("status.online")]
+    Online,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {}
+
+#[derive(L10nMessage)]
+#[l10n_display("fr")]
+#[l10n_message("home", "state.online")]
+struct Online;
@@ -0,0 +1,23 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", key = "state")]
+struct Online;
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state", key = "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message(key = "state")]
+enum Status {
+    #[l10n_message(".online")]
+    Online,
+}
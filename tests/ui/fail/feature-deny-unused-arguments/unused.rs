@@ -0,0 +1,28 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let _ = l10n::message!(
+        "home",
+        "welcome",
+        "first-name" = "Alan",
+        "last-name" = "Turing",
+        "extra" = "value"
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.online", "extra" = "value", other)]
+struct Online {
+    other: &'static str,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state", "reason" = "Working", "gender" = "other")]
+enum State {
+    #[l10n_message(".online", "extra" = "value")]
+    Online,
+    #[l10n_message(".busy")]
+    Busy,
+}
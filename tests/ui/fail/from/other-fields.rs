@@ -0,0 +1,20 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy", "reason" = reason, "gender" = gender)]
+struct StructBusy {
+    #[l10n_from]
+    reason: String,
+    gender: String,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".busy", "reason" = .0, "gender" = .1)]
+    Busy(#[l10n_from] String, String),
+}
@@ -0,0 +1,19 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".busy", "reason" = .0, "gender" = "other")]
+    Busy(#[l10n_from] String),
+    #[l10n_message(".offline")]
+    Offline,
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Away {
+        #[l10n_from]
+        reason: Option<String>,
+    },
+}
@@ -0,0 +1,2 @@
+time = { TIEM() }
+day = { DAY() }
@@ -0,0 +1,7 @@
+l10n::init!({
+    transforms: {
+        "de": |s| std::borrow::Cow::from(s.to_uppercase()),
+    }
+});
+
+fn main() {}
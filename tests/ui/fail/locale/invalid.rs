@@ -0,0 +1,27 @@
+use l10n::unic_langid::LanguageIdentifier;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy", reason, "gender" = "other")]
+struct Notification {
+    #[l10n_locale]
+    locale: LanguageIdentifier,
+    #[l10n_locale]
+    fallback: LanguageIdentifier,
+    reason: String,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Busy {
+        #[l10n_locale]
+        locale: LanguageIdentifier,
+        reason: String,
+    },
+}
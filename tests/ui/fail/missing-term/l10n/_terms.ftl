@@ -0,0 +1 @@
+-brand = Chat App
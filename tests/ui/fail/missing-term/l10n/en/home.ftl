@@ -0,0 +1,2 @@
+welcome = Welcome on { -brand }
+about = About { -company }
@@ -0,0 +1,2 @@
+welcome = Bienvenue sur { -brand }
+about = À propos de { -company }
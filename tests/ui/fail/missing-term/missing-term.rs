@@ -0,0 +1,3 @@
+l10n::init!();
+
+fn main() {}
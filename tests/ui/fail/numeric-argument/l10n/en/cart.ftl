@@ -0,0 +1,2 @@
+items = { NUMBER($count) } items ({ $label })
+total = Total: { $total }
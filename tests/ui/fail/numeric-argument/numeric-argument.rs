@@ -0,0 +1,41 @@
+#![deny(deprecated)]
+
+use l10n::fluent_bundle::{FluentArgs, FluentValue};
+use l10n::L10nMessage;
+
+fn number<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    positional.get(0).cloned().unwrap_or(FluentValue::Error)
+}
+
+l10n::init!({
+    functions: {
+        "NUMBER": number
+    }
+});
+
+#[derive(L10nMessage)]
+#[l10n_message("cart", "items", count, label)]
+struct Items {
+    count: String,
+    label: String,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("cart", "items", count, "label" = "items")]
+struct CountedItems {
+    count: usize,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("cart", "total", total)]
+struct Total {
+    total: String,
+}
+
+#[derive(L10nMessage)]
+enum Cart {
+    #[l10n_message("cart", "items", "count" = .0, "label" = .1)]
+    Items(String, String),
+}
+
+fn main() {}
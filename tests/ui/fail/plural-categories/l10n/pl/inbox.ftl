@@ -0,0 +1,4 @@
+emails = { $count ->
+    [one] Jeden e-mail
+   *[other] { $count } e-maili
+}
@@ -0,0 +1,5 @@
+l10n::init!({
+    check_plural_categories: true,
+});
+
+fn main() {}
@@ -0,0 +1,29 @@
+use l10n::unic_langid::langid;
+use l10n::{message, message_args, L10nMessage};
+
+l10n::init!({ use_isolating: false });
+
+fn main() {
+    let greeting = Greeting {
+        user: "Alice".to_string(),
+    };
+    assert_eq!(greeting.translate(&langid!("en")), "Hello Alice!");
+    assert_eq!(greeting.translate(&langid!("fr")), "Bonjour Alice !");
+
+    // Overriding the aliased argument also overrides its aliases.
+    let args = message_args!("name" => "Bob");
+    assert_eq!(
+        greeting.translate_with_args(&langid!("fr"), Some(&args)),
+        "Bonjour Bob !"
+    );
+
+    let message = message!("profile", "greeting", "name" = "Carol", alias("prenom" = "name"));
+    assert_eq!(message.translate(&langid!("en")), "Hello Carol!");
+    assert_eq!(message.translate(&langid!("fr")), "Bonjour Carol !");
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("profile", "greeting", "name" = user.as_str(), alias("prenom" = "name"))]
+struct Greeting {
+    user: String,
+}
@@ -0,0 +1 @@
+greeting = Hello { $name }!
@@ -0,0 +1 @@
+greeting = Bonjour { $prenom } !
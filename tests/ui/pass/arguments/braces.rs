@@ -0,0 +1,31 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    // Argument values are never parsed as Fluent, braces are kept as is.
+    let welcome = l10n::message!(
+        "home",
+        "welcome",
+        "first-name" = "{ $last-name }",
+        "last-name" = "{ -app }"
+    );
+
+    assert_eq!(
+        welcome.translate(&langid!("en")),
+        "Welcome \u{2068}{ $last-name }\u{2069} on Chat App!"
+    );
+    assert_eq!(
+        welcome.translate(&langid!("fr")),
+        "Bienvenue \u{2068}{ $last-name }\u{2069} \u{2068}{ -app }\u{2069} sur chat app."
+    );
+
+    assert_eq!(
+        welcome.translate_with_args(
+            &langid!("en"),
+            Some(&l10n::message_args!("first-name" => "{$x}"))
+        ),
+        "Welcome \u{2068}{$x}\u{2069} on Chat App!"
+    );
+}
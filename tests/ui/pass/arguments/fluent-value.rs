@@ -0,0 +1,47 @@
+use l10n::fluent_bundle::FluentValue;
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let first_name = "Alan".to_string();
+    // Converted once, cloning a borrowed `FluentValue` is cheap.
+    let first_name = FluentValue::from(first_name.as_str());
+    let last_name = FluentValue::from("Turing");
+
+    let welcome = l10n::message!(
+        "home",
+        "welcome",
+        "first-name" = first_name.clone(),
+        "last-name" = last_name.clone()
+    );
+    assert_eq!(
+        welcome.translate(&langid!("fr")),
+        "Bienvenue \u{2068}Alan\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+
+    assert_eq!(
+        welcome.translate_with_args(
+            &langid!("en"),
+            Some(&l10n::message_args!("first-name" => first_name.clone()))
+        ),
+        "Welcome \u{2068}Alan\u{2069} on Chat App!"
+    );
+
+    let welcome = Welcome {
+        first_name: first_name.clone(),
+        last_name,
+    };
+    assert_eq!(
+        welcome.translate(&langid!("fr")),
+        "Bienvenue \u{2068}Alan\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message('a, "home", "welcome", "first-name" = first_name, "last-name" = last_name)]
+struct Welcome<'a> {
+    first_name: FluentValue<'a>,
+    last_name: FluentValue<'a>,
+}
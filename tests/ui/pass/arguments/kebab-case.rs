@@ -0,0 +1,41 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let welcome = Welcome {
+        first_name: "Alan".to_string(),
+        last_name: "Turing".to_string(),
+    };
+    assert_eq!(
+        welcome.translate(&langid!("fr")),
+        "Bienvenue \u{2068}Alan\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+
+    let message = Message::Welcome {
+        first_name: "Alan".to_string(),
+        last_name: "Turing".to_string(),
+    };
+    assert_eq!(
+        message.translate(&langid!("fr")),
+        "Bienvenue \u{2068}Alan\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "welcome", first_name, last_name, kebab_case = true)]
+struct Welcome {
+    first_name: String,
+    last_name: String,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", kebab_case = true)]
+enum Message {
+    #[l10n_message("welcome", first_name, last_name)]
+    Welcome {
+        first_name: String,
+        last_name: String,
+    },
+}
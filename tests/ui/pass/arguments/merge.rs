@@ -0,0 +1,25 @@
+use l10n::unic_langid::langid;
+use l10n::{merge_args, message_args, L10nMessage};
+
+l10n::init!();
+
+fn main() {
+    let welcome = l10n::message!(
+        "home",
+        "welcome",
+        "first-name" = "Ada",
+        "last-name" = "Lovelace"
+    );
+
+    let mut args = message_args!("first-name" => "Alan");
+    set_last_name(&mut args);
+    merge_args!(&mut args, "first-name" => "John",);
+    assert_eq!(
+        welcome.translate_with_args(&langid!("fr"), Some(&args)),
+        "Bienvenue \u{2068}John\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+}
+
+fn set_last_name(args: &mut l10n::fluent_bundle::FluentArgs) {
+    merge_args!(args, "last-name" => "Turing");
+}
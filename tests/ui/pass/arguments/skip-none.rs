@@ -0,0 +1,73 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let state = Busy {
+        reason: "Working".to_string(),
+        gender: Some("female".to_string()),
+    };
+    assert_eq!(
+        state.translate(&langid!("fr")),
+        "\u{2068}Occupée\u{2069} (\u{2068}Working\u{2069})"
+    );
+
+    let state = Busy {
+        reason: "Working".to_string(),
+        gender: None,
+    };
+    assert!(state.try_translate(&langid!("fr")).is_err());
+    assert_eq!(
+        state.translate_with_args(
+            &langid!("fr"),
+            Some(&l10n::message_args!("gender" => "male"))
+        ),
+        "\u{2068}Occupé\u{2069} (\u{2068}Working\u{2069})"
+    );
+
+    let state = State::BusyFor {
+        reason: "Working".to_string(),
+        hours: None,
+        gender: Some("male".to_string()),
+    };
+    assert_eq!(
+        state.translate_with_args(&langid!("fr"), Some(&l10n::message_args!("hours" => 2))),
+        "\u{2068}Occupé\u{2069} pour \u{2068}\u{2068}2\u{2069} heures\u{2069} (\u{2068}Working\u{2069})"
+    );
+
+    let state = Shorthand {
+        skip_none: "Working".to_string(),
+        gender: "female".to_string(),
+    };
+    assert_eq!(
+        state.translate(&langid!("fr")),
+        "\u{2068}Occupée\u{2069} (\u{2068}Working\u{2069})"
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy", reason, gender, skip_none = true)]
+struct Busy {
+    reason: String,
+    gender: Option<String>,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state", skip_none = true)]
+enum State {
+    #[l10n_message(".busy-for", reason, hours, gender)]
+    BusyFor {
+        reason: String,
+        hours: Option<u32>,
+        gender: Option<String>,
+    },
+}
+
+// A bare `skip_none` is the shorthand argument of the field
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy", skip_none, alias("reason" = "skip_none"), gender)]
+struct Shorthand {
+    skip_none: String,
+    gender: String,
+}
@@ -0,0 +1,26 @@
+use l10n::unic_langid::langid;
+use l10n::{message_args, L10nMessage};
+
+l10n::init!();
+
+fn main() {
+    let welcome = l10n::message!(
+        "home",
+        "welcome",
+        "first-name" = "Ada",
+        "last-name" = "Lovelace"
+    );
+
+    let base = message_args!("first-name" => "Alan", "last-name" => "Turing");
+    let args = message_args!(..base, "first-name" => "John");
+    assert_eq!(
+        welcome.translate_with_args(&langid!("fr"), Some(&args)),
+        "Bienvenue \u{2068}John\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+
+    let args = message_args!(..&base);
+    assert_eq!(
+        welcome.translate_with_args(&langid!("fr"), Some(&args)),
+        "Bienvenue \u{2068}Alan\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+}
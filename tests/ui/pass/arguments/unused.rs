@@ -0,0 +1,35 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    // Unused arguments only emit a warning
+    let welcome = l10n::message!(
+        "home",
+        "welcome",
+        "first-name" = "Alan",
+        "last-name" = "Turing",
+        "extra" = "value"
+    );
+    assert_eq!(
+        welcome.translate(&langid!("en")),
+        "Welcome \u{2068}Alan\u{2069} on Chat App!"
+    );
+
+    assert_eq!(Online.translate(&langid!("en")), "Online");
+    assert_eq!(State::Online.translate(&langid!("en")), "Online");
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.online", "extra" = "value")]
+struct Online;
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state", "reason" = "Working", "gender" = "other")]
+enum State {
+    #[l10n_message(".online", "extra" = "value")]
+    Online,
+    #[l10n_message(".busy")]
+    Busy,
+}
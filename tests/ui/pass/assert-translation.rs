@@ -0,0 +1,33 @@
+use l10n::unic_langid::langid;
+use l10n::assert_translation;
+
+l10n::init!();
+
+fn main() {
+    let en = langid!("en");
+    let fr = langid!("fr");
+
+    assert_translation!("home", "state.online", &en => "Online");
+    assert_translation!("home", "state.online", &fr => "En ligne");
+    assert_translation!(
+        "home",
+        "welcome",
+        &en,
+        "first-name" = "Alan",
+        "last-name" = "Turing" => "Welcome \u{2068}Alan\u{2069} on Chat App!"
+    );
+    let first_name = "Alan";
+    assert_translation!(
+        "home",
+        "welcome",
+        &fr,
+        "first-name" = first_name,
+        "last-name" = "Turing",
+        => "Bienvenue \u{2068}Alan\u{2069} \u{2068}Turing\u{2069} sur chat app."
+    );
+
+    let result = std::panic::catch_unwind(|| {
+        assert_translation!("home", "state.online", &langid!("en") => "Offline");
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,26 @@
+use l10n::fluent_bundle::{FluentArgs, FluentValue};
+use l10n::unic_langid::langid;
+use l10n::{BundleResolver, L10nMessage};
+
+fn copyright<'a>(
+    resolver: &BundleResolver,
+    _: &[FluentValue<'a>],
+    _: &FluentArgs,
+) -> FluentValue<'a> {
+    match resolver.format("-brand") {
+        Some(brand) => FluentValue::from(format!("© {}", brand)),
+        None => FluentValue::Error,
+    }
+}
+
+l10n::init!({
+    bundle_functions: {
+        "COPYRIGHT": copyright
+    }
+});
+
+fn main() {
+    let footer = l10n::message!("app", "footer");
+    assert_eq!(footer.translate(&langid!("en")), "© Acme");
+    assert_eq!(footer.translate(&langid!("fr")), "© Acmé");
+}
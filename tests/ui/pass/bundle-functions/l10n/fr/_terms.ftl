@@ -0,0 +1 @@
+-brand = Acmé
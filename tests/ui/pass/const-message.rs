@@ -0,0 +1,13 @@
+use l10n::unic_langid::langid;
+use l10n::{const_message, L10nMessage, StaticMessage};
+
+l10n::init!();
+
+const ONLINE: StaticMessage = const_message!("home", "state.online");
+static OFFLINE: StaticMessage = const_message!("home", "state.offline",);
+
+fn main() {
+    assert_eq!(ONLINE.translate(&langid!("en")), "Online");
+    assert_eq!(ONLINE.translate(&langid!("fr")), "En ligne");
+    assert_eq!(OFFLINE.translate(&langid!("fr")), "Hors ligne");
+}
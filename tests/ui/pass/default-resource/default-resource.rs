@@ -0,0 +1,62 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let welcome = Welcome {
+        first_name: "Alan",
+        last_name: "Turing",
+    };
+    assert_eq!(
+        welcome.translate(&langid!("en")),
+        "Welcome \u{2068}Alan\u{2069} on Chat App!"
+    );
+
+    let state = State::Busy {
+        reason: "Meeting".to_string(),
+    };
+    assert_eq!(
+        state.translate(&langid!("en")),
+        "Busy (\u{2068}Meeting\u{2069})"
+    );
+    assert_eq!(Online.translate(&langid!("fr")), "En ligne");
+    assert_eq!(Other::Offline.translate(&langid!("fr")), "Hors ligne");
+    assert_eq!(Status::Online.translate(&langid!("en")), "Available");
+    assert_eq!(Status::Offline.translate(&langid!("fr")), "Absent");
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("welcome", "first-name" = *first_name, "last-name" = *last_name)]
+struct Welcome {
+    first_name: &'static str,
+    last_name: &'static str,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.online")]
+struct Online;
+
+#[derive(L10nMessage)]
+#[l10n_message(key = "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Busy { reason: String },
+}
+
+#[derive(L10nMessage)]
+enum Other {
+    #[l10n_message("state.offline")]
+    Offline,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("settings", key = "status")]
+enum Status {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".offline")]
+    Offline,
+}
@@ -0,0 +1,29 @@
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let state = State::Busy {
+        reason: "Meeting".to_string(),
+    };
+    println!("{}", state);
+    assert_eq!(state.to_string(), "Busy (\u{2068}Meeting\u{2069})");
+
+    let online = Online;
+    assert_eq!(format!("[{}]", online), "[Online]");
+}
+
+#[derive(L10nMessage)]
+#[l10n_display]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Busy { reason: String },
+}
+
+#[derive(L10nMessage)]
+#[l10n_display]
+#[l10n_message("home", "state.online")]
+struct Online;
@@ -0,0 +1,34 @@
+use l10n::L10nMessageKeys;
+
+l10n::init!();
+
+fn main() {
+    assert_eq!(
+        State::message_keys(),
+        vec![("home", "state.online"), ("home", "state.busy"), ("home", "welcome")]
+    );
+    assert_eq!(
+        <&Welcome<'static>>::message_keys(),
+        vec![("home", "welcome")]
+    );
+    assert!(L10N.check_message_keys(State::message_keys()).is_empty());
+}
+
+#[derive(l10n::L10nMessage)]
+#[l10n_keys]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Busy { reason: String },
+    #[l10n_message(transparent)]
+    Welcome(Welcome<'static>),
+}
+
+#[derive(l10n::L10nMessage)]
+#[l10n_keys]
+#[l10n_message("home", "welcome", "first-name" = *name, "last-name" = "Turing")]
+struct Welcome<'a> {
+    name: &'a str,
+}
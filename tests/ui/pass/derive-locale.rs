@@ -0,0 +1,41 @@
+use l10n::unic_langid::{langid, LanguageIdentifier};
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let notification = Notification {
+        locale: langid!("fr"),
+        reason: "Working".to_string(),
+    };
+    assert_eq!(
+        notification.localized(),
+        notification.translate(&langid!("fr"))
+    );
+    assert_eq!(
+        notification.translate(&langid!("en")),
+        "Busy (\u{2068}Working\u{2069})"
+    );
+
+    let welcome = Welcome {
+        locale: &langid!("en"),
+        name: "Alice",
+    };
+    assert_eq!(welcome.localized(), welcome.translate(&langid!("en")));
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy", reason, "gender" = "other")]
+struct Notification {
+    #[l10n_locale]
+    locale: LanguageIdentifier,
+    reason: String,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message('a, "home", "state.busy", "reason" = *name, "gender" = "other")]
+pub struct Welcome<'a> {
+    #[l10n_locale]
+    locale: &'a LanguageIdentifier,
+    name: &'a str,
+}
@@ -0,0 +1,29 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!({ use_isolating: false });
+
+fn main() {
+    let translations = State::Online.translate_all_locales(&L10N);
+    assert_eq!(translations.len(), 5);
+    assert_eq!(translations[&langid!("en")], "Online");
+    assert_eq!(translations[&langid!("en-GB")], "Online");
+    assert_eq!(translations[&langid!("fr-CA")], "En ligne");
+
+    let busy = State::Busy {
+        reason: "Meeting".to_string(),
+    };
+    assert_eq!(
+        busy.translate_all_locales(&L10N)[&langid!("fr")],
+        "Non disponible (Meeting)"
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Busy { reason: String },
+}
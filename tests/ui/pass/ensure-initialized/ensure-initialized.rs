@@ -0,0 +1,11 @@
+use l10n::InitError;
+
+l10n::init!();
+
+fn main() {
+    // The translations are checked at compile time but the runtime directory
+    // does not exist.
+    let err = ensure_initialized().unwrap_err();
+    assert!(matches!(err, InitError::Parse(_)));
+    assert!(err.to_string().starts_with("impossible to read path"));
+}
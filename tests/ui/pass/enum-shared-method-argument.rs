@@ -0,0 +1,51 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let fr = &langid!("fr");
+
+    let busy = State::Busy {
+        reason: "Travail".to_string(),
+        female: true,
+    };
+    assert_eq!(
+        busy.translate(fr),
+        "\u{2068}Occupée\u{2069} (\u{2068}Travail\u{2069})"
+    );
+
+    let busy_for = State::BusyFor("Travail".to_string(), 2, false);
+    assert_eq!(
+        busy_for.translate(fr),
+        "\u{2068}Occupé\u{2069} pour \u{2068}\u{2068}2\u{2069} heures\u{2069} (\u{2068}Travail\u{2069})"
+    );
+
+    let away = State::Away {
+        reason: "Travail".to_string(),
+    };
+    assert_eq!(
+        away.translate(fr),
+        "\u{2068}Non disponible\u{2069} (\u{2068}Travail\u{2069})"
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state", "gender" = self.gender())]
+enum State {
+    #[l10n_message(".busy", reason)]
+    Busy { reason: String, female: bool },
+    #[l10n_message(".busy-for", "reason" = .0, "hours" = .1)]
+    BusyFor(String, usize, bool),
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Away { reason: String },
+}
+
+impl State {
+    fn gender(&self) -> &'static str {
+        match self {
+            State::Busy { female: true, .. } | State::BusyFor(_, _, true) => "female",
+            _ => "male",
+        }
+    }
+}
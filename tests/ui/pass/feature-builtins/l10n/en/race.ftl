@@ -0,0 +1,6 @@
+place = You finished { ORDINAL($place) ->
+    [one] {$place}st
+    [two] {$place}nd
+    [few] {$place}rd
+   *[other] {$place}th
+}!
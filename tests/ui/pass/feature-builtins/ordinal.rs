@@ -0,0 +1,20 @@
+use l10n::unic_langid::langid;
+use l10n::{message, L10nMessage};
+
+l10n::init!({ use_isolating: false });
+
+fn main() {
+    let en = langid!("en");
+    assert_eq!(
+        message!("race", "place", "place" = 1).translate(&en),
+        "You finished 1st!"
+    );
+    assert_eq!(
+        message!("race", "place", "place" = 22).translate(&en),
+        "You finished 22nd!"
+    );
+    assert_eq!(
+        message!("race", "place", "place" = 13).translate(&en),
+        "You finished 13th!"
+    );
+}
@@ -0,0 +1,45 @@
+use l10n::{registered_messages, RegisteredMessage};
+
+l10n::init!();
+
+fn main() {
+    let mut messages: Vec<_> = registered_messages().collect();
+    messages.sort_by_key(|message| message.key);
+    assert_eq!(
+        messages,
+        [
+            &RegisteredMessage {
+                type_name: concat!(module_path!(), "::State"),
+                resource: "home",
+                key: "state.busy",
+            },
+            &RegisteredMessage {
+                type_name: concat!(module_path!(), "::State"),
+                resource: "home",
+                key: "state.online",
+            },
+            &RegisteredMessage {
+                type_name: concat!(module_path!(), "::Welcome"),
+                resource: "home",
+                key: "welcome",
+            },
+        ]
+    );
+}
+
+#[derive(l10n::L10nMessage)]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".busy", reason, "gender" = "other")]
+    Busy { reason: String },
+    #[l10n_message(transparent)]
+    Welcome(Welcome<'static>),
+}
+
+#[derive(l10n::L10nMessage)]
+#[l10n_message("home", "welcome", "first-name" = *name, "last-name" = "Turing")]
+struct Welcome<'a> {
+    name: &'a str,
+}
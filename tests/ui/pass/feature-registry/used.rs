@@ -0,0 +1,24 @@
+use l10n::{message, used_messages};
+
+l10n::init!();
+
+static OFFLINE: l10n::StaticMessage = l10n::const_message!("home", "state.offline");
+
+fn main() {
+    let _ = message!("home", "state.busy", "reason" = "meeting", "gender" = "other");
+    let _ = &OFFLINE;
+
+    assert_eq!(
+        L10N.report_unused(&used_messages()),
+        [
+            ("home".to_string(), "state.busy-for".to_string()),
+            ("home".to_string(), "state.online".to_string()),
+        ]
+    );
+}
+
+#[derive(l10n::L10nMessage)]
+#[l10n_message("home", "welcome", "first-name" = *name, "last-name" = "Turing")]
+struct Welcome<'a> {
+    name: &'a str,
+}
@@ -0,0 +1,40 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    // Only the arguments required by the fixed locale are needed, `gender` is
+    // only used in French.
+    let busy = Busy {
+        reason: "Working".to_string(),
+    };
+    assert_eq!(
+        busy.translate(&langid!("fr")),
+        "Busy (\u{2068}Working\u{2069})"
+    );
+    assert_eq!(
+        busy.translate(&langid!("en")),
+        "Busy (\u{2068}Working\u{2069})"
+    );
+
+    let state = State::Online;
+    assert_eq!(state.translate(&langid!("en")), "En ligne");
+    let state = State::Offline;
+    assert_eq!(state.translate(&langid!("fr")), "Offline");
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy", reason, locale = "en")]
+struct Busy {
+    reason: String,
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state", locale = "fr")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".offline", locale = "en")]
+    Offline,
+}
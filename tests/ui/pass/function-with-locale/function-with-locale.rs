@@ -0,0 +1,27 @@
+use l10n::fluent_bundle::{FluentArgs, FluentValue};
+use l10n::unic_langid::{langid, LanguageIdentifier};
+use l10n::L10nMessage;
+
+fn date<'a>(
+    locale: &LanguageIdentifier,
+    _: &[FluentValue<'a>],
+    _: &FluentArgs,
+) -> FluentValue<'a> {
+    match locale.region.as_ref().map(|region| region.as_str()) {
+        Some("GB") => FluentValue::from("22/02/2023"),
+        _ => FluentValue::from("02/22/2023"),
+    }
+}
+
+l10n::init!({
+    use_isolating: false,
+    functions: {
+        "DATE"(locale): date
+    }
+});
+
+fn main() {
+    let today = l10n::message!("app", "today");
+    assert_eq!(today.translate(&langid!("en")), "Today is 02/22/2023");
+    assert_eq!(today.translate(&langid!("en-GB")), "Today is 22/02/2023");
+}
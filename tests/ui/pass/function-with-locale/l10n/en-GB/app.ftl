@@ -0,0 +1 @@
+tomorrow = Tomorrow
@@ -0,0 +1 @@
+today = Today is { DATE() }
@@ -0,0 +1,53 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    let online: Slots<'_, 2> = Slots::Online;
+    assert_eq!(online.translate(&langid!("en")), "Online");
+    assert_eq!(online.translate(&langid!("fr")), "En ligne");
+
+    let busy_for = Slots::BusyFor {
+        reasons: ["Working", "Meeting"],
+    };
+    assert_eq!(
+        busy_for.translate(&langid!("en")),
+        "Busy for \u{2068}\u{2068}2\u{2069} hours\u{2069} (\u{2068}Working, Meeting\u{2069})"
+    );
+    assert_eq!(
+        busy_for.translate(&langid!("fr")),
+        "\u{2068}Non disponible\u{2069} pour \u{2068}\u{2068}2\u{2069} heures\u{2069} (\u{2068}Working, Meeting\u{2069})"
+    );
+
+    let busy = Busy::<3> {
+        reason: "Working",
+        hours: [1, 1, 1],
+    };
+    assert_eq!(
+        busy.translate(&langid!("en")),
+        "Busy for \u{2068}\u{2068}3\u{2069} hours\u{2069} (\u{2068}Working\u{2069})"
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message('a, "home", "state")]
+enum Slots<'a, const N: usize>
+where
+    [&'a str; N]: Sized,
+{
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".busy-for", "reason" = reasons.join(", "), "hours" = N, "gender" = "other")]
+    BusyFor { reasons: [&'a str; N] },
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy-for", *reason, "hours" = hours.iter().sum::<usize>(), "gender" = "other")]
+struct Busy<const N: usize>
+where
+    [usize; N]: Sized,
+{
+    reason: &'static str,
+    hours: [usize; N],
+}
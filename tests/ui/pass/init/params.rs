@@ -1,5 +1,5 @@
 use l10n::fluent_bundle::{memoizer::MemoizerKind, FluentArgs, FluentValue};
-use l10n::unic_langid::langid;
+use l10n::unic_langid::{langid, LanguageIdentifier};
 use l10n::L10nMessage;
 use std::borrow::Cow;
 
@@ -14,6 +14,13 @@ fn formatter<M: MemoizerKind>(num: &FluentValue, _intls: &M) -> Option<String> {
     }
 }
 
+fn postprocess(locale: &LanguageIdentifier, translation: String) -> String {
+    match locale.language.as_str() {
+        "fr" => translation.replace(" !", "\u{a0}!"),
+        _ => translation,
+    }
+}
+
 fn lowercase<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
     match positional.get(0) {
         Some(FluentValue::String(n)) => FluentValue::String(Cow::from(n.to_lowercase())),
@@ -25,6 +32,7 @@ fn lowercase<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentV
 l10n::init!({
     transform: Some(transform),
     formatter: Some(formatter),
+    postprocess: Some(postprocess),
     use_isolating: false,
     functions: {
         "LOWERCASE": lowercase,
@@ -51,6 +59,6 @@ fn main() {
     );
     assert_eq!(
         welcome.translate(&langid!("fr")),
-        "Bienvenue alan TURING sur chat app, vous avez déb(ŀ)oqué 1000_f64 points !"
+        "Bienvenue alan TURING sur chat app, vous avez déb(ŀ)oqué 1000_f64 points\u{a0}!"
     );
 }
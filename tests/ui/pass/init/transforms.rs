@@ -0,0 +1,44 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+use std::borrow::Cow;
+
+fn transform(s: &str) -> Cow<str> {
+    Cow::from(s.to_uppercase())
+}
+
+fn fr_transform(s: &str) -> Cow<str> {
+    Cow::from(s.replace("é", "e"))
+}
+
+l10n::init!({
+    transform: Some(transform),
+    transforms: {
+        "fr": fr_transform,
+        "fr-CA": |s| Cow::from(s.replace("o", "0")),
+    },
+    use_isolating: false,
+    functions: {
+        "LOWERCASE": |positional, _named| positional[0].to_owned(),
+        "UPPERCASE": |positional, _named| positional[0].to_owned(),
+    }
+});
+
+fn main() {
+    let first_name = "Alan";
+    let last_name = "Turing";
+    let points = 1000;
+
+    let welcome = l10n::message!("home", "welcome", first_name, last_name, points);
+    assert_eq!(
+        welcome.translate(&langid!("en")),
+        "WELCOME Alan Turing ON CHAT APP, YOU HAVE UNLOCKED 1000 POINTS!"
+    );
+    assert_eq!(
+        welcome.translate(&langid!("fr")),
+        "Bienvenue Alan Turing sur chat app, vous avez debloque 1000 points !"
+    );
+    assert_eq!(
+        welcome.translate(&langid!("fr-CA")),
+        "Bienvenue Alan Turing sur chat app, v0us avez débl0qué 1000 p0ints !"
+    );
+}
@@ -0,0 +1,31 @@
+use l10n::unic_langid::langid;
+use l10n::{L10nMessage, Localized};
+
+l10n::init!();
+
+#[derive(L10nMessage)]
+#[l10n_message("settings", "status")]
+enum Status {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(".offline")]
+    Offline,
+}
+
+#[derive(serde::Serialize)]
+struct Response<'a> {
+    online: Localized<'a, Status>,
+    offline: Localized<'a, Status>,
+}
+
+fn main() {
+    let locale = langid!("fr");
+    let response = Response {
+        online: Localized(&Status::Online, &locale),
+        offline: Localized(&Status::Offline, &locale),
+    };
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"online":"Disponible","offline":"Absent"}"#
+    );
+}
@@ -0,0 +1,31 @@
+use l10n::unic_langid::langid;
+use l10n::{message_dyn, L10nMessage};
+
+l10n::init!();
+
+fn main() {
+    let keys = vec!["state.online", "state.offline"];
+    let menu: Vec<_> = keys
+        .iter()
+        .map(|key| message_dyn!(&L10N, "home", key))
+        .collect();
+    assert_eq!(menu[0].translate(&langid!("en")), "Online");
+    assert_eq!(menu[1].translate(&langid!("fr")), "Hors ligne");
+
+    let resource = String::from("home");
+    let welcome = message_dyn!(
+        &L10N,
+        &resource,
+        "welcome",
+        "first-name" => "Alan",
+        "last-name" => "Turing",
+    );
+    assert_eq!(
+        welcome.translate(&langid!("en")),
+        "Welcome \u{2068}Alan\u{2069} on Chat App!"
+    );
+
+    // Not checked at compile time, the error is returned at runtime.
+    let missing = message_dyn!(&L10N, "home", "missing");
+    assert!(missing.try_translate(&langid!("en")).is_err());
+}
@@ -0,0 +1,15 @@
+use l10n::unic_langid::langid;
+use l10n::{message, L10nMessage};
+
+l10n::init!({
+    use_isolating: false,
+    check_plural_categories: true,
+});
+
+fn main() {
+    assert_eq!(
+        message!("inbox", "emails", "count" = 3).translate(&langid!("en")),
+        "3 emails"
+    );
+    assert!(L10N.check_plural_categories().is_ok());
+}
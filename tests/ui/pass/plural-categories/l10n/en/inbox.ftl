@@ -0,0 +1,4 @@
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
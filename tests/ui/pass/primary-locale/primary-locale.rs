@@ -0,0 +1,12 @@
+use l10n::{message, L10nMessage};
+use l10n::unic_langid::langid;
+
+l10n::init!();
+
+fn main() {
+    let primary = L10N.locales.primary();
+    assert_eq!(primary, &langid!("fr-CA"));
+
+    let online = message!("home", "state.online");
+    assert_eq!(online.translate(primary), "En ligne");
+}
@@ -0,0 +1,19 @@
+use l10n::unic_langid::langid;
+use l10n::L10nMessage;
+
+l10n::init!();
+
+fn main() {
+    // Not checked at compile time, the errors happen when translating
+    let welcome = l10n::message!("home", "welcome");
+    assert!(welcome.try_translate(&langid!("en")).is_err());
+
+    let unknown = l10n::message!("home", "unknown");
+    assert!(unknown.try_translate(&langid!("en")).is_err());
+
+    assert!(Busy.try_translate(&langid!("en")).is_err());
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy")]
+struct Busy;
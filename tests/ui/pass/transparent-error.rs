@@ -0,0 +1,51 @@
+use l10n::unic_langid::langid;
+use l10n::{L10nMessage, TranslateError};
+use std::error::Error;
+
+l10n::init!();
+
+fn main() {
+    let de = &langid!("de");
+
+    let busy: State = Busy(BusyFor {
+        reason: "Working".to_string(),
+    })
+    .into();
+    let err = busy.try_translate(de).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "transparent `State::Busy` failed to translate"
+    );
+
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "transparent `Busy` failed to translate");
+    assert_eq!(
+        err,
+        TranslateError::Transparent {
+            context: "State::Busy",
+            source: Box::new(TranslateError::Transparent {
+                context: "Busy",
+                source: Box::new(TranslateError::LocaleNotSupported { locale: de.clone() }),
+            }),
+        }
+    );
+}
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state")]
+enum State {
+    #[l10n_message(".online")]
+    Online,
+    #[l10n_message(transparent)]
+    Busy(#[l10n_from] Busy),
+}
+
+#[derive(L10nMessage)]
+#[l10n_message(transparent)]
+struct Busy(#[l10n_from] BusyFor);
+
+#[derive(L10nMessage)]
+#[l10n_message("home", "state.busy-for", reason, "hours" = 2, "gender" = "other")]
+struct BusyFor {
+    reason: String,
+}